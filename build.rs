@@ -1,15 +1,23 @@
 fn main() -> Result<(), Box<dyn std::error::Error>> {
     tonic_build::configure()
         .build_server(false)
+        .build_transport(false)
         .out_dir("./src/api/v1_0_x")
         .compile(&["proto/api_v1.0.x.proto"], &["proto"])?;
     tonic_build::configure()
         .build_server(false)
+        .build_transport(false)
         .out_dir("./src/api/v1_1_x")
         .compile(&["proto/api_v1.1.x.proto"], &["proto"])?;
     tonic_build::configure()
         .build_server(false)
+        .build_transport(false)
         .out_dir("./src/api/v21_03_0")
         .compile(&["proto/api_v21.03.x.proto"], &["proto"])?;
+    tonic_build::configure()
+        .build_server(false)
+        .build_transport(false)
+        .out_dir("./src/api/v24_02_0")
+        .compile(&["proto/api_v24.02.x.proto"], &["proto"])?;
     Ok(())
 }
@@ -0,0 +1,115 @@
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use crate::api::facet::ValType;
+use crate::api::Facet;
+
+///
+/// Seconds between January 1, year 1 and Unix epoch. Dgraph stores datetime values in Go binary
+/// time format which counts seconds from year 1.
+///
+const UNIX_TO_INTERNAL: i64 = 62_135_596_800;
+
+///
+/// Typed value of edge facet.
+///
+/// Every variant is encoded into bytes in the same way as Dgraph does it internally.
+///
+#[derive(Clone, Debug, PartialEq)]
+pub enum FacetValue {
+    String(String),
+    Int(i64),
+    Float(f64),
+    Bool(bool),
+    Datetime(SystemTime),
+}
+
+impl FacetValue {
+    fn val_type(&self) -> ValType {
+        match self {
+            FacetValue::String(_) => ValType::String,
+            FacetValue::Int(_) => ValType::Int,
+            FacetValue::Float(_) => ValType::Float,
+            FacetValue::Bool(_) => ValType::Bool,
+            FacetValue::Datetime(_) => ValType::Datetime,
+        }
+    }
+
+    fn into_bytes(self) -> Vec<u8> {
+        match self {
+            FacetValue::String(value) => value.into_bytes(),
+            FacetValue::Int(value) => value.to_le_bytes().to_vec(),
+            FacetValue::Float(value) => value.to_bits().to_le_bytes().to_vec(),
+            FacetValue::Bool(value) => vec![value as u8],
+            FacetValue::Datetime(value) => datetime_to_bytes(value),
+        }
+    }
+
+    pub(crate) fn into_facet<S: Into<String>>(self, key: S) -> Facet {
+        Facet {
+            key: key.into(),
+            val_type: self.val_type() as i32,
+            value: self.into_bytes(),
+            ..Default::default()
+        }
+    }
+}
+
+///
+/// Encode time in UTC into Go `time.Time` binary format (version 1).
+///
+fn datetime_to_bytes(value: SystemTime) -> Vec<u8> {
+    let (secs, nanos) = match value.duration_since(UNIX_EPOCH) {
+        Ok(duration) => (duration.as_secs() as i64, duration.subsec_nanos() as i32),
+        Err(err) => {
+            let duration = err.duration();
+            let secs = -(duration.as_secs() as i64);
+            match duration.subsec_nanos() {
+                0 => (secs, 0),
+                nanos => (secs - 1, (1_000_000_000 - nanos) as i32),
+            }
+        }
+    };
+    let mut bytes = Vec::with_capacity(15);
+    bytes.push(1);
+    bytes.extend_from_slice(&(secs + UNIX_TO_INTERNAL).to_be_bytes());
+    bytes.extend_from_slice(&nanos.to_be_bytes());
+    //offset -1 is used for UTC
+    bytes.extend_from_slice(&(-1i16).to_be_bytes());
+    bytes
+}
+
+impl From<String> for FacetValue {
+    fn from(value: String) -> Self {
+        FacetValue::String(value)
+    }
+}
+
+impl From<&str> for FacetValue {
+    fn from(value: &str) -> Self {
+        FacetValue::String(value.to_string())
+    }
+}
+
+impl From<i64> for FacetValue {
+    fn from(value: i64) -> Self {
+        FacetValue::Int(value)
+    }
+}
+
+impl From<f64> for FacetValue {
+    fn from(value: f64) -> Self {
+        FacetValue::Float(value)
+    }
+}
+
+impl From<bool> for FacetValue {
+    fn from(value: bool) -> Self {
+        FacetValue::Bool(value)
+    }
+}
+
+impl From<SystemTime> for FacetValue {
+    fn from(value: SystemTime) -> Self {
+        FacetValue::Datetime(value)
+    }
+}
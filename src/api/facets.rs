@@ -0,0 +1,180 @@
+use std::collections::HashMap;
+use std::ops::Deref;
+
+use serde::de::{self, DeserializeOwned, Deserializer};
+use serde::Deserialize;
+use serde_json::{Map, Value};
+
+///
+/// Separator of predicate and facet name in keys of JSON response.
+///
+const FACET_SEPARATOR: char = '|';
+
+///
+/// Facets of node grouped by predicate and facet name.
+///
+pub type NodeFacets = HashMap<String, HashMap<String, Value>>;
+
+///
+/// Node of query response together with facets of its edges.
+///
+/// Dgraph returns facets in JSON as `predicate|facet` keys, which cannot be mapped into struct
+/// fields by serde. `Facets<T>` removes all such keys from node, groups them by predicate and
+/// deserializes the rest of node into `T`.
+///
+/// Facets of uid edge are returned inside of child node, so wrap type of child node to read them.
+/// Facets of value list are returned as object keyed by index of value in list.
+///
+/// # Example
+///
+/// ```
+/// use serde_derive::Deserialize;
+/// use dgraph_tonic::{Facets, Response};
+///
+/// #[derive(Deserialize)]
+/// struct Person {
+///     name: String,
+///     #[serde(default)]
+///     friend: Vec<Facets<Person>>,
+/// }
+///
+/// #[derive(Deserialize)]
+/// struct People {
+///     people: Vec<Facets<Person>>,
+/// }
+///
+/// let response = Response {
+///     json: br#"{"people": [{
+///         "name": "Alice",
+///         "name|origin": "french",
+///         "friend": [{"name": "Bob", "friend|close": true}]
+///     }]}"#
+///     .to_vec(),
+///     ..Default::default()
+/// };
+/// let people: People = response.try_into().expect("JSON");
+/// let alice = &people.people[0];
+/// assert_eq!(alice.name, "Alice");
+/// assert_eq!(alice.facet("name", "origin"), Some(&"french".into()));
+/// let bob = &alice.friend[0];
+/// assert_eq!(bob.name, "Bob");
+/// assert_eq!(bob.facet("friend", "close"), Some(&true.into()));
+/// ```
+///
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Facets<T> {
+    pub value: T,
+    pub facets: NodeFacets,
+}
+
+impl<T> Facets<T> {
+    ///
+    /// Return all facets of predicate.
+    ///
+    /// # Arguments
+    ///
+    /// * `predicate` - name of predicate
+    ///
+    pub fn of(&self, predicate: &str) -> Option<&HashMap<String, Value>> {
+        self.facets.get(predicate)
+    }
+
+    ///
+    /// Return value of one facet of predicate.
+    ///
+    /// # Arguments
+    ///
+    /// * `predicate` - name of predicate
+    /// * `facet` - name of facet
+    ///
+    pub fn facet(&self, predicate: &str, facet: &str) -> Option<&Value> {
+        self.of(predicate).and_then(|facets| facets.get(facet))
+    }
+
+    ///
+    /// Consume wrapper and return deserialized node without facets.
+    ///
+    pub fn into_inner(self) -> T {
+        self.value
+    }
+}
+
+impl<T> Deref for Facets<T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        &self.value
+    }
+}
+
+impl<'de, T: DeserializeOwned> Deserialize<'de> for Facets<T> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let mut node = Map::deserialize(deserializer)?;
+        let facets = take_facets(&mut node);
+        let value = T::deserialize(Value::Object(node)).map_err(de::Error::custom)?;
+        Ok(Self { value, facets })
+    }
+}
+
+///
+/// Remove all `predicate|facet` keys from JSON node and return them grouped by predicate.
+///
+fn take_facets(node: &mut Map<String, Value>) -> NodeFacets {
+    let keys: Vec<String> = node
+        .keys()
+        .filter(|key| key.contains(FACET_SEPARATOR))
+        .cloned()
+        .collect();
+    let mut facets = NodeFacets::new();
+    for key in keys {
+        if let (Some(value), Some((predicate, facet))) =
+            (node.remove(&key), key.split_once(FACET_SEPARATOR))
+        {
+            facets
+                .entry(predicate.to_string())
+                .or_default()
+                .insert(facet.to_string(), value);
+        }
+    }
+    facets
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::{json, Value};
+
+    use crate::Facets;
+
+    #[test]
+    fn deserialize_facets() {
+        let node = json!({
+            "name": "Alice",
+            "name|origin": "french",
+            "nickname": ["Al", "Ali"],
+            "nickname|kind": {"0": "short", "1": "long"},
+            "friend|close": true,
+            "friend|since": "2006-01-02T15:04:05Z",
+        });
+        let node: Facets<Value> = serde_json::from_value(node).expect("JSON");
+        assert_eq!(
+            node.value,
+            json!({"name": "Alice", "nickname": ["Al", "Ali"]})
+        );
+        assert_eq!(node.facets.len(), 3);
+        assert_eq!(node.facet("name", "origin"), Some(&json!("french")));
+        assert_eq!(
+            node.facet("nickname", "kind"),
+            Some(&json!({"0": "short", "1": "long"}))
+        );
+        assert_eq!(node.of("friend").map(|facets| facets.len()), Some(2));
+        assert_eq!(node.facet("friend", "weight"), None);
+    }
+
+    #[test]
+    fn deserialize_without_facets() {
+        let node: Facets<Value> = serde_json::from_value(json!({"uid": "0x1"})).expect("JSON");
+        assert!(node.facets.is_empty());
+        assert_eq!(node.into_inner(), json!({"uid": "0x1"}));
+        assert!(serde_json::from_value::<Facets<Value>>(json!([1, 2])).is_err());
+    }
+}
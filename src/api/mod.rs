@@ -7,13 +7,31 @@ pub use crate::api::v1_0_x::*;
 pub use crate::api::v1_1_x::*;
 #[cfg(feature = "dgraph-21-03")]
 pub use crate::api::v21_03_0::*;
+#[cfg(feature = "dgraph-24-02")]
+pub use crate::api::v24_02_0::*;
 
+pub use facet_value::FacetValue;
+pub use facets::{Facets, NodeFacets};
+pub use schema::{PredicateBuilder, SchemaBuilder, TypeBuilder};
+
+mod facet_value;
+mod facets;
 mod mutation;
+mod payload;
+#[cfg(any(
+    feature = "dgraph-1-1",
+    feature = "dgraph-21-03",
+    feature = "dgraph-24-02"
+))]
+mod request_builder;
 mod response;
+mod schema;
 mod txn_context;
 mod v1_0_x;
 mod v1_1_x;
 mod v21_03_0;
+mod v24_02_0;
+mod version;
 
 #[async_trait]
 #[doc(hidden)]
@@ -25,7 +43,11 @@ pub(crate) trait IDgraphClient: Clone + Sized {
     #[cfg(feature = "dgraph-1-0")]
     async fn mutate(&mut self, mu: Mutation) -> Result<Assigned>;
 
-    #[cfg(any(feature = "dgraph-1-1", feature = "dgraph-21-03"))]
+    #[cfg(any(
+        feature = "dgraph-1-1",
+        feature = "dgraph-21-03",
+        feature = "dgraph-24-02"
+    ))]
     async fn do_request(&mut self, req: Request) -> Result<Response>;
 
     async fn alter(&mut self, op: Operation) -> Result<Payload>;
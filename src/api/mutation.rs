@@ -1,8 +1,19 @@
+use std::collections::HashMap;
+use std::hash::Hash;
+
+use prost::Message;
 use serde::Serialize;
 use serde_json::Error;
 
+use crate::api::value::Val;
+use crate::api::{FacetValue, NQuad, Value};
 use crate::Mutation;
 
+///
+/// Value which is used by Dgraph for `*` object of delete N-Quad.
+///
+const STAR_ALL: &str = "_STAR_ALL";
+
 impl Mutation {
     ///
     /// Create new Dgraph Mutation object.
@@ -18,6 +29,50 @@ impl Mutation {
         }
     }
 
+    ///
+    /// Create new Mutation which sets data defined by RDF N-Quads.
+    ///
+    /// # Arguments
+    ///
+    /// * `nquads` - set nquads
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use dgraph_tonic::Mutation;
+    ///
+    /// let mu = Mutation::from_set_nquads(r#"_:alice <name> "Alice" ."#);
+    /// ```
+    ///
+    pub fn from_set_nquads<N: Into<Vec<u8>>>(nquads: N) -> Self {
+        Self {
+            set_nquads: nquads.into(),
+            ..Default::default()
+        }
+    }
+
+    ///
+    /// Create new Mutation which deletes data defined by RDF N-Quads.
+    ///
+    /// # Arguments
+    ///
+    /// * `nquads` - delete nquads
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use dgraph_tonic::Mutation;
+    ///
+    /// let mu = Mutation::from_del_nquads("<0x1> <name> * .");
+    /// ```
+    ///
+    pub fn from_del_nquads<N: Into<Vec<u8>>>(nquads: N) -> Self {
+        Self {
+            del_nquads: nquads.into(),
+            ..Default::default()
+        }
+    }
+
     ///
     /// Can be applied on a Mutation object to not run conflict detection over the index,
     /// which would decrease the number of transaction conflicts and aborts.
@@ -183,4 +238,274 @@ impl Mutation {
     pub fn set_cond<S: Into<String>>(&mut self, cond: S) {
         self.cond = cond.into();
     }
+
+    ///
+    /// Add edge between two nodes with facets into set part of Mutation.
+    ///
+    /// Facets are encoded in the same binary form which is used by Dgraph, so they can be queried
+    /// and filtered like facets set in RDF N-Quads.
+    ///
+    /// # Arguments
+    ///
+    /// * `subject` - uid or blank node of edge source
+    /// * `predicate` - edge predicate
+    /// * `object` - uid or blank node of edge target
+    /// * `facets` - map of facet keys and values
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::collections::HashMap;
+    /// use dgraph_tonic::{FacetValue, Mutation};
+    ///
+    /// let mut facets = HashMap::new();
+    /// facets.insert("close", FacetValue::Bool(true));
+    /// facets.insert("since", FacetValue::Int(2006));
+    ///
+    /// let mut mu = Mutation::new();
+    /// mu.add_edge_with_facets("_:alice", "friend", "_:bob", facets);
+    /// ```
+    ///
+    pub fn add_edge_with_facets<S, P, O, K>(
+        &mut self,
+        subject: S,
+        predicate: P,
+        object: O,
+        facets: HashMap<K, FacetValue>,
+    ) where
+        S: Into<String>,
+        P: Into<String>,
+        O: Into<String>,
+        K: Into<String> + Eq + Hash,
+    {
+        let mut facets: Vec<_> = facets
+            .into_iter()
+            .map(|(key, value)| value.into_facet(key))
+            .collect();
+        facets.sort_by(|a, b| a.key.cmp(&b.key));
+        self.set.push(NQuad {
+            subject: subject.into(),
+            predicate: predicate.into(),
+            object_id: object.into(),
+            facets,
+            ..Default::default()
+        });
+    }
+
+    ///
+    /// Add edge between two nodes into delete part of Mutation.
+    ///
+    /// If `object` is `*`, all edges of `predicate` are deleted from `subject`.
+    ///
+    /// # Arguments
+    ///
+    /// * `subject` - uid of edge source
+    /// * `predicate` - edge predicate
+    /// * `object` - uid of edge target or `*`
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use dgraph_tonic::Mutation;
+    ///
+    /// let mut mu = Mutation::new();
+    /// //remove one friend
+    /// mu.delete_edge("0x1", "friend", "0x2");
+    /// //remove all friends
+    /// mu.delete_edge("0x1", "friend", "*");
+    /// ```
+    ///
+    pub fn delete_edge<S, P, O>(&mut self, subject: S, predicate: P, object: O)
+    where
+        S: Into<String>,
+        P: Into<String>,
+        O: Into<String>,
+    {
+        let object: String = object.into();
+        if object == "*" {
+            self.delete_value(subject, predicate, star_value());
+        } else {
+            self.del.push(NQuad {
+                subject: subject.into(),
+                predicate: predicate.into(),
+                object_id: object,
+                ..Default::default()
+            });
+        }
+    }
+
+    ///
+    /// Add value of predicate into delete part of Mutation.
+    ///
+    /// # Arguments
+    ///
+    /// * `subject` - uid of node
+    /// * `predicate` - deleted predicate
+    /// * `value` - deleted value
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use dgraph_tonic::{Mutation, Value};
+    /// use dgraph_tonic::value::Val;
+    ///
+    /// let mut mu = Mutation::new();
+    /// mu.delete_value(
+    ///     "0x1",
+    ///     "name",
+    ///     Value {
+    ///         val: Some(Val::StrVal("Alice".into())),
+    ///     },
+    /// );
+    /// ```
+    ///
+    pub fn delete_value<S, P>(&mut self, subject: S, predicate: P, value: Value)
+    where
+        S: Into<String>,
+        P: Into<String>,
+    {
+        self.del.push(NQuad {
+            subject: subject.into(),
+            predicate: predicate.into(),
+            object_value: Some(value),
+            ..Default::default()
+        });
+    }
+
+    ///
+    /// Return size of mutation in bytes after serialization into protobuf message.
+    ///
+    /// Use it to split large batches before they exceed maximal size of gRPC message.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use dgraph_tonic::Mutation;
+    ///
+    /// let mu = Mutation::from_set_nquads(r#"_:alice <name> "Alice" ."#);
+    /// assert!(mu.encoded_len() > 0);
+    /// ```
+    ///
+    pub fn encoded_len(&self) -> usize {
+        Message::encoded_len(self)
+    }
+}
+
+fn star_value() -> Value {
+    Value {
+        val: Some(Val::DefaultVal(STAR_ALL.to_string())),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+    use std::time::{Duration, UNIX_EPOCH};
+
+    use crate::api::facet::ValType;
+    use crate::api::value::Val;
+    use crate::{FacetValue, Mutation, Value};
+
+    #[test]
+    fn from_nquads() {
+        let mu = Mutation::from_set_nquads(r#"_:alice <name> "Alice" ."#);
+        let mut expected = Mutation::new();
+        expected.set_set_nquads(r#"_:alice <name> "Alice" ."#);
+        assert_eq!(mu, expected);
+        let mu = Mutation::from_del_nquads(b"<0x1> <name> * .".to_vec());
+        let mut expected = Mutation::new();
+        expected.set_delete_nquads("<0x1> <name> * .");
+        assert_eq!(mu, expected);
+    }
+
+    #[test]
+    fn add_edge_with_facets() {
+        let mut facets = HashMap::new();
+        facets.insert("since", FacetValue::Int(2006));
+        facets.insert("close", FacetValue::Bool(true));
+        facets.insert("weight", FacetValue::Float(0.5));
+        facets.insert("kind", FacetValue::String("school".into()));
+        facets.insert(
+            "met",
+            FacetValue::Datetime(UNIX_EPOCH + Duration::from_secs(1)),
+        );
+        let mut mu = Mutation::new();
+        mu.add_edge_with_facets("_:alice", "friend", "_:bob", facets);
+        assert_eq!(mu.set.len(), 1);
+        let nquad = &mu.set[0];
+        assert_eq!(nquad.subject, "_:alice");
+        assert_eq!(nquad.predicate, "friend");
+        assert_eq!(nquad.object_id, "_:bob");
+        let keys: Vec<&str> = nquad.facets.iter().map(|f| f.key.as_str()).collect();
+        assert_eq!(keys, vec!["close", "kind", "met", "since", "weight"]);
+        let close = &nquad.facets[0];
+        assert_eq!(close.val_type, ValType::Bool as i32);
+        assert_eq!(close.value, vec![1]);
+        let kind = &nquad.facets[1];
+        assert_eq!(kind.val_type, ValType::String as i32);
+        assert_eq!(kind.value, b"school".to_vec());
+        let met = &nquad.facets[2];
+        assert_eq!(met.val_type, ValType::Datetime as i32);
+        assert_eq!(
+            met.value,
+            vec![1, 0, 0, 0, 14, 119, 145, 247, 1, 0, 0, 0, 0, 255, 255]
+        );
+        let since = &nquad.facets[3];
+        assert_eq!(since.val_type, ValType::Int as i32);
+        assert_eq!(since.value, 2006i64.to_le_bytes().to_vec());
+        let weight = &nquad.facets[4];
+        assert_eq!(weight.val_type, ValType::Float as i32);
+        assert_eq!(weight.value, 0.5f64.to_bits().to_le_bytes().to_vec());
+    }
+
+    #[test]
+    fn delete_edge() {
+        let mut mu = Mutation::new();
+        mu.delete_edge("0x1", "friend", "0x2");
+        mu.delete_edge("0x1", "friend", "*");
+        assert!(mu.set.is_empty());
+        assert_eq!(mu.del.len(), 2);
+        let edge = &mu.del[0];
+        assert_eq!(edge.subject, "0x1");
+        assert_eq!(edge.predicate, "friend");
+        assert_eq!(edge.object_id, "0x2");
+        assert_eq!(edge.object_value, None);
+        let star = &mu.del[1];
+        assert_eq!(star.subject, "0x1");
+        assert_eq!(star.predicate, "friend");
+        assert_eq!(star.object_id, "");
+        assert_eq!(
+            star.object_value,
+            Some(Value {
+                val: Some(Val::DefaultVal("_STAR_ALL".to_string()))
+            })
+        );
+    }
+
+    #[test]
+    fn encoded_len() {
+        assert_eq!(Mutation::new().encoded_len(), 0);
+        let nquads = r#"_:alice <name> "Alice" ."#;
+        let mu = Mutation::from_set_nquads(nquads);
+        // field tag and length prefix precede nquads
+        assert_eq!(mu.encoded_len(), nquads.len() + 2);
+        let mut mu = mu;
+        mu.delete_value("0x1", "name", Value::default());
+        assert!(mu.encoded_len() > nquads.len() + 2);
+    }
+
+    #[test]
+    fn delete_value() {
+        let mut mu = Mutation::new();
+        let value = Value {
+            val: Some(Val::StrVal("Alice".to_string())),
+        };
+        mu.delete_value("0x1", "name", value.to_owned());
+        assert_eq!(mu.del.len(), 1);
+        let nquad = &mu.del[0];
+        assert_eq!(nquad.subject, "0x1");
+        assert_eq!(nquad.predicate, "name");
+        assert_eq!(nquad.object_id, "");
+        assert_eq!(nquad.object_value, Some(value));
+    }
 }
@@ -0,0 +1,83 @@
+use serde::de::DeserializeOwned;
+
+use crate::errors::DgraphError;
+use crate::Payload;
+
+impl Payload {
+    ///
+    /// Return data of payload as string, if they are valid UTF-8.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use dgraph_tonic::Payload;
+    ///
+    /// let payload = Payload {
+    ///     data: b"Done".to_vec(),
+    /// };
+    /// assert_eq!(payload.as_str(), Some("Done"));
+    /// ```
+    ///
+    pub fn as_str(&self) -> Option<&str> {
+        std::str::from_utf8(&self.data).ok()
+    }
+
+    ///
+    /// Try deserialize JSON data of payload into T
+    ///
+    /// # Errors
+    ///
+    /// Return `JsonDeserialize` error with length of data when they cannot be deserialized.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use std::collections::HashMap;
+    /// use dgraph_tonic::Payload;
+    ///
+    /// let payload = Payload {
+    ///     data: br#"{"code": "Success", "message": "Done"}"#.to_vec(),
+    /// };
+    /// let data: HashMap<String, String> = payload.json().expect("JSON");
+    /// assert_eq!(data["code"], "Success");
+    /// ```
+    ///
+    pub fn json<T: DeserializeOwned>(&self) -> Result<T, DgraphError> {
+        serde_json::from_slice(&self.data).map_err(|source| DgraphError::JsonDeserialize {
+            raw_len: self.data.len(),
+            source,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::Value;
+
+    use crate::{DgraphError, Payload};
+
+    #[test]
+    fn as_str() {
+        let payload = Payload {
+            data: vec![0xff, 0xfe],
+        };
+        assert_eq!(payload.as_str(), None);
+        assert_eq!(Payload::default().as_str(), Some(""));
+    }
+
+    #[test]
+    fn json() {
+        let payload = Payload {
+            data: br#"{"code": "Success"}"#.to_vec(),
+        };
+        let data: Value = payload.json().expect("JSON");
+        assert_eq!(data["code"], "Success");
+        let err = Payload::default()
+            .json::<Value>()
+            .expect_err("Empty payload");
+        assert!(matches!(
+            err,
+            DgraphError::JsonDeserialize { raw_len: 0, .. }
+        ));
+    }
+}
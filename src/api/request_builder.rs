@@ -0,0 +1,56 @@
+use crate::{Mutation, Request};
+
+impl Request {
+    ///
+    /// Create upsert request with query block and mutations.
+    ///
+    /// Dgraph v24.02 and newer appends unique check blocks to query of upsert. Query which does
+    /// not end with newline is then rejected by query lexer with "Unclosed action", so missing
+    /// trailing newline is added. Otherwise query is sent verbatim.
+    ///
+    /// # Arguments
+    ///
+    /// * `query` - query block of upsert
+    /// * `mutations` - mutations which can use variables of query block
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use dgraph_tonic::{Mutation, Request};
+    ///
+    /// let mu = Mutation::from_set_nquads(r#"uid(user) <email> "alice@dgraph.io" ."#);
+    /// let request = Request::upsert(
+    ///     r#"query { user as var(func: eq(email, "alice@dgraph.io")) }"#,
+    ///     vec![mu],
+    /// );
+    /// assert!(request.query.ends_with("}\n"));
+    /// ```
+    ///
+    pub fn upsert<Q: Into<String>>(query: Q, mutations: Vec<Mutation>) -> Self {
+        let mut query = query.into();
+        if !query.is_empty() && !query.ends_with('\n') {
+            query.push('\n');
+        }
+        Self {
+            query,
+            mutations,
+            ..Default::default()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Mutation, Request};
+
+    #[test]
+    fn upsert() {
+        let query = "query {\n  user as var(func: eq(email, \"a\")) @filter(has(name))\n}";
+        let request = Request::upsert(query, vec![Mutation::new()]);
+        assert_eq!(request.query, format!("{}\n", query));
+        assert_eq!(request.mutations, vec![Mutation::new()]);
+        let query = "query {\n  user as var(func: eq(email, \"a\")) @cascade\n}\n\n";
+        assert_eq!(Request::upsert(query, Vec::new()).query, query);
+        assert_eq!(Request::upsert("", Vec::new()).query, "");
+    }
+}
@@ -1,31 +1,630 @@
-use serde::de::{self};
+use std::collections::HashMap;
+use std::marker::PhantomData;
+
+use serde::de::{self, DeserializeSeed};
 use serde_json::error::Error;
-use serde_json::Value;
+use serde_json::value::RawValue;
+use serde_json::{Deserializer, Value};
 
-use crate::Response;
+use crate::errors::DgraphError;
+use crate::{Facets, NodeFacets, Response};
 
 impl Response {
     ///
     /// Try deserialize response JSON data into T
     ///
-    pub fn try_into<'a, T>(&'a self) -> Result<T, Error>
+    /// # Errors
+    ///
+    /// Return `JsonDeserialize` error with length of JSON data when they cannot be deserialized.
+    ///
+    pub fn try_into<'a, T>(&'a self) -> Result<T, DgraphError>
     where
         T: de::Deserialize<'a>,
     {
-        let result: T = serde_json::from_slice(&self.json)?;
-        Ok(result)
+        serde_json::from_slice(&self.json).map_err(|source| self.json_error(source))
     }
 
     ///
     /// Consume response and try return response JSON data deserialized into T
     ///
-    pub fn try_into_owned<T>(self) -> Result<T, Error>
+    /// # Errors
+    ///
+    /// Return `JsonDeserialize` error with length of JSON data when they cannot be deserialized.
+    ///
+    pub fn try_into_owned<T>(self) -> Result<T, DgraphError>
+    where
+        T: de::DeserializeOwned,
+    {
+        serde_json::from_slice(&self.json).map_err(|source| self.json_error(source))
+    }
+
+    ///
+    /// Try deserialize nodes of one query block into vector of T
+    ///
+    /// Wrapper struct with field named as query block is not needed. Missing block is returned
+    /// as empty vector, because Dgraph omits blocks without results in some versions.
+    ///
+    /// # Arguments
+    ///
+    /// * `block` - name of query block
+    ///
+    /// # Errors
+    ///
+    /// Return `JsonDeserialize` error with length of JSON data when they are invalid or nodes of
+    /// block cannot be deserialized.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use serde_derive::Deserialize;
+    /// use dgraph_tonic::Response;
+    ///
+    /// #[derive(Deserialize)]
+    /// struct Person {
+    ///     name: String,
+    /// }
+    ///
+    /// let response = Response {
+    ///     json: br#"{"all": [{"name": "Alice"}, {"name": "Bob"}]}"#.to_vec(),
+    ///     ..Default::default()
+    /// };
+    /// let all = response.try_into_block::<Person>("all").expect("JSON");
+    /// assert_eq!(all.len(), 2);
+    /// assert_eq!(all[1].name, "Bob");
+    /// ```
+    ///
+    pub fn try_into_block<T>(&self, block: &str) -> Result<Vec<T>, DgraphError>
     where
         T: de::DeserializeOwned,
     {
-        let result: T = serde_json::from_slice(&self.json)?;
+        let mut data: HashMap<String, Value> = self.try_into()?;
+        match data.remove(block) {
+            Some(nodes) => serde_json::from_value(nodes).map_err(|source| self.json_error(source)),
+            None => Ok(Vec::new()),
+        }
+    }
+
+    ///
+    /// Return true if query block is empty array or it is missing in response.
+    ///
+    /// Use it to check whether query matched anything without deserialization into typed
+    /// structure.
+    ///
+    /// # Arguments
+    ///
+    /// * `block` - name of query block
+    ///
+    /// # Errors
+    ///
+    /// Return `JsonDeserialize` error when JSON data are invalid.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use dgraph_tonic::Response;
+    ///
+    /// let response = Response {
+    ///     json: br#"{"alice": [{"uid": "0x1"}], "bob": []}"#.to_vec(),
+    ///     ..Default::default()
+    /// };
+    /// assert!(!response.is_empty_block("alice").unwrap());
+    /// assert!(response.is_empty_block("bob").unwrap());
+    /// assert!(response.is_empty_block("carol").unwrap());
+    /// ```
+    ///
+    pub fn is_empty_block(&self, block: &str) -> Result<bool, DgraphError> {
+        let data: Value = self.try_into()?;
+        let empty = match data.get(block) {
+            Some(Value::Array(nodes)) => nodes.is_empty(),
+            Some(Value::Null) | None => true,
+            Some(_) => false,
+        };
+        Ok(empty)
+    }
+
+    ///
+    /// Lazily deserialize nodes of one query block.
+    ///
+    /// Nodes are read from response JSON data one by one, so whole vector of T is never
+    /// allocated. Missing block is iterated as empty.
+    ///
+    /// # Arguments
+    ///
+    /// * `block` - name of query block
+    ///
+    /// # Errors
+    ///
+    /// Iterator returns `JsonDeserialize` error when JSON data are invalid, block is not array or
+    /// node cannot be deserialized. Iteration ends after first error.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use serde_derive::Deserialize;
+    /// use dgraph_tonic::Response;
+    ///
+    /// #[derive(Deserialize)]
+    /// struct Person {
+    ///     name: String,
+    /// }
+    ///
+    /// let response = Response {
+    ///     json: br#"{"all": [{"name": "Alice"}, {"name": "Bob"}]}"#.to_vec(),
+    ///     ..Default::default()
+    /// };
+    /// for person in response.iter_block::<Person>("all") {
+    ///     println!("{}", person.expect("JSON").name);
+    /// }
+    /// ```
+    ///
+    pub fn iter_block<'a, T>(
+        &'a self,
+        block: &str,
+    ) -> impl Iterator<Item = Result<T, DgraphError>> + 'a
+    where
+        T: de::Deserialize<'a> + 'a,
+    {
+        let (nodes, error) = match self.try_into::<HashMap<String, &RawValue>>() {
+            Ok(mut blocks) => {
+                let nodes = blocks.remove(block).map(|nodes| nodes.get().as_bytes());
+                (nodes.unwrap_or(b"[]"), None)
+            }
+            Err(err) => (&b"[]"[..], Some(err)),
+        };
+        error
+            .map(Err)
+            .into_iter()
+            .chain(BlockIter::new(nodes, self.json.len()))
+    }
+
+    fn json_error(&self, source: Error) -> DgraphError {
+        DgraphError::JsonDeserialize {
+            raw_len: self.json.len(),
+            source,
+        }
+    }
+
+    ///
+    /// Drive user-provided `DeserializeSeed` over response JSON data.
+    ///
+    /// Seed visits JSON data directly, so huge responses can be processed incrementally
+    /// without allocating the whole deserialized structure.
+    ///
+    /// # Arguments
+    ///
+    /// * `seed` - stateful deserializer of response JSON data
+    ///
+    /// # Errors
+    ///
+    /// Return serde_json::Error when seed cannot deserialize JSON data or if there are trailing
+    /// characters after JSON data.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use std::fmt;
+    /// use serde::de::{DeserializeSeed, Deserializer, IgnoredAny, MapAccess, Visitor};
+    /// use dgraph_tonic::Response;
+    ///
+    /// // count all nodes in `items` block without allocating them
+    /// struct ItemsCounter;
+    ///
+    /// impl<'de> DeserializeSeed<'de> for ItemsCounter {
+    ///     type Value = usize;
+    ///
+    ///     fn deserialize<D: Deserializer<'de>>(self, deserializer: D) -> Result<usize, D::Error> {
+    ///         deserializer.deserialize_map(self)
+    ///     }
+    /// }
+    ///
+    /// impl<'de> Visitor<'de> for ItemsCounter {
+    ///     type Value = usize;
+    ///
+    ///     fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    ///         write!(f, "query response")
+    ///     }
+    ///
+    ///     fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<usize, A::Error> {
+    ///         let mut count = 0;
+    ///         while let Some(key) = map.next_key::<String>()? {
+    ///             if key == "items" {
+    ///                 count += map.next_value::<Vec<IgnoredAny>>()?.len();
+    ///             } else {
+    ///                 map.next_value::<IgnoredAny>()?;
+    ///             }
+    ///         }
+    ///         Ok(count)
+    ///     }
+    /// }
+    ///
+    /// let response = Response {
+    ///     json: br#"{"items": [{"uid": "0x1"}, {"uid": "0x2"}]}"#.to_vec(),
+    ///     ..Default::default()
+    /// };
+    /// assert_eq!(response.deserialize_seed(ItemsCounter).expect("JSON"), 2);
+    /// ```
+    ///
+    pub fn deserialize_seed<'a, S>(&'a self, seed: S) -> Result<S::Value, Error>
+    where
+        S: DeserializeSeed<'a>,
+    {
+        let mut deserializer = serde_json::Deserializer::from_slice(&self.json);
+        let result = seed.deserialize(&mut deserializer)?;
+        deserializer.end()?;
         Ok(result)
     }
+
+    ///
+    /// Return facets of every node in query block.
+    ///
+    /// Facets of n-th node of block are on n-th position, grouped by predicate. See `Facets`
+    /// for deserialization of nodes together with facets.
+    ///
+    /// # Arguments
+    ///
+    /// * `block` - name of query block
+    ///
+    /// # Errors
+    ///
+    /// Return `JsonDeserialize` error when JSON data are invalid or block is not list of nodes.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use dgraph_tonic::Response;
+    ///
+    /// let response = Response {
+    ///     json: br#"{"friends": [{"name": "Bob", "friend|close": true}, {"name": "Carol"}]}"#
+    ///         .to_vec(),
+    ///     ..Default::default()
+    /// };
+    /// let facets = response.facets("friends").expect("JSON");
+    /// assert_eq!(facets.len(), 2);
+    /// assert_eq!(facets[0]["friend"]["close"], true);
+    /// assert!(facets[1].is_empty());
+    /// ```
+    ///
+    pub fn facets(&self, block: &str) -> Result<Vec<NodeFacets>, DgraphError> {
+        let nodes: Vec<Facets<de::IgnoredAny>> = self.try_into_block(block)?;
+        Ok(nodes.into_iter().map(|node| node.facets).collect())
+    }
+
+    ///
+    /// Return headers which were attached to response by Dgraph.
+    ///
+    /// Older Dgraph versions do not send any headers, so map is always empty there.
+    ///
+    pub fn headers(&self) -> HashMap<String, Vec<String>> {
+        #[cfg(any(
+            feature = "dgraph-1-1",
+            feature = "dgraph-21-03",
+            feature = "dgraph-24-02"
+        ))]
+        {
+            self.hdrs
+                .iter()
+                .map(|(key, values)| (key.to_owned(), values.value.to_owned()))
+                .collect()
+        }
+        #[cfg(feature = "dgraph-1-0")]
+        {
+            HashMap::new()
+        }
+    }
+
+    ///
+    /// Return uids of blank nodes in given order.
+    ///
+    /// Uids of mutation response are stored in map, so their iteration order is random. Blank
+    /// node which was not created by mutation is returned as `None`.
+    ///
+    /// # Arguments
+    ///
+    /// * `blank_names` - names of blank nodes without `_:` prefix
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use dgraph_tonic::Response;
+    ///
+    /// let mut response = Response::default();
+    /// response.uids.insert("bob".into(), "0x2".into());
+    /// response.uids.insert("alice".into(), "0x1".into());
+    /// assert_eq!(
+    ///     response.uids_in_order(&["alice", "bob", "carol"]),
+    ///     vec![Some("0x1".to_string()), Some("0x2".to_string()), None]
+    /// );
+    /// ```
+    ///
+    #[cfg(any(
+        feature = "dgraph-1-1",
+        feature = "dgraph-21-03",
+        feature = "dgraph-24-02"
+    ))]
+    pub fn uids_in_order(&self, blank_names: &[&str]) -> Vec<Option<String>> {
+        uids_in_order(&self.uids, blank_names)
+    }
+
+    ///
+    /// Return number of nodes and edges affected by mutation.
+    ///
+    /// Count is sum of `num_uids` metrics reported by Dgraph. Aggregated `_total` entry is
+    /// skipped, so nothing is counted twice. Dgraph 1.0 does not report metrics of mutations,
+    /// so this method is available only for Dgraph 1.1 and newer.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use dgraph_tonic::{Metrics, Response};
+    ///
+    /// let mut metrics = Metrics::default();
+    /// metrics.num_uids.insert("mutation_cost".into(), 2);
+    /// let response = Response {
+    ///     metrics: Some(metrics),
+    ///     ..Default::default()
+    /// };
+    /// assert_eq!(response.affected_count(), 2);
+    /// ```
+    ///
+    #[cfg(any(
+        feature = "dgraph-1-1",
+        feature = "dgraph-21-03",
+        feature = "dgraph-24-02"
+    ))]
+    pub fn affected_count(&self) -> u64 {
+        self.metrics
+            .as_ref()
+            .map(|metrics| {
+                metrics
+                    .num_uids
+                    .iter()
+                    .filter(|(key, _)| !key.starts_with('_'))
+                    .map(|(_, count)| count)
+                    .sum()
+            })
+            .unwrap_or_default()
+    }
+
+    ///
+    /// Parse RDF response data into subject, predicate, object triples.
+    ///
+    /// Only minimal N-Quads parsing is done. `<uid>` terms are returned without angle brackets and
+    /// quoted literals are returned unescaped without language tag or datatype.
+    ///
+    /// # Errors
+    ///
+    /// Return `InvalidRdf` error with line number when RDF data cannot be parsed or it is not
+    /// valid UTF-8.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use dgraph_tonic::Response;
+    ///
+    /// let response = Response {
+    ///     rdf: b"<0x1> <name> \"Alice\" .\n<0x1> <friend> <0x2> .\n".to_vec(),
+    ///     ..Default::default()
+    /// };
+    /// let triples = response.rdf_triples().expect("RDF");
+    /// assert_eq!(triples[0], ("0x1".into(), "name".into(), "Alice".into()));
+    /// assert_eq!(triples[1], ("0x1".into(), "friend".into(), "0x2".into()));
+    /// ```
+    ///
+    #[cfg(any(
+        feature = "dgraph-1-1",
+        feature = "dgraph-21-03",
+        feature = "dgraph-24-02"
+    ))]
+    pub fn rdf_triples(&self) -> anyhow::Result<Vec<(String, String, String)>> {
+        let rdf = match std::str::from_utf8(&self.rdf) {
+            Ok(rdf) => rdf,
+            Err(err) => {
+                let valid = &self.rdf[..err.valid_up_to()];
+                let line = valid.iter().filter(|byte| **byte == b'\n').count() + 1;
+                anyhow::bail!(DgraphError::InvalidRdf(line))
+            }
+        };
+        let mut triples = Vec::new();
+        for (index, line) in rdf.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            match parse_nquad(line) {
+                Some(triple) => triples.push(triple),
+                None => anyhow::bail!(DgraphError::InvalidRdf(index + 1)),
+            }
+        }
+        Ok(triples)
+    }
+}
+
+#[cfg(any(
+    feature = "dgraph-1-1",
+    feature = "dgraph-21-03",
+    feature = "dgraph-24-02"
+))]
+fn parse_nquad(line: &str) -> Option<(String, String, String)> {
+    let (subject, rest) = parse_term(line)?;
+    let (predicate, rest) = parse_term(rest)?;
+    let (object, mut rest) = parse_term(rest)?;
+    if !rest.starts_with('.') {
+        //skip graph label
+        rest = parse_term(rest)?.1;
+    }
+    if rest == "." {
+        Some((subject, predicate, object))
+    } else {
+        None
+    }
+}
+
+///
+/// Parse one term and return it together with rest of line without leading whitespace.
+///
+#[cfg(any(
+    feature = "dgraph-1-1",
+    feature = "dgraph-21-03",
+    feature = "dgraph-24-02"
+))]
+fn parse_term(input: &str) -> Option<(String, &str)> {
+    if let Some(rest) = input.strip_prefix('<') {
+        let end = rest.find('>')?;
+        return Some((rest[..end].to_string(), rest[end + 1..].trim_start()));
+    }
+    if input.starts_with("_:") {
+        let end = input.find(char::is_whitespace).unwrap_or(input.len());
+        return Some((input[..end].to_string(), input[end..].trim_start()));
+    }
+    let mut chars = input.strip_prefix('"')?.char_indices();
+    let mut literal = String::new();
+    let end = loop {
+        match chars.next()? {
+            (index, '"') => break index + 2,
+            (_, '\\') => match chars.next()?.1 {
+                't' => literal.push('\t'),
+                'n' => literal.push('\n'),
+                'r' => literal.push('\r'),
+                'b' => literal.push('\u{8}'),
+                'f' => literal.push('\u{c}'),
+                'u' => literal.push(parse_unicode(&mut chars, 4)?),
+                'U' => literal.push(parse_unicode(&mut chars, 8)?),
+                c => literal.push(c),
+            },
+            (_, c) => literal.push(c),
+        }
+    };
+    let mut rest = &input[end..];
+    if let Some(tag) = rest.strip_prefix('@') {
+        rest = &tag[tag.find(char::is_whitespace)?..];
+    } else if let Some(datatype) = rest.strip_prefix("^^<") {
+        rest = &datatype[datatype.find('>')? + 1..];
+    }
+    Some((literal, rest.trim_start()))
+}
+
+#[cfg(any(
+    feature = "dgraph-1-1",
+    feature = "dgraph-21-03",
+    feature = "dgraph-24-02"
+))]
+fn parse_unicode(chars: &mut std::str::CharIndices, len: usize) -> Option<char> {
+    let code: String = chars.take(len).map(|(_, c)| c).collect();
+    if code.len() != len {
+        return None;
+    }
+    std::char::from_u32(u32::from_str_radix(&code, 16).ok()?)
+}
+
+#[cfg(feature = "dgraph-1-0")]
+impl crate::Assigned {
+    ///
+    /// Return uids of blank nodes in given order.
+    ///
+    /// Uids of mutation response are stored in map, so their iteration order is random. Blank
+    /// node which was not created by mutation is returned as `None`.
+    ///
+    /// # Arguments
+    ///
+    /// * `blank_names` - names of blank nodes without `_:` prefix
+    ///
+    pub fn uids_in_order(&self, blank_names: &[&str]) -> Vec<Option<String>> {
+        uids_in_order(&self.uids, blank_names)
+    }
+}
+
+fn uids_in_order(uids: &HashMap<String, String>, blank_names: &[&str]) -> Vec<Option<String>> {
+    blank_names
+        .iter()
+        .map(|name| uids.get(*name).cloned())
+        .collect()
+}
+
+///
+/// Iterator which deserializes nodes of JSON array one by one.
+///
+struct BlockIter<'a, T> {
+    nodes: &'a [u8],
+    offset: usize,
+    raw_len: usize,
+    done: bool,
+    node: PhantomData<T>,
+}
+
+impl<'a, T: de::Deserialize<'a>> BlockIter<'a, T> {
+    fn new(nodes: &'a [u8], raw_len: usize) -> Self {
+        Self {
+            nodes,
+            offset: 0,
+            raw_len,
+            done: false,
+            node: PhantomData,
+        }
+    }
+
+    fn next_node(&mut self) -> Result<Option<T>, Error> {
+        if self.offset == 0 {
+            self.expect(b'[')?;
+            if self.peek() == Some(b']') {
+                return Ok(None);
+            }
+        } else if self.peek() == Some(b']') {
+            return Ok(None);
+        } else {
+            self.expect(b',')?;
+        }
+        let mut stream = Deserializer::from_slice(&self.nodes[self.offset..]).into_iter();
+        match stream.next() {
+            Some(node) => {
+                self.offset += stream.byte_offset();
+                node.map(Some)
+            }
+            None => Err(de::Error::custom("unexpected end of query block")),
+        }
+    }
+
+    ///
+    /// Skip whitespaces and return next byte of array.
+    ///
+    fn peek(&mut self) -> Option<u8> {
+        while let Some(byte) = self.nodes.get(self.offset) {
+            if !byte.is_ascii_whitespace() {
+                return Some(*byte);
+            }
+            self.offset += 1;
+        }
+        None
+    }
+
+    fn expect(&mut self, expected: u8) -> Result<(), Error> {
+        if self.peek() == Some(expected) {
+            self.offset += 1;
+            Ok(())
+        } else {
+            Err(de::Error::custom(format!(
+                "expected `{}` in query block",
+                expected as char
+            )))
+        }
+    }
+}
+
+impl<'a, T: de::Deserialize<'a>> Iterator for BlockIter<'a, T> {
+    type Item = Result<T, DgraphError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.done {
+            return None;
+        }
+        let node = self.next_node().transpose();
+        self.done = !matches!(node, Some(Ok(_)));
+        node.map(|node| {
+            node.map_err(|source| DgraphError::JsonDeserialize {
+                raw_len: self.raw_len,
+                source,
+            })
+        })
+    }
 }
 
 impl From<Response> for Value {
@@ -33,3 +632,299 @@ impl From<Response> for Value {
         serde_json::from_slice(&reps.json).expect("JSON")
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+    use std::marker::PhantomData;
+
+    use serde::de::{DeserializeSeed, Deserializer};
+    use serde_derive::Deserialize;
+    use serde_json::Value;
+
+    use crate::{DgraphError, Response};
+
+    #[derive(Deserialize)]
+    struct Item<'a> {
+        name: &'a str,
+    }
+
+    struct NamesLen<'a>(PhantomData<&'a ()>);
+
+    impl<'de> DeserializeSeed<'de> for NamesLen<'de> {
+        type Value = usize;
+
+        fn deserialize<D: Deserializer<'de>>(self, deserializer: D) -> Result<usize, D::Error> {
+            #[derive(Deserialize)]
+            struct Items<'a> {
+                #[serde(borrow)]
+                items: Vec<Item<'a>>,
+            }
+            let items: Items<'de> = serde::Deserialize::deserialize(deserializer)?;
+            Ok(items.items.iter().map(|item| item.name.len()).sum())
+        }
+    }
+
+    #[cfg(any(
+        feature = "dgraph-1-1",
+        feature = "dgraph-21-03",
+        feature = "dgraph-24-02"
+    ))]
+    #[test]
+    fn uids_in_order() {
+        let mut response = Response::default();
+        for i in 0..10 {
+            response.uids.insert(format!("n{}", i), format!("0x{}", i));
+        }
+        let names: Vec<String> = (0..10).rev().map(|i| format!("n{}", i)).collect();
+        let names: Vec<&str> = names.iter().map(String::as_str).collect();
+        let uids = response.uids_in_order(&names);
+        let expected: Vec<Option<String>> =
+            (0..10).rev().map(|i| Some(format!("0x{}", i))).collect();
+        assert_eq!(uids, expected);
+        assert_eq!(
+            response.uids_in_order(&["missing", "n1"]),
+            vec![None, Some("0x1".to_string())]
+        );
+    }
+
+    #[cfg(feature = "dgraph-1-0")]
+    #[test]
+    fn assigned_uids_in_order() {
+        let mut assigned = crate::Assigned::default();
+        assigned.uids.insert("alice".to_string(), "0x1".to_string());
+        assert_eq!(
+            assigned.uids_in_order(&["bob", "alice"]),
+            vec![None, Some("0x1".to_string())]
+        );
+    }
+
+    #[test]
+    fn try_into_block() {
+        let response = Response {
+            json: br#"{"all": [{"name": "Alice"}], "count": [{"count": 1}]}"#.to_vec(),
+            ..Default::default()
+        };
+        let all: Vec<HashMap<String, String>> = response.try_into_block("all").expect("JSON");
+        assert_eq!(all.len(), 1);
+        assert_eq!(all[0]["name"], "Alice");
+        let missing: Vec<Value> = response.try_into_block("missing").expect("JSON");
+        assert!(missing.is_empty());
+        assert!(matches!(
+            response.try_into_block::<HashMap<String, String>>("count"),
+            Err(DgraphError::JsonDeserialize { .. })
+        ));
+    }
+
+    #[test]
+    fn is_empty_block() {
+        let response = Response {
+            json: br#"{"present": [{"uid": "0x1"}], "empty": [], "null": null, "count": 0}"#
+                .to_vec(),
+            ..Default::default()
+        };
+        assert!(!response.is_empty_block("present").unwrap());
+        assert!(response.is_empty_block("empty").unwrap());
+        assert!(response.is_empty_block("null").unwrap());
+        assert!(response.is_empty_block("absent").unwrap());
+        assert!(!response.is_empty_block("count").unwrap());
+        let invalid = Response {
+            json: br#"{"present": [{"uid": "0x1"}"#.to_vec(),
+            ..Default::default()
+        };
+        assert!(matches!(
+            invalid.is_empty_block("present"),
+            Err(DgraphError::JsonDeserialize { .. })
+        ));
+        assert!(Response::default().is_empty_block("present").is_err());
+    }
+
+    #[test]
+    fn iter_block() {
+        let response = Response {
+            json: br#"{"all": [ {"name": "Alice"} , {"name": "Bob"}], "empty": [ ], "count": 1}"#
+                .to_vec(),
+            ..Default::default()
+        };
+        let all: Vec<Item> = response
+            .iter_block("all")
+            .collect::<Result<_, _>>()
+            .expect("JSON");
+        let names: Vec<&str> = all.iter().map(|item| item.name).collect();
+        assert_eq!(names, vec!["Alice", "Bob"]);
+        assert_eq!(response.iter_block::<Value>("empty").count(), 0);
+        assert_eq!(response.iter_block::<Value>("missing").count(), 0);
+        let count: Vec<_> = response.iter_block::<Value>("count").collect();
+        assert!(matches!(
+            count.as_slice(),
+            [Err(DgraphError::JsonDeserialize { .. })]
+        ));
+        let invalid = Response {
+            json: br#"{"all": [{"name": "Alice"}, {"name": 1}, {"name": "Bob"}]}"#.to_vec(),
+            ..Default::default()
+        };
+        let all: Vec<Result<Item, _>> = invalid.iter_block("all").collect();
+        assert_eq!(all.len(), 2);
+        assert!(all[0].is_ok());
+        assert!(all[1].is_err());
+        let broken = Response {
+            json: br#"{"all": ["#.to_vec(),
+            ..Default::default()
+        };
+        let all: Vec<Result<Value, _>> = broken.iter_block("all").collect();
+        assert_eq!(all.len(), 1);
+        assert!(all[0].is_err());
+    }
+
+    #[test]
+    fn facets() {
+        let response = Response {
+            json: br#"{"q": [{"uid": "0x1", "name|origin": "french"}], "other": 1}"#.to_vec(),
+            ..Default::default()
+        };
+        let facets = response.facets("q").expect("JSON");
+        assert_eq!(facets.len(), 1);
+        assert_eq!(facets[0]["name"]["origin"], "french");
+        assert!(response.facets("missing").expect("JSON").is_empty());
+        assert!(matches!(
+            response.facets("other"),
+            Err(DgraphError::JsonDeserialize { .. })
+        ));
+    }
+
+    #[test]
+    fn deserialize_seed() {
+        let response = Response {
+            json: br#"{"items": [{"name": "Alice"}, {"name": "Bob"}]}"#.to_vec(),
+            ..Default::default()
+        };
+        let len = response.deserialize_seed(NamesLen(PhantomData));
+        assert_eq!(len.expect("JSON"), 8);
+    }
+
+    #[cfg(any(
+        feature = "dgraph-1-1",
+        feature = "dgraph-21-03",
+        feature = "dgraph-24-02"
+    ))]
+    #[test]
+    fn headers() {
+        let mut response = Response::default();
+        response.hdrs.insert(
+            "key".to_string(),
+            crate::api::ListOfString {
+                value: vec!["a".to_string(), "b".to_string()],
+            },
+        );
+        let headers = response.headers();
+        assert_eq!(headers.len(), 1);
+        assert_eq!(headers["key"], vec!["a".to_string(), "b".to_string()]);
+    }
+
+    #[cfg(any(
+        feature = "dgraph-1-1",
+        feature = "dgraph-21-03",
+        feature = "dgraph-24-02"
+    ))]
+    #[test]
+    fn rdf_triples() {
+        let response = Response {
+            rdf: br#"<0x1> <name> "Alice \"A\"" .
+<0x1> <name> "Alicia"@es .
+<0x1> <age> "25"^^<xs:int> .
+<0x1> <friend> <0x2> .
+
+_:b <name> "\u0042ob" .
+"#
+            .to_vec(),
+            ..Default::default()
+        };
+        let triples = response.rdf_triples().expect("RDF");
+        let expected = vec![
+            ("0x1", "name", "Alice \"A\""),
+            ("0x1", "name", "Alicia"),
+            ("0x1", "age", "25"),
+            ("0x1", "friend", "0x2"),
+            ("_:b", "name", "Bob"),
+        ];
+        let expected: Vec<(String, String, String)> = expected
+            .into_iter()
+            .map(|(s, p, o)| (s.to_string(), p.to_string(), o.to_string()))
+            .collect();
+        assert_eq!(triples, expected);
+    }
+
+    #[cfg(any(
+        feature = "dgraph-1-1",
+        feature = "dgraph-21-03",
+        feature = "dgraph-24-02"
+    ))]
+    #[test]
+    fn affected_count() {
+        assert_eq!(Response::default().affected_count(), 0);
+        let mut metrics = crate::api::Metrics::default();
+        metrics.num_uids.insert("_total".to_string(), 5);
+        metrics.num_uids.insert("mutation_cost".to_string(), 5);
+        let response = Response {
+            metrics: Some(metrics),
+            ..Default::default()
+        };
+        assert_eq!(response.affected_count(), 5);
+    }
+
+    #[cfg(any(
+        feature = "dgraph-1-1",
+        feature = "dgraph-21-03",
+        feature = "dgraph-24-02"
+    ))]
+    #[test]
+    fn invalid_rdf_triples() {
+        let response = Response {
+            rdf: b"<0x1> <name> \"Alice\" .\n<0x1> <name> \"Alice .\n".to_vec(),
+            ..Default::default()
+        };
+        let err = response.rdf_triples().expect_err("Invalid RDF");
+        assert!(matches!(
+            err.downcast_ref::<crate::DgraphError>(),
+            Some(crate::DgraphError::InvalidRdf(2))
+        ));
+        let response = Response {
+            rdf: b"<0x1> <name> \"Alice\" .\n<0x1> <name> \"\xff\" .\n".to_vec(),
+            ..Default::default()
+        };
+        let err = response.rdf_triples().expect_err("Invalid UTF-8");
+        assert!(matches!(
+            err.downcast_ref::<crate::DgraphError>(),
+            Some(crate::DgraphError::InvalidRdf(2))
+        ));
+    }
+
+    #[test]
+    fn deserialize_seed_trailing_characters() {
+        let response = Response {
+            json: br#"{"items": []} x"#.to_vec(),
+            ..Default::default()
+        };
+        let len = response.deserialize_seed(NamesLen(PhantomData));
+        assert!(len.is_err());
+    }
+
+    #[test]
+    fn try_into_json_error() {
+        let response = Response {
+            json: br#"[{"uid":"0x1"}]"#.to_vec(),
+            ..Default::default()
+        };
+        let err = response
+            .try_into::<Vec<Item>>()
+            .err()
+            .expect("Missing field");
+        assert!(matches!(
+            err,
+            DgraphError::JsonDeserialize { raw_len: 15, .. }
+        ));
+        assert!(err
+            .to_string()
+            .starts_with("Dgraph: Failed to deserialize 15-byte response: missing field `name`"));
+    }
+}
@@ -0,0 +1,351 @@
+///
+/// Builder of Dgraph schema, which produces schema string accepted by `set_schema`.
+///
+/// Every predicate and type definition is written on its own line in order they were added.
+/// Builder does not validate schema, invalid definitions are rejected by Dgraph when schema is
+/// altered.
+///
+/// # Example
+///
+/// ```
+/// use dgraph_tonic::SchemaBuilder;
+///
+/// let schema = SchemaBuilder::new()
+///     .predicate("name").string().index(&["exact", "term"]).upsert()
+///     .predicate("age").int()
+///     .predicate("friend").uid().list().reverse()
+///     .type_("Person").fields(&["name", "age", "friend"])
+///     .build();
+/// assert_eq!(
+///     schema,
+///     "name: string @index(exact, term) @upsert .\n\
+///      age: int .\n\
+///      friend: [uid] @reverse .\n\
+///      type Person {\n  name\n  age\n  friend\n}\n"
+/// );
+/// ```
+///
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct SchemaBuilder {
+    definitions: Vec<String>,
+}
+
+impl SchemaBuilder {
+    ///
+    /// Create empty schema.
+    ///
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    ///
+    /// Start definition of predicate. Predicate has `default` type until other type is set.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - name of predicate
+    ///
+    pub fn predicate<S: Into<String>>(self, name: S) -> PredicateBuilder {
+        PredicateBuilder {
+            schema: self,
+            name: name.into(),
+            kind: "default",
+            list: false,
+            directives: Vec::new(),
+        }
+    }
+
+    ///
+    /// Start definition of type. Trailing underscore is used, because `type` is Rust keyword.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - name of type
+    ///
+    pub fn type_<S: Into<String>>(self, name: S) -> TypeBuilder {
+        TypeBuilder {
+            schema: self,
+            name: name.into(),
+        }
+    }
+
+    ///
+    /// Return schema string.
+    ///
+    pub fn build(self) -> String {
+        self.definitions
+            .into_iter()
+            .map(|definition| definition + "\n")
+            .collect()
+    }
+}
+
+impl From<SchemaBuilder> for String {
+    fn from(schema: SchemaBuilder) -> Self {
+        schema.build()
+    }
+}
+
+///
+/// Definition of one predicate in `SchemaBuilder`.
+///
+#[derive(Clone, Debug, PartialEq)]
+pub struct PredicateBuilder {
+    schema: SchemaBuilder,
+    name: String,
+    kind: &'static str,
+    list: bool,
+    directives: Vec<String>,
+}
+
+impl PredicateBuilder {
+    fn kind(mut self, kind: &'static str) -> Self {
+        self.kind = kind;
+        self
+    }
+
+    fn directive<S: Into<String>>(mut self, directive: S) -> Self {
+        self.directives.push(directive.into());
+        self
+    }
+
+    ///
+    /// Predicate has `string` type.
+    ///
+    pub fn string(self) -> Self {
+        self.kind("string")
+    }
+
+    ///
+    /// Predicate has `int` type.
+    ///
+    pub fn int(self) -> Self {
+        self.kind("int")
+    }
+
+    ///
+    /// Predicate has `float` type.
+    ///
+    pub fn float(self) -> Self {
+        self.kind("float")
+    }
+
+    ///
+    /// Predicate has `bool` type.
+    ///
+    pub fn bool(self) -> Self {
+        self.kind("bool")
+    }
+
+    ///
+    /// Predicate has `datetime` type.
+    ///
+    pub fn datetime(self) -> Self {
+        self.kind("datetime")
+    }
+
+    ///
+    /// Predicate has `geo` type.
+    ///
+    pub fn geo(self) -> Self {
+        self.kind("geo")
+    }
+
+    ///
+    /// Predicate has `password` type.
+    ///
+    pub fn password(self) -> Self {
+        self.kind("password")
+    }
+
+    ///
+    /// Predicate is edge to other node.
+    ///
+    pub fn uid(self) -> Self {
+        self.kind("uid")
+    }
+
+    ///
+    /// Predicate holds list of values of its type, e.g. `[string]`.
+    ///
+    pub fn list(mut self) -> Self {
+        self.list = true;
+        self
+    }
+
+    ///
+    /// Add `@index` directive with given tokenizers.
+    ///
+    /// # Arguments
+    ///
+    /// * `tokenizers` - e.g. `exact`, `term`, `hash` or `year`
+    ///
+    pub fn index(self, tokenizers: &[&str]) -> Self {
+        self.directive(format!("@index({})", tokenizers.join(", ")))
+    }
+
+    ///
+    /// Add `@upsert` directive.
+    ///
+    pub fn upsert(self) -> Self {
+        self.directive("@upsert")
+    }
+
+    ///
+    /// Add `@reverse` directive.
+    ///
+    pub fn reverse(self) -> Self {
+        self.directive("@reverse")
+    }
+
+    ///
+    /// Add `@count` directive.
+    ///
+    pub fn count(self) -> Self {
+        self.directive("@count")
+    }
+
+    ///
+    /// Add `@lang` directive.
+    ///
+    pub fn lang(self) -> Self {
+        self.directive("@lang")
+    }
+
+    ///
+    /// Add `@noconflict` directive.
+    ///
+    pub fn noconflict(self) -> Self {
+        self.directive("@noconflict")
+    }
+
+    ///
+    /// Finish predicate and start definition of next predicate.
+    ///
+    pub fn predicate<S: Into<String>>(self, name: S) -> PredicateBuilder {
+        self.finish().predicate(name)
+    }
+
+    ///
+    /// Finish predicate and start definition of type.
+    ///
+    pub fn type_<S: Into<String>>(self, name: S) -> TypeBuilder {
+        self.finish().type_(name)
+    }
+
+    ///
+    /// Finish predicate and return schema string.
+    ///
+    pub fn build(self) -> String {
+        self.finish().build()
+    }
+
+    ///
+    /// Finish predicate and return schema builder.
+    ///
+    pub fn finish(self) -> SchemaBuilder {
+        let mut schema = self.schema;
+        let kind = if self.list {
+            format!("[{}]", self.kind)
+        } else {
+            self.kind.to_string()
+        };
+        let mut definition = format!("{}: {}", self.name, kind);
+        for directive in self.directives {
+            definition.push(' ');
+            definition.push_str(&directive);
+        }
+        definition.push_str(" .");
+        schema.definitions.push(definition);
+        schema
+    }
+}
+
+impl From<PredicateBuilder> for String {
+    fn from(predicate: PredicateBuilder) -> Self {
+        predicate.build()
+    }
+}
+
+///
+/// Definition of one type in `SchemaBuilder`.
+///
+#[derive(Clone, Debug, PartialEq)]
+pub struct TypeBuilder {
+    schema: SchemaBuilder,
+    name: String,
+}
+
+impl TypeBuilder {
+    ///
+    /// Set fields of type and return schema builder.
+    ///
+    /// # Arguments
+    ///
+    /// * `fields` - names of predicates of type
+    ///
+    pub fn fields(self, fields: &[&str]) -> SchemaBuilder {
+        let mut schema = self.schema;
+        let fields: String = fields
+            .iter()
+            .map(|field| format!("  {}\n", field))
+            .collect();
+        schema
+            .definitions
+            .push(format!("type {} {{\n{}}}", self.name, fields));
+        schema
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::SchemaBuilder;
+
+    #[test]
+    fn predicates() {
+        let schema = SchemaBuilder::new()
+            .predicate("name")
+            .string()
+            .index(&["exact"])
+            .build();
+        assert_eq!(schema, "name: string @index(exact) .\n");
+        let schema: String = SchemaBuilder::new()
+            .predicate("nickname")
+            .string()
+            .list()
+            .index(&["hash", "trigram"])
+            .lang()
+            .count()
+            .predicate("born")
+            .datetime()
+            .index(&["year"])
+            .predicate("location")
+            .geo()
+            .index(&["geo"])
+            .noconflict()
+            .predicate("secret")
+            .password()
+            .predicate("misc")
+            .into();
+        assert_eq!(
+            schema,
+            "nickname: [string] @index(hash, trigram) @lang @count .\n\
+             born: datetime @index(year) .\n\
+             location: geo @index(geo) @noconflict .\n\
+             secret: password .\n\
+             misc: default .\n"
+        );
+    }
+
+    #[test]
+    fn types() {
+        let schema = SchemaBuilder::new()
+            .type_("Person")
+            .fields(&["name", "age"])
+            .type_("Empty")
+            .fields(&[])
+            .build();
+        assert_eq!(schema, "type Person {\n  name\n  age\n}\ntype Empty {\n}\n");
+        assert_eq!(SchemaBuilder::new().build(), "");
+    }
+}
@@ -396,17 +396,6 @@ pub mod dgraph_client {
     pub struct DgraphClient<T> {
         inner: tonic::client::Grpc<T>,
     }
-    impl DgraphClient<tonic::transport::Channel> {
-        /// Attempt to create a new client by connecting to a given endpoint.
-        pub async fn connect<D>(dst: D) -> Result<Self, tonic::transport::Error>
-        where
-            D: std::convert::TryInto<tonic::transport::Endpoint>,
-            D::Error: Into<StdError>,
-        {
-            let conn = tonic::transport::Endpoint::new(dst)?.connect().await?;
-            Ok(Self::new(conn))
-        }
-    }
     impl<T> DgraphClient<T>
     where
         T: tonic::client::GrpcService<tonic::body::BoxBody>,
@@ -456,10 +445,26 @@ pub mod dgraph_client {
             self.inner = self.inner.accept_compressed(encoding);
             self
         }
+        /// Limits the maximum size of a decoded message.
+        ///
+        /// Default: `4MB`
+        #[must_use]
+        pub fn max_decoding_message_size(mut self, limit: usize) -> Self {
+            self.inner = self.inner.max_decoding_message_size(limit);
+            self
+        }
+        /// Limits the maximum size of an encoded message.
+        ///
+        /// Default: `usize::MAX`
+        #[must_use]
+        pub fn max_encoding_message_size(mut self, limit: usize) -> Self {
+            self.inner = self.inner.max_encoding_message_size(limit);
+            self
+        }
         pub async fn login(
             &mut self,
             request: impl tonic::IntoRequest<super::LoginRequest>,
-        ) -> Result<tonic::Response<super::Response>, tonic::Status> {
+        ) -> std::result::Result<tonic::Response<super::Response>, tonic::Status> {
             self.inner
                 .ready()
                 .await
@@ -471,12 +476,14 @@ pub mod dgraph_client {
                 })?;
             let codec = tonic::codec::ProstCodec::default();
             let path = http::uri::PathAndQuery::from_static("/api.Dgraph/Login");
-            self.inner.unary(request.into_request(), path, codec).await
+            let mut req = request.into_request();
+            req.extensions_mut().insert(GrpcMethod::new("api.Dgraph", "Login"));
+            self.inner.unary(req, path, codec).await
         }
         pub async fn query(
             &mut self,
             request: impl tonic::IntoRequest<super::Request>,
-        ) -> Result<tonic::Response<super::Response>, tonic::Status> {
+        ) -> std::result::Result<tonic::Response<super::Response>, tonic::Status> {
             self.inner
                 .ready()
                 .await
@@ -488,12 +495,14 @@ pub mod dgraph_client {
                 })?;
             let codec = tonic::codec::ProstCodec::default();
             let path = http::uri::PathAndQuery::from_static("/api.Dgraph/Query");
-            self.inner.unary(request.into_request(), path, codec).await
+            let mut req = request.into_request();
+            req.extensions_mut().insert(GrpcMethod::new("api.Dgraph", "Query"));
+            self.inner.unary(req, path, codec).await
         }
         pub async fn mutate(
             &mut self,
             request: impl tonic::IntoRequest<super::Mutation>,
-        ) -> Result<tonic::Response<super::Assigned>, tonic::Status> {
+        ) -> std::result::Result<tonic::Response<super::Assigned>, tonic::Status> {
             self.inner
                 .ready()
                 .await
@@ -505,12 +514,14 @@ pub mod dgraph_client {
                 })?;
             let codec = tonic::codec::ProstCodec::default();
             let path = http::uri::PathAndQuery::from_static("/api.Dgraph/Mutate");
-            self.inner.unary(request.into_request(), path, codec).await
+            let mut req = request.into_request();
+            req.extensions_mut().insert(GrpcMethod::new("api.Dgraph", "Mutate"));
+            self.inner.unary(req, path, codec).await
         }
         pub async fn alter(
             &mut self,
             request: impl tonic::IntoRequest<super::Operation>,
-        ) -> Result<tonic::Response<super::Payload>, tonic::Status> {
+        ) -> std::result::Result<tonic::Response<super::Payload>, tonic::Status> {
             self.inner
                 .ready()
                 .await
@@ -522,12 +533,14 @@ pub mod dgraph_client {
                 })?;
             let codec = tonic::codec::ProstCodec::default();
             let path = http::uri::PathAndQuery::from_static("/api.Dgraph/Alter");
-            self.inner.unary(request.into_request(), path, codec).await
+            let mut req = request.into_request();
+            req.extensions_mut().insert(GrpcMethod::new("api.Dgraph", "Alter"));
+            self.inner.unary(req, path, codec).await
         }
         pub async fn commit_or_abort(
             &mut self,
             request: impl tonic::IntoRequest<super::TxnContext>,
-        ) -> Result<tonic::Response<super::TxnContext>, tonic::Status> {
+        ) -> std::result::Result<tonic::Response<super::TxnContext>, tonic::Status> {
             self.inner
                 .ready()
                 .await
@@ -539,12 +552,14 @@ pub mod dgraph_client {
                 })?;
             let codec = tonic::codec::ProstCodec::default();
             let path = http::uri::PathAndQuery::from_static("/api.Dgraph/CommitOrAbort");
-            self.inner.unary(request.into_request(), path, codec).await
+            let mut req = request.into_request();
+            req.extensions_mut().insert(GrpcMethod::new("api.Dgraph", "CommitOrAbort"));
+            self.inner.unary(req, path, codec).await
         }
         pub async fn check_version(
             &mut self,
             request: impl tonic::IntoRequest<super::Check>,
-        ) -> Result<tonic::Response<super::Version>, tonic::Status> {
+        ) -> std::result::Result<tonic::Response<super::Version>, tonic::Status> {
             self.inner
                 .ready()
                 .await
@@ -556,7 +571,9 @@ pub mod dgraph_client {
                 })?;
             let codec = tonic::codec::ProstCodec::default();
             let path = http::uri::PathAndQuery::from_static("/api.Dgraph/CheckVersion");
-            self.inner.unary(request.into_request(), path, codec).await
+            let mut req = request.into_request();
+            req.extensions_mut().insert(GrpcMethod::new("api.Dgraph", "CheckVersion"));
+            self.inner.unary(req, path, codec).await
         }
     }
 }
@@ -0,0 +1,3 @@
+pub use crate::api::v24_02_0::api::*;
+
+mod api;
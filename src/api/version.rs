@@ -0,0 +1,67 @@
+use crate::Version;
+
+impl Version {
+    ///
+    /// Parse version tag into `(major, minor, patch)` numbers.
+    ///
+    /// Leading `v` and suffixes like pre-release or git hash (`v20.11.0-rc1-5-gabcdef`) are
+    /// ignored. Missing patch number is returned as `0`.
+    ///
+    /// Return `None` if tag does not start with `major.minor` numbers.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use dgraph_tonic::Version;
+    ///
+    /// let version = Version {
+    ///     tag: "v21.03.0-g1234abc".into(),
+    /// };
+    /// assert_eq!(version.parse(), Some((21, 3, 0)));
+    /// ```
+    ///
+    pub fn parse(&self) -> Option<(u16, u16, u16)> {
+        let tag = self.tag.trim();
+        let tag = tag
+            .strip_prefix('v')
+            .or_else(|| tag.strip_prefix('V'))
+            .unwrap_or(tag);
+        let end = tag
+            .find(|c: char| !c.is_ascii_digit() && c != '.')
+            .unwrap_or(tag.len());
+        let mut numbers = tag[..end].split('.');
+        let major = numbers.next()?.parse().ok()?;
+        let minor = numbers.next()?.parse().ok()?;
+        let patch = match numbers.next() {
+            Some(patch) => patch.parse().ok()?,
+            None => 0,
+        };
+        Some((major, minor, patch))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::Version;
+
+    fn parse(tag: &str) -> Option<(u16, u16, u16)> {
+        Version { tag: tag.into() }.parse()
+    }
+
+    #[test]
+    fn parse_tag() {
+        assert_eq!(parse("v21.03.0"), Some((21, 3, 0)));
+        assert_eq!(parse("v1.1.1"), Some((1, 1, 1)));
+        assert_eq!(parse("20.11.2"), Some((20, 11, 2)));
+        assert_eq!(parse("v20.11.0-rc1-5-gabcdef"), Some((20, 11, 0)));
+        assert_eq!(parse("v21.03-slash"), Some((21, 3, 0)));
+    }
+
+    #[test]
+    fn parse_invalid_tag() {
+        assert_eq!(parse(""), None);
+        assert_eq!(parse("dev"), None);
+        assert_eq!(parse("v21"), None);
+        assert_eq!(parse("v21..0"), None);
+    }
+}
@@ -1,8 +1,13 @@
 use std::sync::{Arc, Mutex};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
 
 use anyhow::Result;
 use async_trait::async_trait;
+use base64::engine::general_purpose::URL_SAFE_NO_PAD;
+use base64::Engine;
+use http::Uri;
 use prost::Message;
+use serde_derive::Deserialize;
 use tonic::metadata::MetadataValue;
 use tonic::service::Interceptor;
 use tonic::{Request, Status};
@@ -82,6 +87,10 @@ impl<C: ILazyChannel> ILazyClient for LazyAclClient<C> {
     fn channel(self) -> Self::Channel {
         self.channel
     }
+
+    fn uri(&self) -> &Uri {
+        self.channel.uri()
+    }
 }
 
 ///
@@ -92,7 +101,7 @@ impl<C: ILazyChannel> ILazyClient for LazyAclClient<C> {
 pub struct Acl<C: ILazyChannel> {
     access_jwt: Arc<Mutex<String>>,
     refresh_jwt: Mutex<String>,
-    clients: Vec<LazyAclClient<C>>,
+    clients: Arc<[LazyAclClient<C>]>,
 }
 
 #[async_trait]
@@ -105,7 +114,15 @@ impl<C: ILazyChannel> IClient for Acl<C> {
     }
 
     fn clients(self) -> Vec<Self::Client> {
-        self.clients
+        self.clients.to_vec()
+    }
+
+    fn all_clients(&self) -> Vec<Self::Client> {
+        self.clients.to_vec()
+    }
+
+    fn shared_clients(&self) -> Arc<[Self::Client]> {
+        Arc::clone(&self.clients)
     }
 }
 
@@ -172,7 +189,7 @@ pub type TxnAclTlsMutated = TxnMutatedType<LazyAclClient<LazyTlsChannel>>;
 struct Login<T: Into<String>> {
     user_id: T,
     password: T,
-    #[cfg(feature = "dgraph-21-03")]
+    #[cfg(any(feature = "dgraph-21-03", feature = "dgraph-24-02"))]
     namespace: Option<u64>,
 }
 
@@ -182,12 +199,16 @@ impl<S: IClient> ClientVariant<S> {
         let login = LoginRequest {
             userid: login.user_id.into(),
             password: login.password.into(),
-            #[cfg(feature = "dgraph-21-03")]
+            #[cfg(any(feature = "dgraph-21-03", feature = "dgraph-24-02"))]
             namespace: login.namespace.unwrap_or_default(),
             ..Default::default()
         };
         let resp = stub.login(login).await?;
         let jwt: Jwt = Jwt::decode(resp.json.as_slice())?;
+        Ok(self.into_acl(jwt))
+    }
+
+    fn into_acl(self, jwt: Jwt) -> AclClientType<S::Channel> {
         let access_jwt = Arc::new(Mutex::new(jwt.access_jwt));
         let clients = self
             .extra
@@ -197,15 +218,15 @@ impl<S: IClient> ClientVariant<S> {
                 let channel = client.channel();
                 LazyAclClient::new(channel, Arc::clone(&access_jwt))
             })
-            .collect::<Vec<LazyAclClient<S::Channel>>>();
-        Ok(AclClientType {
+            .collect::<Arc<[LazyAclClient<S::Channel>]>>();
+        AclClientType {
             state: self.state,
             extra: Acl {
                 clients,
                 access_jwt,
                 refresh_jwt: Mutex::new(jwt.refresh_jwt),
             },
-        })
+        }
     }
 
     ///
@@ -241,7 +262,7 @@ impl<S: IClient> ClientVariant<S> {
         self.do_login(Login {
             password,
             user_id,
-            #[cfg(feature = "dgraph-21-03")]
+            #[cfg(any(feature = "dgraph-21-03", feature = "dgraph-24-02"))]
             namespace: None,
         })
         .await
@@ -275,7 +296,7 @@ impl<S: IClient> ClientVariant<S> {
     /// }
     /// ```
     ///
-    #[cfg(feature = "dgraph-21-03")]
+    #[cfg(any(feature = "dgraph-21-03", feature = "dgraph-24-02"))]
     pub async fn login_into_namespace<T: Into<String>>(
         self,
         user_id: T,
@@ -285,11 +306,101 @@ impl<S: IClient> ClientVariant<S> {
         self.do_login(Login {
             password,
             user_id,
-            #[cfg(feature = "dgraph-21-03")]
+            #[cfg(any(feature = "dgraph-21-03", feature = "dgraph-24-02"))]
             namespace: Some(namespace),
         })
         .await
     }
+
+    ///
+    /// Consume original client and return client with acl turn on, which uses JWT tokens of
+    /// previous login obtained with `tokens()`.
+    ///
+    /// Access JWT is used until it expires. When it is already expired or it cannot be decoded,
+    /// login is refreshed with refresh JWT immediately, so invalid tokens are rejected here and
+    /// not by first operation. Persist new tokens after login, because refreshed ones replace
+    /// the original tokens.
+    ///
+    /// **Security:** JWT tokens grant the same permissions as password of user until refresh JWT
+    /// expires. Store them only where password could be stored, e.g. in file readable only by
+    /// owner, and never log them.
+    ///
+    /// # Arguments
+    ///
+    /// * `access_jwt`: Access JWT of previous login
+    /// * `refresh_jwt`: Refresh JWT of previous login
+    ///
+    /// # Errors
+    ///
+    /// * gRPC communication error
+    /// * Dgraph error when access JWT is expired and refresh JWT is not valid
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use dgraph_tonic::Client;
+    ///
+    /// #[tokio::main]
+    /// async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    ///     let client = Client::new("http://127.0.0.1:19080").expect("Dgraph client");
+    ///     let logged = client.login("groot", "password").await.expect("Logged in");
+    ///     // persist tokens, e.g. into file readable only by owner
+    ///     let (access_jwt, refresh_jwt) = logged.tokens();
+    ///     // and use them by next run instead of password
+    ///     let client = Client::new("http://127.0.0.1:19080").expect("Dgraph client");
+    ///     let logged = client
+    ///         .login_with_tokens(access_jwt, refresh_jwt)
+    ///         .await
+    ///         .expect("Logged in");
+    ///     Ok(())
+    /// }
+    /// ```
+    ///
+    pub async fn login_with_tokens<T: Into<String>>(
+        self,
+        access_jwt: T,
+        refresh_jwt: T,
+    ) -> Result<AclClientType<S::Channel>> {
+        let jwt = Jwt {
+            access_jwt: access_jwt.into(),
+            refresh_jwt: refresh_jwt.into(),
+        };
+        let expired = jwt_expired(&jwt.access_jwt, SystemTime::now() + JWT_EXPIRY_MARGIN);
+        let client = self.into_acl(jwt);
+        if expired {
+            client.refresh_login().await?;
+        }
+        Ok(client)
+    }
+}
+
+///
+/// Access JWT which expires sooner is considered as expired.
+///
+const JWT_EXPIRY_MARGIN: Duration = Duration::from_secs(10);
+
+///
+/// Claims of JWT which are checked by client.
+///
+#[derive(Deserialize)]
+struct JwtClaims {
+    exp: u64,
+}
+
+///
+/// Return true if JWT expires before `at` or its expiration cannot be decoded.
+///
+fn jwt_expired(token: &str, at: SystemTime) -> bool {
+    let exp = token
+        .split('.')
+        .nth(1)
+        .and_then(|payload| URL_SAFE_NO_PAD.decode(payload).ok())
+        .and_then(|payload| serde_json::from_slice::<JwtClaims>(&payload).ok())
+        .map(|claims| UNIX_EPOCH + Duration::from_secs(claims.exp));
+    match exp {
+        Some(exp) => exp <= at,
+        None => true,
+    }
 }
 
 impl<C: ILazyChannel> AclClientType<C> {
@@ -335,12 +446,92 @@ impl<C: ILazyChannel> AclClientType<C> {
         }
         Ok(())
     }
+
+    ///
+    /// Return actual access and refresh JWT tokens of logged client.
+    ///
+    /// Tokens can be persisted and used by `login_with_tokens` later. They grant permissions of
+    /// logged user, so keep them secret.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use dgraph_tonic::Client;
+    ///
+    /// #[tokio::main]
+    /// async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    ///     let client = Client::new("http://127.0.0.1:19080").expect("Dgraph client");
+    ///     let logged = client.login("groot", "password").await.expect("Logged in");
+    ///     let (access_jwt, refresh_jwt) = logged.tokens();
+    ///     Ok(())
+    /// }
+    /// ```
+    ///
+    pub fn tokens(&self) -> (String, String) {
+        let access_jwt = self.extra.access_jwt.lock().unwrap().to_owned();
+        let refresh_jwt = self.extra.refresh_jwt.lock().unwrap().to_owned();
+        (access_jwt, refresh_jwt)
+    }
+
+    ///
+    /// Return logged client which sends requests into another namespace.
+    ///
+    /// Guardian of namespaces logged into namespace 0 can query and mutate data of other
+    /// namespaces. `namespace` metadata is attached to every request together with access JWT,
+    /// so one logged client can work across namespaces without new login.
+    ///
+    /// # Arguments
+    ///
+    /// * `namespace`: Namespace Id
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use dgraph_tonic::{Client, Query};
+    ///
+    /// #[tokio::main]
+    /// async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    ///     let client = Client::new("http://127.0.0.1:19080").expect("Dgraph client");
+    ///     let guardian = client
+    ///         .login_into_namespace("groot", "password", 0)
+    ///         .await
+    ///         .expect("Logged in")
+    ///         .with_query_namespace(1);
+    ///     let mut txn = guardian.new_read_only_txn();
+    ///     let _ = txn.query("{ q(func: has(name)) { name } }").await;
+    ///     Ok(())
+    /// }
+    /// ```
+    ///
+    #[cfg(any(feature = "dgraph-21-03", feature = "dgraph-24-02"))]
+    pub fn with_query_namespace(self, namespace: u64) -> Self {
+        self.for_namespace(namespace)
+    }
 }
 
 #[cfg(test)]
 mod tests {
+    use std::time::{Duration, UNIX_EPOCH};
+
+    use base64::engine::general_purpose::URL_SAFE_NO_PAD;
+    use base64::Engine;
+
+    use super::jwt_expired;
     use crate::Client;
 
+    fn jwt(payload: &str) -> String {
+        format!("e30.{}.signature", URL_SAFE_NO_PAD.encode(payload))
+    }
+
+    #[test]
+    fn jwt_expiration() {
+        let token = jwt(r#"{"exp": 1000, "userid": "groot"}"#);
+        assert!(!jwt_expired(&token, UNIX_EPOCH + Duration::from_secs(999)));
+        assert!(jwt_expired(&token, UNIX_EPOCH + Duration::from_secs(1000)));
+        assert!(jwt_expired(&jwt(r#"{"userid": "groot"}"#), UNIX_EPOCH));
+        assert!(jwt_expired("invalid", UNIX_EPOCH));
+    }
+
     #[tokio::test]
     async fn login() {
         let client = Client::new("http://127.0.0.1:19080")
@@ -353,7 +544,7 @@ mod tests {
         assert!(client.is_ok());
     }
 
-    #[cfg(feature = "dgraph-21-03")]
+    #[cfg(any(feature = "dgraph-21-03", feature = "dgraph-24-02"))]
     #[tokio::test]
     async fn login_into_namespace() {
         let client = Client::new("http://127.0.0.1:19080")
@@ -366,7 +557,7 @@ mod tests {
         assert!(client.is_ok());
     }
 
-    #[cfg(feature = "dgraph-21-03")]
+    #[cfg(any(feature = "dgraph-21-03", feature = "dgraph-24-02"))]
     #[tokio::test]
     async fn deny_login_into_namespace() {
         let client = Client::new("http://127.0.0.1:19080")
@@ -386,4 +577,85 @@ mod tests {
         let refresh = client.refresh_login().await;
         assert!(refresh.is_ok());
     }
+
+    #[tokio::test]
+    async fn login_with_tokens() {
+        let logged = Client::new("http://127.0.0.1:19080")
+            .unwrap()
+            .login("groot", "password")
+            .await
+            .expect("logged");
+        let (access_jwt, refresh_jwt) = logged.tokens();
+        let client = Client::new("http://127.0.0.1:19080")
+            .unwrap()
+            .login_with_tokens(access_jwt.to_owned(), refresh_jwt.to_owned())
+            .await
+            .expect("logged with tokens");
+        assert_eq!(client.tokens(), (access_jwt, refresh_jwt.to_owned()));
+        // expired access JWT is refreshed
+        let client = Client::new("http://127.0.0.1:19080")
+            .unwrap()
+            .login_with_tokens("expired".to_string(), refresh_jwt.to_owned())
+            .await
+            .expect("refreshed");
+        assert_ne!(client.tokens().0, "expired");
+        let client = Client::new("http://127.0.0.1:19080")
+            .unwrap()
+            .login_with_tokens("expired", "invalid")
+            .await;
+        assert!(client.is_err());
+    }
+
+    #[cfg(any(feature = "dgraph-21-03", feature = "dgraph-24-02"))]
+    #[tokio::test]
+    async fn with_query_namespace() {
+        use std::sync::{Arc, Mutex};
+
+        use prost::Message;
+
+        use crate::api::{Jwt, LoginRequest, Response, TxnContext};
+        use crate::fake_server::{serve, FakeReply};
+        use crate::Query;
+
+        let received = Arc::new(Mutex::new(Vec::new()));
+        let (addr, _server) = {
+            let received = Arc::clone(&received);
+            serve(move |request| {
+                if request.path.ends_with("/Login") {
+                    assert_eq!(request.message::<LoginRequest>().namespace, 0);
+                    let jwt = Jwt {
+                        access_jwt: "access".into(),
+                        refresh_jwt: "refresh".into(),
+                    };
+                    return FakeReply::new(&Response {
+                        json: jwt.encode_to_vec(),
+                        ..Default::default()
+                    });
+                }
+                received.lock().unwrap().push(request.headers);
+                FakeReply::new(&Response {
+                    txn: Some(TxnContext {
+                        start_ts: 1,
+                        ..Default::default()
+                    }),
+                    ..Default::default()
+                })
+            })
+            .await
+        };
+        let client = Client::new(addr)
+            .unwrap()
+            .login_into_namespace("groot", "password", 0)
+            .await
+            .expect("logged")
+            .with_query_namespace(2);
+        let mut txn = client.new_read_only_txn();
+        txn.query("{ q(func: uid(0x1)) { uid } }")
+            .await
+            .expect("Query");
+        let received = received.lock().unwrap();
+        assert_eq!(received.len(), 1);
+        assert_eq!(received[0]["accessjwt"], "access");
+        assert_eq!(received[0]["namespace"], "2");
+    }
 }
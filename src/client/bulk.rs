@@ -0,0 +1,210 @@
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::Instant;
+
+use anyhow::Result;
+use futures::{Stream, StreamExt};
+
+use crate::client::{ClientVariant, IClient};
+use crate::{Mutate, Mutation};
+
+///
+/// Summary of mutations executed by `bulk_mutate`.
+///
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct BulkStats {
+    ///
+    /// Number of committed mutations
+    ///
+    pub committed: usize,
+    ///
+    /// Number of mutations which failed or were aborted
+    ///
+    pub failed: usize,
+    ///
+    /// Number of uids assigned to blank nodes of committed mutations
+    ///
+    pub uids: usize,
+    ///
+    /// `true` if deadline stopped reading of mutations before stream was exhausted
+    ///
+    pub deadline_exceeded: bool,
+}
+
+impl<C: IClient> ClientVariant<C> {
+    ///
+    /// Commit every mutation from stream in own mutated transaction.
+    ///
+    /// At most `concurrency` transactions run at same time. No new mutation is started after
+    /// deadline and transactions still running at deadline are cancelled and counted as failed,
+    /// so returned stats are partial. Failed mutations are only counted and they do not stop
+    /// remaining work.
+    ///
+    /// # Arguments
+    ///
+    /// * `mutations`: stream of mutations, each one is committed with `mutate_and_commit_now`
+    /// * `concurrency`: maximal number of running transactions
+    /// * `deadline`: time after which no new transaction is started and running ones are cancelled
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use std::time::{Duration, Instant};
+    ///
+    /// use dgraph_tonic::{Client, Mutation};
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     let client = Client::new("http://127.0.0.1:19080").expect("Dgraph client");
+    ///     let mutations = (0..1000).map(|i| {
+    ///         let mut mu = Mutation::new();
+    ///         mu.set_set_nquads(format!(r#"_:n <name> "Person {}" ."#, i));
+    ///         mu
+    ///     });
+    ///     let deadline = Instant::now() + Duration::from_secs(60);
+    ///     let stats = client
+    ///         .bulk_mutate(futures::stream::iter(mutations), 8, deadline)
+    ///         .await
+    ///         .expect("Stats");
+    ///     println!("{:?}", stats);
+    /// }
+    /// ```
+    ///
+    pub async fn bulk_mutate<M>(
+        &self,
+        mutations: M,
+        concurrency: usize,
+        deadline: Instant,
+    ) -> Result<BulkStats>
+    where
+        M: Stream<Item = Mutation> + Send,
+    {
+        let deadline_exceeded = AtomicBool::new(false);
+        let stop = async {
+            tokio::time::sleep_until(deadline.into()).await;
+            deadline_exceeded.store(true, Ordering::Relaxed);
+        };
+        let mut stats = mutations
+            .take_until(stop)
+            .map(|mu| {
+                let txn = self.new_mutated_txn().with_deadline(deadline);
+                tokio::time::timeout_at(deadline.into(), txn.mutate_and_commit_now(mu))
+            })
+            .buffer_unordered(concurrency.max(1))
+            .fold(BulkStats::default(), |mut stats, response| async move {
+                match response {
+                    Ok(Ok(response)) => {
+                        stats.committed += 1;
+                        stats.uids += response.uids.len();
+                    }
+                    Ok(Err(_)) | Err(_) => stats.failed += 1,
+                }
+                stats
+            })
+            .await;
+        stats.deadline_exceeded = deadline_exceeded.load(Ordering::Relaxed);
+        Ok(stats)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use futures::stream;
+
+    use super::*;
+    use crate::Client;
+    #[cfg(feature = "acl")]
+    use crate::{AclClientType, LazyChannel};
+
+    #[cfg(not(feature = "acl"))]
+    async fn client() -> Client {
+        Client::new("http://127.0.0.1:19080").unwrap()
+    }
+
+    #[cfg(feature = "acl")]
+    async fn client() -> AclClientType<LazyChannel> {
+        let default = Client::new("http://127.0.0.1:19080").unwrap();
+        default.login("groot", "password").await.unwrap()
+    }
+
+    fn mutations(count: usize) -> Vec<Mutation> {
+        (0..count)
+            .map(|i| {
+                let mut mu = Mutation::new();
+                mu.set_set_nquads(format!(r#"_:n <name> "Bulk {}" ."#, i));
+                mu
+            })
+            .collect()
+    }
+
+    #[tokio::test]
+    async fn bulk_mutate() {
+        let client = client().await;
+        let deadline = Instant::now() + Duration::from_secs(30);
+        let stats = client
+            .bulk_mutate(stream::iter(mutations(10)), 4, deadline)
+            .await
+            .expect("Stats");
+        assert_eq!(stats.committed, 10);
+        assert_eq!(stats.uids, 10);
+        assert_eq!(stats.failed, 0);
+        assert!(!stats.deadline_exceeded);
+    }
+
+    #[tokio::test]
+    async fn bulk_mutate_failed() {
+        let client = Client::new("http://127.0.0.1:1").unwrap();
+        let deadline = Instant::now() + Duration::from_secs(30);
+        let stats = client
+            .bulk_mutate(stream::iter(mutations(3)), 2, deadline)
+            .await
+            .expect("Stats");
+        assert_eq!(stats.committed, 0);
+        assert_eq!(stats.failed, 3);
+        assert!(!stats.deadline_exceeded);
+    }
+
+    #[tokio::test]
+    async fn bulk_mutate_running_after_deadline() {
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        // connections are accepted, but server never responds
+        let server = tokio::spawn(async move {
+            let mut connections = Vec::new();
+            while let Ok((stream, _)) = listener.accept().await {
+                connections.push(stream);
+            }
+        });
+        let client = Client::new(format!("http://{}", addr)).unwrap();
+        let deadline = Instant::now() + Duration::from_millis(200);
+        let mutations = stream::iter(mutations(2)).chain(stream::pending());
+        let stats = tokio::time::timeout(
+            Duration::from_secs(5),
+            client.bulk_mutate(mutations, 2, deadline),
+        )
+        .await
+        .expect("Bounded by deadline")
+        .expect("Stats");
+        assert_eq!(stats.committed, 0);
+        assert_eq!(stats.failed, 2);
+        assert!(stats.deadline_exceeded);
+        server.abort();
+    }
+
+    #[tokio::test]
+    async fn bulk_mutate_deadline() {
+        let client = Client::new("http://127.0.0.1:1").unwrap();
+        let stats = client
+            .bulk_mutate(stream::pending(), 2, Instant::now())
+            .await
+            .expect("Stats");
+        assert_eq!(
+            stats,
+            BulkStats {
+                deadline_exceeded: true,
+                ..Default::default()
+            }
+        );
+    }
+}
@@ -4,34 +4,83 @@ use anyhow::Result;
 use async_trait::async_trait;
 use http::Uri;
 use std::fmt::Debug;
+#[cfg(feature = "uds")]
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::Arc;
-use tonic::transport::Channel;
+#[cfg(feature = "uds")]
+use tokio::net::UnixStream;
+use tokio::sync::Mutex;
+use tonic::transport::{Channel, Error as TransportError};
+use tower::service_fn;
+#[cfg(feature = "tracing")]
 use tracing::trace;
+#[cfg(feature = "tracing")]
 use tracing_attributes::instrument;
 
-use crate::client::lazy::{ILazyChannel, LazyClient};
-use crate::client::{balance_list, rnd_item, ClientState, ClientVariant, IClient};
+use crate::client::lazy::{ILazyChannel, ILazyClient, LazyClient};
+use crate::client::proxy::{env_proxy, parse_proxy, tunnel};
+use crate::client::{
+    balance_list, rnd_item, ClientState, ClientVariant, IClient, CHANNEL_SCHEME, UDS_SCHEME,
+};
+#[cfg(feature = "uds")]
+use crate::client::{uds_path, uds_uri};
+use crate::errors::ClientError;
+use crate::stub::Stub;
 use crate::{
-    Endpoint, EndpointConfig, Endpoints, TxnBestEffortType, TxnMutatedType, TxnReadOnlyType,
-    TxnType,
+    Endpoint, EndpointConfig, Endpoints, IDgraphClient, TxnBestEffortType, TxnMutatedType,
+    TxnReadOnlyType, TxnType,
 };
 
 ///
 /// Lazy initialization of gRPC channel
 ///
+/// Connected channel is shared by all clones, so clients lifted by `login` reuse established
+/// connection.
+///
+/// Connected channel is kept also when its connection is broken, e.g. after server restart,
+/// because channel reconnects itself on next request. Request which could not be sent over
+/// closed connection is repeated once, so restart of server is not visible to caller.
+///
 #[derive(Clone, Debug)]
 pub struct LazyChannel {
     uri: Uri,
-    channel: Option<Channel>,
+    channel: Arc<Mutex<Option<Channel>>>,
+    connected: Arc<AtomicBool>,
     endpoint_config: Option<Arc<dyn EndpointConfig>>,
+    proxy: Option<Uri>,
+    #[cfg(feature = "uds")]
+    socket: Option<PathBuf>,
 }
 
 impl LazyChannel {
     fn new(uri: Uri) -> Self {
+        Self {
+            #[cfg(feature = "uds")]
+            socket: uds_path(&uri),
+            uri,
+            channel: Arc::new(Mutex::new(None)),
+            connected: Arc::new(AtomicBool::new(false)),
+            endpoint_config: None,
+            proxy: None,
+        }
+    }
+
+    ///
+    /// Wrap channel provided by user. Channel is identified by placeholder uri with its index.
+    ///
+    fn from_channel(index: usize, channel: Channel) -> Self {
+        let uri = format!("{}://{}", CHANNEL_SCHEME, index)
+            .parse()
+            .expect("Placeholder uri");
         Self {
             uri,
-            channel: None,
+            channel: Arc::new(Mutex::new(Some(channel))),
+            connected: Arc::new(AtomicBool::new(true)),
             endpoint_config: None,
+            proxy: None,
+            #[cfg(feature = "uds")]
+            socket: None,
         }
     }
 
@@ -39,23 +88,108 @@ impl LazyChannel {
         self.endpoint_config = endpoint_config;
         self
     }
+
+    ///
+    /// Channel will be connected through proxy. Unix domain sockets and channels provided by user
+    /// are never proxied.
+    ///
+    fn with_proxy(mut self, proxy: Option<Uri>) -> Self {
+        let scheme = self.uri.scheme_str();
+        if scheme != Some(UDS_SCHEME) && scheme != Some(CHANNEL_SCHEME) {
+            self.proxy = proxy;
+            self.channel = Arc::new(Mutex::new(None));
+            self.connected = Arc::new(AtomicBool::new(false));
+        }
+        self
+    }
+
+    ///
+    /// Unix domain socket path is not valid HTTP/2 authority, so channel uses placeholder uri
+    /// and socket is opened by custom connector.
+    ///
+    fn endpoint_uri(&self) -> Uri {
+        if self.uri.scheme_str() == Some(UDS_SCHEME) {
+            Uri::from_static("http://localhost")
+        } else {
+            self.uri.to_owned()
+        }
+    }
+
+    ///
+    /// Return clone of connected channel. Channel is connected if it is not already.
+    ///
+    /// # Errors
+    ///
+    /// * `ConnectionFailed`: endpoint cannot be reached
+    ///
+    pub async fn connected_channel(&mut self) -> Result<Channel> {
+        self.channel().await
+    }
+
+    ///
+    /// Return true if channel was already connected.
+    ///
+    /// Connected channel is not dropped when its connection is broken, so it is not a health
+    /// check of endpoint. It only distinguishes channel which was never connected.
+    ///
+    pub fn is_connected(&self) -> bool {
+        self.connected.load(Ordering::SeqCst)
+    }
+
+    async fn connect(&self, endpoint: Endpoint) -> Result<Channel, TransportError> {
+        #[cfg(feature = "uds")]
+        if let Some(path) = &self.socket {
+            let path = path.to_owned();
+            return endpoint
+                .connect_with_connector(service_fn(move |_: Uri| {
+                    UnixStream::connect(path.to_owned())
+                }))
+                .await;
+        }
+        if let Some(proxy) = &self.proxy {
+            let proxy = proxy.to_owned();
+            return endpoint
+                .connect_with_connector(service_fn(move |uri: Uri| tunnel(proxy.to_owned(), uri)))
+                .await;
+        }
+        endpoint.connect().await
+    }
 }
 
 #[async_trait]
 impl ILazyChannel for LazyChannel {
     async fn channel(&mut self) -> Result<Channel> {
-        if let Some(channel) = &self.channel {
+        let mut shared = self.channel.lock().await;
+        if let Some(channel) = shared.as_ref() {
             Ok(channel.to_owned())
         } else {
-            let mut endpoint: Endpoint = self.uri.to_owned().into();
+            let mut endpoint: Endpoint = self.endpoint_uri().into();
             if let Some(endpoint_config) = &self.endpoint_config {
                 endpoint = endpoint_config.configure_endpoint(endpoint);
             }
-            let channel = endpoint.connect().await?;
-            self.channel.replace(channel.to_owned());
+            let channel = match self.connect(endpoint).await {
+                Ok(channel) => channel,
+                Err(source) => {
+                    return Err(ClientError::ConnectionFailed {
+                        uri: self.uri.to_owned(),
+                        source,
+                    }
+                    .into());
+                }
+            };
+            shared.replace(channel.to_owned());
+            self.connected.store(true, Ordering::SeqCst);
             Ok(channel)
         }
     }
+
+    fn is_connected(&self) -> bool {
+        LazyChannel::is_connected(self)
+    }
+
+    fn uri(&self) -> &Uri {
+        &self.uri
+    }
 }
 
 ///
@@ -64,7 +198,7 @@ impl ILazyChannel for LazyChannel {
 #[derive(Debug)]
 #[doc(hidden)]
 pub struct Http {
-    clients: Vec<LazyClient<LazyChannel>>,
+    clients: Arc<[LazyClient<LazyChannel>]>,
 }
 
 #[async_trait]
@@ -77,7 +211,15 @@ impl IClient for Http {
     }
 
     fn clients(self) -> Vec<Self::Client> {
-        self.clients
+        self.clients.to_vec()
+    }
+
+    fn all_clients(&self) -> Vec<Self::Client> {
+        self.clients.to_vec()
+    }
+
+    fn shared_clients(&self) -> Arc<[Self::Client]> {
+        Arc::clone(&self.clients)
     }
 }
 
@@ -107,7 +249,7 @@ pub type TxnBestEffort = TxnBestEffortType<LazyClient<LazyChannel>>;
 pub type TxnMutated = TxnMutatedType<LazyClient<LazyChannel>>;
 
 impl Client {
-    fn init_clients<S: TryInto<Uri>, E: Into<Endpoints<S>> + Debug>(
+    fn init_clients<S: TryInto<Uri> + Debug, E: Into<Endpoints<S>> + Debug>(
         endpoints: E,
         endpoint_config: Option<Arc<dyn EndpointConfig>>,
     ) -> Result<Vec<LazyClient<LazyChannel>>> {
@@ -123,6 +265,11 @@ impl Client {
     /// Create new Dgraph client for interacting v DB.
     ///
     /// The client can be backed by multiple endpoints (to the same server, or multiple servers in a cluster).
+    /// Duplicated endpoints are used only once.
+    ///
+    /// Scheme of every endpoint is honored, so `http://` and `https://` endpoints can be mixed in
+    /// one client. `https://` endpoints are connected over TLS with server certificate verified
+    /// against webpki roots. Use `TlsClient` for custom CA or client certificates.
     ///
     /// # Arguments
     ///
@@ -142,14 +289,19 @@ impl Client {
     /// let client = Client::new(vec!["http://127.0.0.1:19080", "http://127.0.0.1:19080"]).expect("Dgraph client");
     /// // one endpoint
     /// let client = Client::new("http://127.0.0.1:19080").expect("Dgraph client");
+    /// // plain and TLS endpoints
+    /// let client = Client::new(vec!["http://127.0.0.1:19080", "https://dgraph.example.com:443"]).expect("Dgraph client");
     /// ```
     ///
-    #[instrument]
-    pub fn new<S: TryInto<Uri>, E: Into<Endpoints<S>> + Debug>(endpoints: E) -> Result<Self> {
+    #[cfg_attr(feature = "tracing", instrument)]
+    pub fn new<S: TryInto<Uri> + Debug, E: Into<Endpoints<S>> + Debug>(
+        endpoints: E,
+    ) -> Result<Self> {
         let extra = Http {
-            clients: Self::init_clients(endpoints, None)?,
+            clients: Self::init_clients(endpoints, None)?.into(),
         };
         let state = Box::new(ClientState::new());
+        #[cfg(feature = "tracing")]
         trace!("New http client");
         Ok(Self { state, extra })
     }
@@ -158,6 +310,7 @@ impl Client {
     /// Create new Dgraph client with custom endpoint configuration for interacting with DB.
     ///
     /// The client can be backed by multiple endpoints (to the same server, or multiple servers in a cluster).
+    /// Duplicated endpoints are used only once.
     ///
     /// # Arguments
     ///
@@ -193,9 +346,9 @@ impl Client {
     /// let client = Client::new_with_endpoint_config("http://127.0.0.1:19080", endpoint_config).expect("Dgraph client");
     /// ```
     ///
-    #[instrument]
+    #[cfg_attr(feature = "tracing", instrument)]
     pub fn new_with_endpoint_config<
-        S: TryInto<Uri>,
+        S: TryInto<Uri> + Debug,
         E: Into<Endpoints<S>> + Debug,
         C: EndpointConfig + 'static,
     >(
@@ -203,10 +356,402 @@ impl Client {
         endpoint_config: C,
     ) -> Result<Self> {
         let extra = Http {
-            clients: Self::init_clients(endpoints, Some(Arc::new(endpoint_config)))?,
+            clients: Self::init_clients(endpoints, Some(Arc::new(endpoint_config)))?.into(),
         };
         let state = Box::new(ClientState::new());
+        #[cfg(feature = "tracing")]
         trace!("New http client");
         Ok(Self { state, extra })
     }
+
+    ///
+    /// Create new Dgraph client and check that every endpoint is reachable.
+    ///
+    /// Version of every endpoint is checked immediately, so misconfigured endpoint is reported on
+    /// startup. Channels of client stay lazy, they are reconnected on demand afterwards.
+    ///
+    /// # Arguments
+    ///
+    /// * `endpoints` - one endpoint or vector of endpoints
+    ///
+    /// # Errors
+    ///
+    /// * endpoints vector is empty
+    /// * item in vector cannot by converted into Uri
+    /// * `ConnectionFailed`: some endpoint cannot be reached. Error context lists all failed
+    ///   endpoints.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use dgraph_tonic::Client;
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     let client = Client::new_eager(vec!["http://127.0.0.1:19080", "http://127.0.0.1:19080"])
+    ///         .await
+    ///         .expect("Dgraph client");
+    /// }
+    /// ```
+    ///
+    #[cfg_attr(feature = "tracing", instrument)]
+    pub async fn new_eager<S: TryInto<Uri> + Debug, E: Into<Endpoints<S>> + Debug>(
+        endpoints: E,
+    ) -> Result<Self> {
+        let client = Self::new(endpoints)?;
+        let mut failed = Vec::new();
+        let mut first_err = None;
+        for lazy_client in client.extra.clients.iter() {
+            let mut stub = Stub::new(lazy_client.to_owned());
+            if let Err(err) = stub.check_version().await {
+                failed.push(lazy_client.uri().to_string());
+                first_err.get_or_insert(err);
+            }
+        }
+        match first_err {
+            Some(err) => Err(err.context(format!("Unreachable endpoints: {}", failed.join(", ")))),
+            None => Ok(client),
+        }
+    }
+
+    ///
+    /// Create new Dgraph client over existing gRPC channel.
+    ///
+    /// Channel is used as it is, so it can be built with custom connector, e.g. for mTLS or
+    /// service mesh. Endpoint configuration and proxy settings of client are not applied to it.
+    ///
+    /// Client is not lazy in this case. Connecting and reconnecting is up to channel, client
+    /// never replaces it. Endpoint of channel is not known, so it is reported as `channel://0`.
+    ///
+    /// # Arguments
+    ///
+    /// * `channel` - gRPC channel to Dgraph Alpha
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use dgraph_tonic::{Channel, Client};
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     let channel = Channel::from_static("http://127.0.0.1:19080")
+    ///         .connect()
+    ///         .await
+    ///         .expect("Connected channel");
+    ///     let client = Client::from_channel(channel);
+    /// }
+    /// ```
+    ///
+    pub fn from_channel(channel: Channel) -> Self {
+        Self::from_channels(vec![channel]).expect("One channel")
+    }
+
+    ///
+    /// Create new Dgraph client over existing gRPC channels.
+    ///
+    /// Same as `from_channel`, but requests are balanced between all channels. Endpoint of n-th
+    /// channel is reported as `channel://n`.
+    ///
+    /// # Arguments
+    ///
+    /// * `channels` - gRPC channels to Dgraph Alphas
+    ///
+    /// # Errors
+    ///
+    /// * `NoEndpointsDefined`: channels vector is empty
+    ///
+    #[cfg_attr(feature = "tracing", instrument(skip(channels)))]
+    pub fn from_channels(channels: Vec<Channel>) -> Result<Self> {
+        if channels.is_empty() {
+            return Err(ClientError::NoEndpointsDefined.into());
+        }
+        let extra = Http {
+            clients: channels
+                .into_iter()
+                .enumerate()
+                .map(|(index, channel)| LazyClient::new(LazyChannel::from_channel(index, channel)))
+                .collect(),
+        };
+        let state = Box::new(ClientState::new());
+        #[cfg(feature = "tracing")]
+        trace!("New http client from channels");
+        Ok(Self { state, extra })
+    }
+
+    ///
+    /// Create new Dgraph client connected over Unix domain socket.
+    ///
+    /// Endpoint is represented as `unix://localhost/<path>` uri with percent-encoded path, so it
+    /// can be also passed into `Client::new` together with other endpoints. Socket is opened on
+    /// decoded path, so path can contain spaces or other characters not allowed in uri.
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - absolute path of socket where Dgraph Alpha listens, optionally in
+    ///   `unix:///<path>` form
+    ///
+    /// # Errors
+    ///
+    /// * path is not absolute or cannot be represented as uri
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use dgraph_tonic::Client;
+    ///
+    /// let client = Client::new_uds("/var/run/dgraph.sock").expect("Dgraph client");
+    /// ```
+    ///
+    #[cfg(feature = "uds")]
+    #[cfg_attr(feature = "tracing", instrument)]
+    pub fn new_uds<P: AsRef<Path> + Debug>(path: P) -> Result<Self> {
+        let path = path.as_ref();
+        let prefix = format!("{}://", UDS_SCHEME);
+        let path = match path.to_str().and_then(|path| path.strip_prefix(&prefix)) {
+            Some(path) => Path::new(path),
+            None => path,
+        };
+        if !path.is_absolute() {
+            return Err(ClientError::InvalidEndpoint.into());
+        }
+        Self::new(uds_uri(path)?)
+    }
+
+    ///
+    /// Connect all endpoints through HTTP proxy.
+    ///
+    /// Connection is tunneled with HTTP `CONNECT` request, so only `http://` proxies are supported
+    /// and proxy authentication is not. Endpoints over Unix domain socket are never proxied.
+    ///
+    /// # Arguments
+    ///
+    /// * `proxy` - uri of proxy, e.g. `http://proxy:3128`
+    ///
+    /// # Errors
+    ///
+    /// * `InvalidProxy`: proxy cannot be converted into Uri or it is not supported
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use dgraph_tonic::Client;
+    ///
+    /// let client = Client::new("http://127.0.0.1:19080")
+    ///     .expect("Dgraph client")
+    ///     .with_proxy("http://127.0.0.1:3128")
+    ///     .expect("Proxy");
+    /// ```
+    ///
+    pub fn with_proxy<U: TryInto<Uri>>(mut self, proxy: U) -> Result<Self> {
+        let proxy = parse_proxy(proxy)?;
+        self.extra.clients = self
+            .extra
+            .clients
+            .iter()
+            .cloned()
+            .map(|client| LazyClient::new(client.channel().with_proxy(Some(proxy.to_owned()))))
+            .collect();
+        Ok(self)
+    }
+
+    ///
+    /// Connect endpoints through proxy defined by environment.
+    ///
+    /// Proxy is read from `HTTP_PROXY` or `http_proxy` variable. Hosts listed in `NO_PROXY` or
+    /// `no_proxy` variable are connected directly. Same restrictions as in `with_proxy` apply.
+    ///
+    /// # Errors
+    ///
+    /// * `InvalidProxy`: proxy from environment is not supported
+    ///
+    pub fn with_proxy_from_env(mut self) -> Result<Self> {
+        let mut clients = Vec::new();
+        for client in self.extra.clients.iter().cloned() {
+            let proxy = env_proxy(client.uri(), false)?;
+            clients.push(LazyClient::new(client.channel().with_proxy(proxy)));
+        }
+        self.extra.clients = clients.into();
+        Ok(self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Arc;
+
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+    use tokio::net::TcpListener;
+
+    use super::*;
+    use crate::fake_server::{serve_on, FakeReply};
+
+    #[tokio::test]
+    async fn shared_channel() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let uri: Uri = format!("http://{}", listener.local_addr().unwrap())
+            .parse()
+            .unwrap();
+        let accepted = Arc::new(AtomicUsize::new(0));
+        let counter = Arc::clone(&accepted);
+        tokio::spawn(async move {
+            let mut streams = Vec::new();
+            while let Ok((stream, _)) = listener.accept().await {
+                counter.fetch_add(1, Ordering::SeqCst);
+                streams.push(stream);
+            }
+        });
+        let mut channel = LazyChannel::new(uri);
+        let mut cloned = channel.clone();
+        channel.channel().await.expect("Connected");
+        cloned.channel().await.expect("Connected");
+        LazyClient::new(cloned)
+            .channel()
+            .channel()
+            .await
+            .expect("Connected");
+        tokio::time::sleep(std::time::Duration::from_millis(50)).await;
+        assert_eq!(accepted.load(Ordering::SeqCst), 1);
+    }
+
+    #[tokio::test]
+    async fn is_connected() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(async move {
+            let mut streams = Vec::new();
+            while let Ok((stream, _)) = listener.accept().await {
+                streams.push(stream);
+            }
+        });
+        let client = Client::new(vec![
+            format!("http://{}", addr),
+            "http://127.0.0.1:1".to_string(),
+        ])
+        .unwrap();
+        assert!(!client.is_connected());
+        let mut channels: Vec<LazyChannel> = client
+            .extra
+            .all_clients()
+            .into_iter()
+            .map(|client| client.channel())
+            .collect();
+        assert!(channels[1].channel().await.is_err());
+        assert!(!channels[1].is_connected());
+        assert!(!client.is_connected());
+        channels[0].channel().await.expect("Connected");
+        assert!(channels[0].is_connected());
+        assert!(client.is_connected());
+    }
+
+    #[tokio::test]
+    async fn proxy_connect() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let proxy = format!("http://{}", listener.local_addr().unwrap());
+        let handle = tokio::spawn(async move {
+            let (mut socket, _) = listener.accept().await.unwrap();
+            let mut request = vec![0u8; 1024];
+            let read = socket.read(&mut request).await.unwrap();
+            socket
+                .write_all(b"HTTP/1.1 200 Connection established\r\n\r\n")
+                .await
+                .unwrap();
+            String::from_utf8_lossy(&request[..read]).to_string()
+        });
+        let client = Client::new("http://dgraph:9080")
+            .unwrap()
+            .with_proxy(proxy)
+            .expect("Proxy");
+        client.channel().await.expect("Connected through proxy");
+        let request = handle.await.unwrap();
+        assert!(request.starts_with("CONNECT dgraph:9080 HTTP/1.1\r\n"));
+    }
+
+    ///
+    /// Serve one connection with fake Dgraph which answers every call with version.
+    ///
+    fn fake_dgraph(listener: TcpListener) -> tokio::task::JoinHandle<()> {
+        serve_on(listener, |_| {
+            FakeReply::new(&crate::Version {
+                tag: "v1.0.0".to_string(),
+            })
+        })
+    }
+
+    #[tokio::test]
+    async fn reconnect_after_server_restart() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        let server = fake_dgraph(listener);
+        let client = Client::new(format!("http://{}", addr)).unwrap();
+        let version = client.check_version().await.expect("Version");
+        assert_eq!(version.tag, "v1.0.0");
+        server.abort();
+        let _ = server.await;
+        let server = fake_dgraph(TcpListener::bind(addr).await.unwrap());
+        let version = client.check_version().await.expect("Reconnected");
+        assert_eq!(version.tag, "v1.0.0");
+        server.abort();
+    }
+
+    #[tokio::test]
+    async fn from_channel() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        let server = fake_dgraph(listener);
+        let channel = Channel::from_shared(format!("http://{}", addr))
+            .unwrap()
+            .connect()
+            .await
+            .expect("Connected");
+        let client = Client::from_channel(channel)
+            .with_proxy("http://127.0.0.1:1")
+            .expect("Proxy");
+        let uris: Vec<String> = client
+            .extra
+            .all_clients()
+            .iter()
+            .map(|client| client.uri().to_string())
+            .collect();
+        assert_eq!(uris, vec!["channel://0/"]);
+        assert!(client.is_connected());
+        let version = client.check_version().await.expect("Version");
+        assert_eq!(version.tag, "v1.0.0");
+        server.abort();
+    }
+
+    #[test]
+    fn from_no_channels() {
+        let err = Client::from_channels(Vec::new()).expect_err("No channels");
+        assert!(matches!(
+            err.downcast_ref::<ClientError>(),
+            Some(ClientError::NoEndpointsDefined)
+        ));
+    }
+
+    #[tokio::test]
+    async fn mixed_schemes() {
+        let mut endpoints = Vec::new();
+        let mut handles = Vec::new();
+        for scheme in &["http", "https"] {
+            let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+            let port = listener.local_addr().unwrap().port();
+            endpoints.push(format!("{}://localhost:{}", scheme, port));
+            handles.push(tokio::spawn(async move {
+                let (mut socket, _) = listener.accept().await.unwrap();
+                let mut first = [0u8; 1];
+                socket.read_exact(&mut first).await.unwrap();
+                first[0]
+            }));
+        }
+        let client = Client::new(endpoints).unwrap();
+        for mut lazy_client in client.extra.all_clients() {
+            let _ =
+                tokio::time::timeout(std::time::Duration::from_millis(200), lazy_client.client())
+                    .await;
+        }
+        // HTTP/2 connection preface starts with `PRI`, TLS handshake record has type 0x16
+        assert_eq!(handles.remove(0).await.unwrap(), b'P');
+        assert_eq!(handles.remove(0).await.unwrap(), 0x16);
+    }
 }
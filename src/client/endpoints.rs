@@ -1,10 +1,21 @@
 use http::Uri;
 use std::convert::TryInto;
+use std::iter::FromIterator;
 
 ///
 /// Helper struct for endpoints input argument in new client function.
 /// Allows to create client with one or more endpoints.
 ///
+/// Endpoints can be also collected from any iterator, e.g. result of service discovery:
+///
+/// ```
+/// use dgraph_tonic::{Client, Endpoints};
+///
+/// let discovered = vec!["http://127.0.0.1:19080", "", "http://127.0.0.1:19080"];
+/// let endpoints: Endpoints<_> = discovered.into_iter().filter(|e| !e.is_empty()).collect();
+/// let client = Client::new(endpoints).expect("Dgraph client");
+/// ```
+///
 #[derive(Debug)]
 pub struct Endpoints<S: TryInto<Uri>> {
     pub(crate) endpoints: Vec<S>,
@@ -24,6 +35,14 @@ impl<S: TryInto<Uri>> From<S> for Endpoints<S> {
     }
 }
 
+impl<S: TryInto<Uri>> FromIterator<S> for Endpoints<S> {
+    fn from_iter<I: IntoIterator<Item = S>>(iter: I) -> Self {
+        Self {
+            endpoints: iter.into_iter().collect(),
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -44,4 +63,13 @@ mod tests {
         assert_eq!(endpoints.endpoints.len(), 1);
         assert_eq!(endpoints.endpoints[0], url);
     }
+
+    #[test]
+    fn from_iterator() {
+        let urls = ["http://localhost:2379", "", "http://localhost:22379"];
+        let endpoints: Endpoints<_> = urls.iter().copied().filter(|url| !url.is_empty()).collect();
+        assert_eq!(endpoints.endpoints.len(), 2);
+        assert_eq!(endpoints.endpoints[0], urls[0]);
+        assert_eq!(endpoints.endpoints[1], urls[2]);
+    }
 }
@@ -2,6 +2,7 @@ use std::fmt::Debug;
 
 use anyhow::Result;
 use async_trait::async_trait;
+use http::Uri;
 use tonic::transport::Channel;
 
 use crate::api::dgraph_client::DgraphClient as DClient;
@@ -16,6 +17,16 @@ pub trait ILazyChannel: Sync + Send + Debug + Clone {
     /// Try create and connect gRPC channel
     ///
     async fn channel(&mut self) -> Result<Channel>;
+
+    ///
+    /// Return true if gRPC channel was already connected
+    ///
+    fn is_connected(&self) -> bool;
+
+    ///
+    /// Return endpoint of channel
+    ///
+    fn uri(&self) -> &Uri;
 }
 
 ///
@@ -34,6 +45,11 @@ pub trait ILazyClient: Sync + Send + Debug + Clone {
     /// Return used lazy channel for client
     ///
     fn channel(self) -> Self::Channel;
+
+    ///
+    /// Return endpoint of used lazy channel
+    ///
+    fn uri(&self) -> &Uri;
 }
 
 ///
@@ -81,4 +97,8 @@ impl<C: ILazyChannel> ILazyClient for LazyClient<C> {
     fn channel(self) -> Self::Channel {
         self.channel
     }
+
+    fn uri(&self) -> &Uri {
+        self.channel.uri()
+    }
 }
@@ -0,0 +1,150 @@
+use std::collections::HashMap;
+use std::sync::Mutex;
+use std::time::Duration;
+
+use http::Uri;
+
+///
+/// Upper bounds of latency histogram buckets in microseconds.
+///
+const BUCKETS: [u64; 16] = [
+    100, 250, 500, 1_000, 2_500, 5_000, 10_000, 25_000, 50_000, 100_000, 250_000, 500_000,
+    1_000_000, 2_500_000, 5_000_000, 10_000_000,
+];
+
+///
+/// Requests, errors and latency histogram of one endpoint.
+///
+#[derive(Clone, Debug, Default)]
+pub struct EndpointStats {
+    requests: u64,
+    errors: u64,
+    histogram: [u64; 17],
+    max_latency: Duration,
+}
+
+impl EndpointStats {
+    ///
+    /// Number of all requests sent to endpoint.
+    ///
+    pub fn requests(&self) -> u64 {
+        self.requests
+    }
+
+    ///
+    /// Number of failed requests.
+    ///
+    pub fn errors(&self) -> u64 {
+        self.errors
+    }
+
+    ///
+    /// Ratio of failed requests in range `0.0..=1.0`.
+    ///
+    pub fn error_rate(&self) -> f64 {
+        if self.requests == 0 {
+            0.0
+        } else {
+            self.errors as f64 / self.requests as f64
+        }
+    }
+
+    ///
+    /// Return latency under which is given `percentile` (`0.0..=100.0`) of requests.
+    ///
+    /// Latencies are kept in histogram, so result is upper bound of matched bucket.
+    /// Return `None` if no request was recorded yet.
+    ///
+    pub fn latency_percentile(&self, percentile: f64) -> Option<Duration> {
+        if self.requests == 0 {
+            return None;
+        }
+        let percentile = percentile.clamp(0.0, 100.0);
+        let rank = ((percentile / 100.0) * self.requests as f64)
+            .ceil()
+            .max(1.0) as u64;
+        let mut seen = 0;
+        for (index, count) in self.histogram.iter().enumerate() {
+            seen += count;
+            if seen >= rank {
+                return match BUCKETS.get(index) {
+                    Some(bound) => Some(Duration::from_micros(*bound).min(self.max_latency)),
+                    None => Some(self.max_latency),
+                };
+            }
+        }
+        Some(self.max_latency)
+    }
+
+    fn record(&mut self, latency: Duration, success: bool) {
+        self.requests += 1;
+        if !success {
+            self.errors += 1;
+        }
+        let micros = latency.as_micros();
+        let index = BUCKETS
+            .iter()
+            .position(|bound| micros <= *bound as u128)
+            .unwrap_or(BUCKETS.len());
+        self.histogram[index] += 1;
+        self.max_latency = self.max_latency.max(latency);
+    }
+}
+
+///
+/// Collected stats of all endpoints used by client.
+///
+#[derive(Debug, Default)]
+pub(crate) struct EndpointsStats {
+    endpoints: Mutex<HashMap<String, EndpointStats>>,
+}
+
+impl EndpointsStats {
+    pub(crate) fn record(&self, uri: &Uri, latency: Duration, success: bool) {
+        let mut endpoints = self.endpoints.lock().expect("Endpoint stats");
+        endpoints
+            .entry(uri.to_string())
+            .or_default()
+            .record(latency, success);
+    }
+
+    pub(crate) fn snapshot(&self) -> HashMap<String, EndpointStats> {
+        self.endpoints.lock().expect("Endpoint stats").to_owned()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::*;
+
+    #[test]
+    fn record() {
+        let stats = EndpointsStats::default();
+        let a: Uri = "http://a:9080".parse().unwrap();
+        let b: Uri = "http://b:9080".parse().unwrap();
+        for _ in 0..9 {
+            stats.record(&a, Duration::from_micros(800), true);
+        }
+        stats.record(&a, Duration::from_millis(20), false);
+        stats.record(&b, Duration::from_secs(20), true);
+        let snapshot = stats.snapshot();
+        let a = &snapshot["http://a:9080/"];
+        assert_eq!(a.requests(), 10);
+        assert_eq!(a.errors(), 1);
+        assert!((a.error_rate() - 0.1).abs() < f64::EPSILON);
+        assert_eq!(a.latency_percentile(50.0), Some(Duration::from_millis(1)));
+        assert_eq!(a.latency_percentile(99.0), Some(Duration::from_millis(20)));
+        let b = &snapshot["http://b:9080/"];
+        assert_eq!(b.errors(), 0);
+        assert_eq!(b.latency_percentile(50.0), Some(Duration::from_secs(20)));
+    }
+
+    #[test]
+    fn empty() {
+        let stats = EndpointStats::default();
+        assert_eq!(stats.error_rate(), 0.0);
+        assert_eq!(stats.latency_percentile(50.0), None);
+    }
+}
@@ -1,15 +1,38 @@
+#[cfg(feature = "metrics")]
+use std::collections::HashMap;
 use std::convert::TryInto;
 use std::fmt::Debug;
 use std::ops::{Deref, DerefMut};
+#[cfg(feature = "uds")]
+use std::path::{Path, PathBuf};
+use std::sync::atomic::AtomicUsize;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
 
 use anyhow::Result;
+use futures::future::{select_ok, FutureExt};
 use http::Uri;
 use rand::Rng;
+use serde::de::DeserializeOwned;
+#[cfg(any(
+    feature = "dgraph-1-1",
+    feature = "dgraph-21-03",
+    feature = "dgraph-24-02"
+))]
+use serde::Deserialize;
+#[cfg(feature = "gzip")]
+use tonic::codec::CompressionEncoding;
 #[cfg(any(feature = "acl", feature = "slash-ql"))]
 use tonic::codegen::InterceptedService;
 use tonic::transport::{Channel, Endpoint};
 
 use crate::api::dgraph_client::DgraphClient as DClient;
+#[cfg(any(
+    feature = "dgraph-1-1",
+    feature = "dgraph-21-03",
+    feature = "dgraph-24-02"
+))]
+use crate::api::operation::DropOp;
 use crate::api::Version;
 #[cfg(feature = "acl")]
 pub use crate::client::acl::{
@@ -20,12 +43,17 @@ pub use crate::client::acl::{
 pub use crate::client::acl::{
     AclTlsClient, TxnAclTls, TxnAclTlsBestEffort, TxnAclTlsMutated, TxnAclTlsReadOnly,
 };
+pub use crate::client::bulk::BulkStats;
 pub use crate::client::default::{
     Client, Http, LazyChannel, Txn, TxnBestEffort, TxnMutated, TxnReadOnly,
 };
 pub use crate::client::endpoints::Endpoints;
 use crate::client::lazy::ILazyChannel;
 pub(crate) use crate::client::lazy::ILazyClient;
+#[cfg(feature = "metrics")]
+pub use crate::client::metrics::EndpointStats;
+#[cfg(feature = "metrics")]
+use crate::client::metrics::EndpointsStats;
 #[cfg(feature = "slash-ql")]
 pub use crate::client::slash_ql::{
     DgraphSlashQlClient, SlashQl, SlashQlClient, TxnSlashQl, TxnSlashQlBestEffort,
@@ -35,22 +63,211 @@ pub use crate::client::slash_ql::{
 pub use crate::client::tls::{
     Tls, TlsClient, TxnTls, TxnTlsBestEffort, TxnTlsMutated, TxnTlsReadOnly,
 };
-use crate::errors::ClientError;
-use crate::stub::Stub;
+use crate::errors::{ClientError, DgraphError};
+use crate::lifecycle::Lifecycle;
+use crate::observer::RequestObserver;
+use crate::retry::{OutagePolicy, RetryPolicy};
+use crate::selector::EndpointSelector;
+use crate::stub::{DeadlineSource, RequestInterceptor, Stub};
 use crate::{
-    IDgraphClient, Operation, Payload, TxnBestEffortType, TxnMutatedType, TxnReadOnlyType, TxnType,
+    IDgraphClient, Operation, Payload, Query, ReadPool, Response, TxnBestEffortType,
+    TxnMutatedType, TxnReadOnlyType, TxnType,
 };
 
 #[cfg(feature = "acl")]
 pub(crate) mod acl;
+pub(crate) mod bulk;
 pub(crate) mod default;
 pub(crate) mod endpoints;
 pub(crate) mod lazy;
+#[cfg(feature = "metrics")]
+pub(crate) mod metrics;
+pub(crate) mod proxy;
 #[cfg(feature = "slash-ql")]
 pub(crate) mod slash_ql;
 #[cfg(feature = "tls")]
 pub(crate) mod tls;
 
+///
+/// Scheme of endpoints which are connected over Unix domain socket
+///
+pub(crate) const UDS_SCHEME: &str = "unix";
+
+///
+/// Return path of Unix domain socket endpoint, e.g. `/var/run/my dir/dgraph.sock` for
+/// `unix://localhost/var/run/my%20dir/dgraph.sock`.
+///
+/// Path is percent-decoded by hand, because `Uri` keeps it encoded.
+///
+#[cfg(feature = "uds")]
+pub(crate) fn uds_path(uri: &Uri) -> Option<PathBuf> {
+    use std::ffi::OsStr;
+    use std::os::unix::ffi::OsStrExt;
+
+    if uri.scheme_str() != Some(UDS_SCHEME) {
+        return None;
+    }
+    let encoded = uri.path().as_bytes();
+    let mut decoded = Vec::with_capacity(encoded.len());
+    let mut i = 0;
+    while i < encoded.len() {
+        if encoded[i] == b'%' {
+            let hex = std::str::from_utf8(encoded.get(i + 1..i + 3)?).ok()?;
+            decoded.push(u8::from_str_radix(hex, 16).ok()?);
+            i += 3;
+        } else {
+            decoded.push(encoded[i]);
+            i += 1;
+        }
+    }
+    Some(PathBuf::from(OsStr::from_bytes(&decoded)))
+}
+
+///
+/// Return endpoint uri of Unix domain socket with percent-encoded path.
+///
+#[cfg(feature = "uds")]
+pub(crate) fn uds_uri(path: &Path) -> Result<Uri> {
+    use std::os::unix::ffi::OsStrExt;
+
+    let mut endpoint = format!("{}://localhost", UDS_SCHEME);
+    for byte in path.as_os_str().as_bytes() {
+        match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'.' | b'_' | b'~' | b'/' => {
+                endpoint.push(*byte as char)
+            }
+            byte => endpoint.push_str(&format!("%{:02X}", byte)),
+        }
+    }
+    endpoint
+        .parse()
+        .map_err(|_| ClientError::InvalidEndpoint.into())
+}
+
+///
+/// Scheme of placeholder endpoints of channels which were provided by user
+///
+pub(crate) const CHANNEL_SCHEME: &str = "channel";
+
+#[cfg(any(
+    feature = "dgraph-1-1",
+    feature = "dgraph-21-03",
+    feature = "dgraph-24-02"
+))]
+#[derive(Deserialize)]
+struct SchemaNode {
+    predicate: String,
+    #[serde(default)]
+    tokenizer: Vec<String>,
+}
+
+#[cfg(any(
+    feature = "dgraph-1-1",
+    feature = "dgraph-21-03",
+    feature = "dgraph-24-02"
+))]
+#[derive(Deserialize)]
+struct SchemaBlock {
+    #[serde(default)]
+    schema: Vec<SchemaNode>,
+}
+
+///
+/// Return tokenizers of every predicate which is indexed by schema.
+///
+/// Tokenizer options, like `metric` of `hnsw` vector index, are not part of tokenizer name
+/// reported by Dgraph, so they are skipped.
+///
+#[cfg(any(
+    feature = "dgraph-1-1",
+    feature = "dgraph-21-03",
+    feature = "dgraph-24-02"
+))]
+fn schema_indexes(schema: &str) -> Vec<(String, Vec<String>)> {
+    schema
+        .lines()
+        .filter_map(|line| {
+            let (predicate, definition) = line.split_once(':')?;
+            let index = definition.split_once("@index(")?.1;
+            let predicate = predicate
+                .trim()
+                .trim_start_matches('<')
+                .trim_end_matches('>');
+            let mut tokenizers = Vec::new();
+            let mut start = 0;
+            let mut depth = 0;
+            for (i, c) in index.char_indices() {
+                match c {
+                    '(' => depth += 1,
+                    ')' if depth > 0 => depth -= 1,
+                    ',' | ')' if depth == 0 => {
+                        let tokenizer = index[start..i].split('(').next().unwrap_or_default();
+                        if !tokenizer.trim().is_empty() {
+                            tokenizers.push(tokenizer.trim().to_string());
+                        }
+                        if c == ')' {
+                            return Some((predicate.to_string(), tokenizers));
+                        }
+                        start = i + 1;
+                    }
+                    _ => {}
+                }
+            }
+            None
+        })
+        .collect()
+}
+
+///
+/// Check if Dgraph reports every expected tokenizer in schema query response.
+///
+#[cfg(any(
+    feature = "dgraph-1-1",
+    feature = "dgraph-21-03",
+    feature = "dgraph-24-02"
+))]
+fn indexes_built(expected: &[(String, Vec<String>)], reported: &SchemaBlock) -> bool {
+    expected.iter().all(|(predicate, tokenizers)| {
+        reported.schema.iter().any(|node| {
+            &node.predicate == predicate
+                && tokenizers
+                    .iter()
+                    .all(|tokenizer| node.tokenizer.contains(tokenizer))
+        })
+    })
+}
+
+///
+/// Create alter operation which drops data, checking that value is given only when required.
+///
+#[cfg(any(
+    feature = "dgraph-1-1",
+    feature = "dgraph-21-03",
+    feature = "dgraph-24-02"
+))]
+pub(crate) fn drop_operation(op: DropOp, value: Option<String>) -> Result<Operation> {
+    let drop_value = match (op, value) {
+        (DropOp::All | DropOp::Data, None) => String::new(),
+        (DropOp::Attr | DropOp::Type, Some(value)) if !value.is_empty() => value,
+        (DropOp::All | DropOp::Data, Some(_)) => {
+            let message = format!("{:?} does not take value", op);
+            return Err(ClientError::InvalidDropOp(message).into());
+        }
+        (DropOp::Attr | DropOp::Type, _) => {
+            let message = format!("{:?} requires value", op);
+            return Err(ClientError::InvalidDropOp(message).into());
+        }
+        (DropOp::None, _) => {
+            return Err(ClientError::InvalidDropOp("nothing to drop".to_string()).into());
+        }
+    };
+    Ok(Operation {
+        drop_op: op as i32,
+        drop_value,
+        ..Default::default()
+    })
+}
+
 ///
 /// return random cloned item from vector
 ///
@@ -65,21 +282,49 @@ pub(crate) fn rnd_item<T: Clone>(items: &[T]) -> T {
 }
 
 ///
-/// Check if every endpoint is valid uri and also check if at least one endpoint is given
+/// Return `true` if debug output of endpoint is quoted string with whitespaces only.
 ///
-pub(crate) fn balance_list<U: TryInto<Uri>, E: Into<Endpoints<U>>>(
+/// Endpoint types are not required to implement `Display`, e.g. `Vec<u8>`, but all of them
+/// implement `Debug`, which quotes and escapes strings.
+///
+fn is_blank(shown: &str) -> bool {
+    serde_json::from_str::<String>(shown).is_ok_and(|endpoint| endpoint.trim().is_empty())
+}
+
+///
+/// Check if every endpoint is valid uri and also check if at least one endpoint is given.
+///
+/// Blank endpoints are reported as `EmptyEndpoint`, so they are not hidden behind generic
+/// `InvalidEndpoint` error. Duplicated endpoints are removed, so load is not skewed towards them.
+/// Order of endpoints is preserved.
+///
+pub(crate) fn balance_list<U: TryInto<Uri> + Debug, E: Into<Endpoints<U>>>(
     endpoints: E,
 ) -> Result<Vec<Uri>> {
     let endpoints: Endpoints<U> = endpoints.into();
     let mut balance_list: Vec<Uri> = Vec::new();
     for maybe_endpoint in endpoints.endpoints {
-        let endpoint = match maybe_endpoint.try_into() {
+        let shown = format!("{:?}", maybe_endpoint);
+        let endpoint: Uri = match maybe_endpoint.try_into() {
             Ok(endpoint) => endpoint,
+            Err(_err) if is_blank(&shown) => {
+                return Err(ClientError::EmptyEndpoint.into());
+            }
             Err(_err) => {
                 return Err(ClientError::InvalidEndpoint.into());
             }
         };
-        balance_list.push(endpoint);
+        #[cfg(feature = "uds")]
+        if endpoint.scheme_str() == Some(UDS_SCHEME) && uds_path(&endpoint).is_none() {
+            return Err(ClientError::InvalidEndpoint.into());
+        }
+        #[cfg(not(feature = "uds"))]
+        if endpoint.scheme_str() == Some(UDS_SCHEME) {
+            return Err(ClientError::InvalidEndpoint.into());
+        }
+        if !balance_list.contains(&endpoint) {
+            balance_list.push(endpoint);
+        }
     }
     if balance_list.is_empty() {
         return Err(ClientError::NoEndpointsDefined.into());
@@ -105,6 +350,75 @@ pub enum DgraphClient {
     },
 }
 
+///
+/// Compression of gRPC messages.
+///
+#[cfg(feature = "gzip")]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Compression {
+    ///
+    /// Messages are not compressed
+    ///
+    None,
+    ///
+    /// Requests are compressed with gzip and compressed responses are accepted
+    ///
+    Gzip,
+}
+
+#[cfg(feature = "gzip")]
+impl DgraphClient {
+    ///
+    /// Return clone of client which uses given compression.
+    ///
+    pub(crate) fn with_compression(self, compression: Compression) -> Self {
+        let encoding = match compression {
+            Compression::None => return self,
+            Compression::Gzip => CompressionEncoding::Gzip,
+        };
+        match self {
+            DgraphClient::Default { client } => DgraphClient::Default {
+                client: client.send_compressed(encoding).accept_compressed(encoding),
+            },
+            #[cfg(feature = "acl")]
+            DgraphClient::Acl { client } => DgraphClient::Acl {
+                client: client.send_compressed(encoding).accept_compressed(encoding),
+            },
+            #[cfg(feature = "slash-ql")]
+            DgraphClient::SlashQl { client } => DgraphClient::SlashQl {
+                client: client.send_compressed(encoding).accept_compressed(encoding),
+            },
+        }
+    }
+}
+
+impl DgraphClient {
+    ///
+    /// Return clone of client which encodes and decodes gRPC messages up to given size.
+    ///
+    pub(crate) fn with_max_message_size(self, bytes: usize) -> Self {
+        match self {
+            DgraphClient::Default { client } => DgraphClient::Default {
+                client: client
+                    .max_encoding_message_size(bytes)
+                    .max_decoding_message_size(bytes),
+            },
+            #[cfg(feature = "acl")]
+            DgraphClient::Acl { client } => DgraphClient::Acl {
+                client: client
+                    .max_encoding_message_size(bytes)
+                    .max_decoding_message_size(bytes),
+            },
+            #[cfg(feature = "slash-ql")]
+            DgraphClient::SlashQl { client } => DgraphClient::SlashQl {
+                client: client
+                    .max_encoding_message_size(bytes)
+                    .max_decoding_message_size(bytes),
+            },
+        }
+    }
+}
+
 ///
 /// Dgraph client with interceptor
 ///
@@ -133,13 +447,72 @@ pub trait IClient: Debug + Send + Sync {
     /// consume self and return all lazy clients
     ///
     fn clients(self) -> Vec<Self::Client>;
+
+    ///
+    /// Return copies of all lazy clients
+    ///
+    fn all_clients(&self) -> Vec<Self::Client>;
+
+    ///
+    /// Return all lazy clients shared without copying
+    ///
+    fn shared_clients(&self) -> Arc<[Self::Client]>;
+}
+
+///
+/// Version of Dgraph remembered by client until its time to live elapses.
+///
+#[derive(Debug)]
+struct VersionCache {
+    ttl: Duration,
+    cached: Mutex<Option<(Version, Instant)>>,
+}
+
+impl VersionCache {
+    fn new(ttl: Duration) -> Self {
+        Self {
+            ttl,
+            cached: Mutex::new(None),
+        }
+    }
+
+    ///
+    /// Return cached version, if it is not expired.
+    ///
+    fn get(&self) -> Option<Version> {
+        match &*self.cached.lock().expect("Version cache") {
+            Some((version, cached_at)) if cached_at.elapsed() < self.ttl => Some(version.clone()),
+            _ => None,
+        }
+    }
+
+    fn set(&self, version: &Version) {
+        self.cached
+            .lock()
+            .expect("Version cache")
+            .replace((version.clone(), Instant::now()));
+    }
 }
 
 ///
 /// Client state.
 ///
 #[derive(Debug, Default)]
-pub struct ClientState;
+pub struct ClientState {
+    deadline_source: Option<DeadlineSource>,
+    retry_policy: Option<Arc<dyn RetryPolicy>>,
+    outage_policy: OutagePolicy,
+    interceptors: Vec<RequestInterceptor>,
+    max_message_size: Option<usize>,
+    #[cfg(feature = "gzip")]
+    compression_threshold: Option<usize>,
+    observer: Option<Arc<dyn RequestObserver>>,
+    selector: Option<Arc<dyn EndpointSelector>>,
+    lifecycle: Arc<Lifecycle>,
+    version_cache: Option<VersionCache>,
+    #[cfg(feature = "metrics")]
+    stats: Arc<EndpointsStats>,
+}
 
 impl ClientState {
     ///
@@ -177,52 +550,876 @@ impl<C: IClient> ClientVariant<C> {
     ///
     /// Return new stub with grpc client implemented according to actual variant.
     ///
-    fn any_stub(&self) -> Stub<C::Client> {
-        Stub::new(self.extra.client())
+    pub(crate) fn any_stub(&self) -> Stub<C::Client> {
+        let (client, outstanding) = self.select_client();
+        self.stub(client).with_outstanding(outstanding)
+    }
+
+    ///
+    /// Return lazy client of endpoint chosen by selector of client together with counter of
+    /// requests in flight to this endpoint, if selector tracks it.
+    ///
+    fn select_client(&self) -> (C::Client, Option<Arc<AtomicUsize>>) {
+        match &self.state.selector {
+            Some(selector) => {
+                let mut clients = self.extra.all_clients();
+                let index = selector.select(clients.len()) % clients.len();
+                (clients.swap_remove(index), selector.outstanding(index))
+            }
+            None => (self.extra.client(), None),
+        }
+    }
+
+    ///
+    /// Return counter of requests in flight to endpoint with given index, if selector tracks it.
+    ///
+    fn outstanding(&self, index: usize) -> Option<Arc<AtomicUsize>> {
+        self.state
+            .selector
+            .as_ref()
+            .and_then(|selector| selector.outstanding(index))
+    }
+
+    fn stub(&self, client: C::Client) -> Stub<C::Client> {
+        let endpoints = match self.state.outage_policy {
+            OutagePolicy::Fail => Vec::new().into(),
+            OutagePolicy::RetryFor(_) => self.extra.shared_clients(),
+        };
+        let stub = Stub::new(client)
+            .with_deadline_source(self.state.deadline_source.clone())
+            .with_retry_policy(self.state.retry_policy.clone())
+            .with_outage_policy(self.state.outage_policy, endpoints)
+            .with_interceptors(self.state.interceptors.clone())
+            .with_max_message_size(self.state.max_message_size)
+            .with_observer(self.state.observer.clone())
+            .with_lifecycle(Some(Arc::clone(&self.state.lifecycle)));
+        #[cfg(feature = "gzip")]
+        let stub = stub.with_compression_threshold(self.state.compression_threshold);
+        #[cfg(feature = "metrics")]
+        let stub = stub.with_stats(Arc::clone(&self.state.stats));
+        stub
+    }
+
+    ///
+    /// Return request counts, error rates and latency histograms of all endpoints which were
+    /// used by this client and its transactions.
+    ///
+    /// Map is keyed by endpoint URI. Endpoints without any request are not included.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use dgraph_tonic::Client;
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     let client = Client::new("http://127.0.0.1:19080").expect("Dgraph client");
+    ///     let _ = client.check_version().await;
+    ///     for (endpoint, stats) in client.endpoint_stats() {
+    ///         println!(
+    ///             "{}: requests {}, error rate {}, p99 {:?}",
+    ///             endpoint,
+    ///             stats.requests(),
+    ///             stats.error_rate(),
+    ///             stats.latency_percentile(99.0)
+    ///         );
+    ///     }
+    /// }
+    /// ```
+    ///
+    #[cfg(feature = "metrics")]
+    pub fn endpoint_stats(&self) -> HashMap<String, EndpointStats> {
+        self.state.stats.snapshot()
+    }
+
+    ///
+    /// Set source of ambient deadline for all gRPC calls made by this client and its transactions.
+    ///
+    /// Source is called before every request. When it returns some deadline, request gets
+    /// gRPC timeout equal to the remaining time, so Dgraph calls respect overall budget of
+    /// caller's request without per-call plumbing. Deadline in past results into immediate
+    /// `DeadlineExceeded` error.
+    ///
+    /// # Arguments
+    ///
+    /// - `source`: function which returns actual deadline, e.g. from task local storage
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use std::time::{Duration, Instant};
+    /// use dgraph_tonic::Client;
+    ///
+    /// let deadline = Instant::now() + Duration::from_secs(5);
+    /// let client = Client::new("http://127.0.0.1:19080")
+    ///     .expect("Dgraph client")
+    ///     .with_deadline_source(move || Some(deadline));
+    /// ```
+    ///
+    pub fn with_deadline_source<F>(mut self, source: F) -> Self
+    where
+        F: Fn() -> Option<Instant> + Send + Sync + 'static,
+    {
+        self.state.deadline_source = Some(DeadlineSource::new(source));
+        self
+    }
+
+    ///
+    /// Add interceptor which is called for every gRPC request made by this client and its
+    /// transactions.
+    ///
+    /// Interceptor gets request without message, so it can read or set metadata, e.g. inject
+    /// `traceparent` header of current span. More interceptors can be added, they are called in
+    /// order of registration. Built-in interceptors of ACL and Slash GraphQL clients are still
+    /// applied after user interceptors.
+    ///
+    /// # Arguments
+    ///
+    /// - `interceptor`: function which modifies outgoing request
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use dgraph_tonic::Client;
+    ///
+    /// let client = Client::new("http://127.0.0.1:19080")
+    ///     .expect("Dgraph client")
+    ///     .with_interceptor(|request| {
+    ///         request.metadata_mut().insert(
+    ///             "traceparent",
+    ///             "00-0af7651916cd43dd8448eb211c80319c-b7ad6b7169203331-01"
+    ///                 .parse()
+    ///                 .expect("Metadata value"),
+    ///         );
+    ///     });
+    /// ```
+    ///
+    pub fn with_interceptor<F>(mut self, interceptor: F) -> Self
+    where
+        F: Fn(&mut tonic::Request<()>) + Send + Sync + 'static,
+    {
+        self.state
+            .interceptors
+            .push(RequestInterceptor::new(interceptor));
+        self
+    }
+
+    ///
+    /// Return client which sends `namespace` metadata with every request.
+    ///
+    /// This is useful in multi-tenant Dgraph without ACL, where namespace cannot be selected
+    /// with login. Metadata is ignored by Dgraph older than v21.03.
+    ///
+    /// Logged client of guardian of namespaces can select namespace of requests with
+    /// `with_query_namespace`.
+    ///
+    /// # Arguments
+    ///
+    /// - `namespace`: Namespace Id
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use dgraph_tonic::Client;
+    ///
+    /// let client = Client::new("http://127.0.0.1:19080")
+    ///     .expect("Dgraph client")
+    ///     .for_namespace(1);
+    /// ```
+    ///
+    #[cfg(any(feature = "dgraph-21-03", feature = "dgraph-24-02"))]
+    pub fn for_namespace(self, namespace: u64) -> Self {
+        let value = tonic::metadata::MetadataValue::from(namespace);
+        self.with_interceptor(move |request| {
+            request.metadata_mut().insert("namespace", value.to_owned());
+        })
+    }
+
+    ///
+    /// Set policy which decides if failed `query`, `mutate`, `alter` or `commit` is repeated.
+    ///
+    /// Policy is shared by client and all its transactions. Without policy, every error is
+    /// returned immediately.
+    ///
+    /// # Arguments
+    ///
+    /// - `policy`: retry policy, e.g. `ExponentialBackoff` or `NoRetry`
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use std::time::Duration;
+    /// use dgraph_tonic::{Client, ExponentialBackoff};
+    ///
+    /// let client = Client::new("http://127.0.0.1:19080")
+    ///     .expect("Dgraph client")
+    ///     .with_retry_policy(ExponentialBackoff {
+    ///         max_attempts: 5,
+    ///         base: Duration::from_millis(50),
+    ///         max: Duration::from_secs(1),
+    ///     });
+    /// ```
+    ///
+    pub fn with_retry_policy<P: RetryPolicy + 'static>(mut self, policy: P) -> Self {
+        self.state.retry_policy = Some(Arc::new(policy));
+        self
+    }
+
+    ///
+    /// Set policy which decides what happens when endpoint of request is not reachable.
+    ///
+    /// With `OutagePolicy::RetryFor`, request which cannot connect to its endpoint or which is
+    /// rejected as unavailable is repeated on other endpoints, until it succeeds or time window
+    /// elapses. This bridges short outage of whole cluster, e.g. during leader election. Default
+    /// policy `OutagePolicy::Fail` returns error immediately. Retry policy is applied to every
+    /// attempt first.
+    ///
+    /// # Arguments
+    ///
+    /// - `policy`: outage policy
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use std::time::Duration;
+    /// use dgraph_tonic::{Client, OutagePolicy};
+    ///
+    /// let client = Client::new(vec!["http://127.0.0.1:19080", "http://127.0.0.1:29080"])
+    ///     .expect("Dgraph client")
+    ///     .with_outage_policy(OutagePolicy::RetryFor(Duration::from_secs(10)));
+    /// ```
+    ///
+    pub fn with_outage_policy(mut self, policy: OutagePolicy) -> Self {
+        self.state.outage_policy = policy;
+        self
+    }
+
+    ///
+    /// Cache version returned by `check_version` for given time to live.
+    ///
+    /// Cached version is returned without request to Dgraph until `ttl` elapses, so version
+    /// conditional code can call `check_version` frequently. Version is not cached by default.
+    /// Upgrade of Dgraph is therefore noticed with delay up to `ttl`.
+    ///
+    /// # Arguments
+    ///
+    /// - `ttl`: how long is version cached
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use std::time::Duration;
+    /// use dgraph_tonic::Client;
+    ///
+    /// let client = Client::new("http://127.0.0.1:19080")
+    ///     .expect("Dgraph client")
+    ///     .with_version_cache(Duration::from_secs(60));
+    /// ```
+    ///
+    pub fn with_version_cache(mut self, ttl: Duration) -> Self {
+        self.state.version_cache = Some(VersionCache::new(ttl));
+        self
+    }
+
+    ///
+    /// Set selector which decides which endpoint is used by new transactions and operations.
+    ///
+    /// Without selector, endpoints are selected randomly.
+    ///
+    /// # Arguments
+    ///
+    /// - `selector`: endpoint selector, e.g. `RandomSelector`, `FixedSelector` or
+    ///   `LeastOutstandingSelector`
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use dgraph_tonic::{Client, FixedSelector};
+    ///
+    /// let client = Client::new(vec!["http://127.0.0.1:19080", "http://127.0.0.1:29080"])
+    ///     .expect("Dgraph client")
+    ///     .with_endpoint_selector(FixedSelector(1));
+    /// ```
+    ///
+    pub fn with_endpoint_selector<S: EndpointSelector + 'static>(mut self, selector: S) -> Self {
+        self.state.selector = Some(Arc::new(selector));
+        self
+    }
+
+    ///
+    /// Set observer which is notified about every gRPC request of client and its transactions.
+    ///
+    /// Observer can be used to emit own latency and error metrics without dependency of this
+    /// crate on any metrics library. Without observer, nothing is reported.
+    ///
+    /// # Arguments
+    ///
+    /// - `observer`: shared observer
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use std::sync::Arc;
+    /// use std::time::Duration;
+    /// use dgraph_tonic::{Client, DgraphError, OpKind, RequestObserver};
+    ///
+    /// #[derive(Debug)]
+    /// struct LogObserver;
+    ///
+    /// impl RequestObserver for LogObserver {
+    ///     fn on_request(&self, op: OpKind) {
+    ///         println!("{:?} started", op);
+    ///     }
+    ///
+    ///     fn on_response(&self, op: OpKind, latency: Duration, result: Result<(), &DgraphError>) {
+    ///         println!("{:?} finished in {:?}, success: {}", op, latency, result.is_ok());
+    ///     }
+    /// }
+    ///
+    /// let client = Client::new("http://127.0.0.1:19080")
+    ///     .expect("Dgraph client")
+    ///     .with_observer(Arc::new(LogObserver));
+    /// ```
+    ///
+    pub fn with_observer(mut self, observer: Arc<dyn RequestObserver>) -> Self {
+        self.state.observer = Some(observer);
+        self
+    }
+
+    ///
+    /// Set maximal size of gRPC messages in bytes.
+    ///
+    /// Dgraph rejects gRPC messages over its limit (4MB by default) with hard to understand error.
+    /// Mutations of client and all its transactions are checked before they are sent and
+    /// `MessageTooLarge` error with actual size is returned instead. Limit is set also as
+    /// maximal size of encoded requests and decoded responses of underlying gRPC client, so
+    /// responses bigger than default 4MB limit can be received when limit is raised.
+    ///
+    /// # Arguments
+    ///
+    /// - `bytes`: maximal size of encoded request and response
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use dgraph_tonic::Client;
+    ///
+    /// let client = Client::new("http://127.0.0.1:19080")
+    ///     .expect("Dgraph client")
+    ///     .with_max_message_size(4 * 1024 * 1024);
+    /// ```
+    ///
+    pub fn with_max_message_size(mut self, bytes: usize) -> Self {
+        self.state.max_message_size = Some(bytes);
+        self
+    }
+
+    ///
+    /// Compress mutation requests bigger than threshold with gzip.
+    ///
+    /// Small mutations are sent without compression, so CPU is not wasted on them, while big
+    /// mutations of client and all its transactions are compressed. Size of encoded request is
+    /// compared. Transactions with `with_compression` compress every request regardless of it.
+    ///
+    /// # Arguments
+    ///
+    /// - `bytes`: size of encoded mutation request above which it is compressed
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use dgraph_tonic::Client;
+    ///
+    /// let client = Client::new("http://127.0.0.1:19080")
+    ///     .expect("Dgraph client")
+    ///     .with_compression_threshold(64 * 1024);
+    /// ```
+    ///
+    #[cfg(feature = "gzip")]
+    pub fn with_compression_threshold(mut self, bytes: usize) -> Self {
+        self.state.compression_threshold = Some(bytes);
+        self
     }
 
     ///
     /// Return transaction in default state, which can be specialized into ReadOnly or Mutated
     ///
-    pub fn new_txn(&self) -> TxnType<C::Client> {
-        TxnType::new(self.any_stub())
-    }
-
+    pub fn new_txn(&self) -> TxnType<C::Client> {
+        TxnType::new(self.any_stub())
+    }
+
+    ///
+    /// Create new transaction which can only do queries.
+    ///
+    /// Read-only transactions are useful to increase read speed because they can circumvent the
+    /// usual consensus protocol. All queries of transaction read same snapshot.
+    ///
+    pub fn new_read_only_txn(&self) -> TxnReadOnlyType<C::Client> {
+        self.new_txn().read_only()
+    }
+
+    ///
+    /// Create new transaction which can only do queries in best effort mode.
+    ///
+    /// Read-only queries can optionally be set as best-effort. Using this flag will ask the
+    /// Dgraph Alpha to try to get timestamps from memory on a best-effort basis to reduce the number
+    /// of outbound requests to Zero. This may yield improved latencies in read-bound workloads where
+    /// linearizable reads are not strictly needed.
+    ///
+    pub fn new_best_effort_txn(&self) -> TxnBestEffortType<C::Client> {
+        self.new_read_only_txn().best_effort()
+    }
+
+    ///
+    /// Create pool for high-throughput read-only queries, which reuses one endpoint and
+    /// prepared read-only transaction for all queries.
+    ///
+    /// See `ReadPool::with_shared_snapshot` for reads of periodically refreshed snapshot.
+    ///
+    pub fn read_pool(&self) -> ReadPool<C::Client> {
+        ReadPool::new(self.new_read_only_txn())
+    }
+
+    ///
+    /// Create new transaction which can do mutate, commit and discard operations
+    ///
+    /// Queries of transaction see its own uncommitted mutations, because they are sent with
+    /// start timestamp obtained by first mutation or query.
+    ///
+    pub fn new_mutated_txn(&self) -> TxnMutatedType<C::Client> {
+        self.new_txn().mutated()
+    }
+
+    ///
+    /// Run query in new read-only transaction and return deserialized response.
+    ///
+    /// Transaction is discarded after query, so use explicit transaction when several queries
+    /// have to see the same snapshot of data.
+    ///
+    /// # Arguments
+    ///
+    /// - `query`: GraphQL+- query
+    ///
+    /// # Errors
+    ///
+    /// * gRPC error
+    /// * `JsonDeserialize` if response cannot be deserialized into `T`
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use dgraph_tonic::Client;
+    /// use serde_json::Value;
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     let client = Client::new("http://127.0.0.1:19080").expect("Dgraph client");
+    ///     let all: Value = client.query("{ all(func: has(name)) { name } }").await.expect("Query");
+    ///     println!("{}", all);
+    /// }
+    /// ```
+    ///
+    pub async fn query<Q, T>(&self, query: Q) -> Result<T>
+    where
+        Q: Into<String> + Send + Sync,
+        T: DeserializeOwned,
+    {
+        let response = self.query_raw(query).await?;
+        Ok(response.try_into_owned()?)
+    }
+
+    ///
+    /// Run query in new read-only transaction and return raw response.
+    ///
+    /// # Arguments
+    ///
+    /// - `query`: GraphQL+- query
+    ///
+    /// # Errors
+    ///
+    /// * gRPC error
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use dgraph_tonic::Client;
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     let client = Client::new("http://127.0.0.1:19080").expect("Dgraph client");
+    ///     let response = client.query_raw("{ all(func: has(name)) { name } }").await.expect("Query");
+    ///     println!("{:?}", response.latency);
+    /// }
+    /// ```
+    ///
+    pub async fn query_raw<Q>(&self, query: Q) -> Result<Response>
+    where
+        Q: Into<String> + Send + Sync,
+    {
+        self.new_read_only_txn().query(query).await
+    }
+
+    ///
+    /// Send read-only query to all endpoints concurrently and return first successful response.
+    ///
+    /// Hedged request reduces tail latency of slow or overloaded endpoints. Query is executed by
+    /// every endpoint, so **only read-only queries** can be used and load of cluster is multiplied
+    /// by number of endpoints. Remaining requests are cancelled when first response is received.
+    ///
+    /// # Arguments
+    ///
+    /// - `query`: GraphQL+- query
+    ///
+    /// # Errors
+    ///
+    /// * last gRPC error if all endpoints fail
+    /// * `JsonDeserialize` if response cannot be deserialized into `T`
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use dgraph_tonic::Client;
+    /// use serde::Deserialize;
+    ///
+    /// #[derive(Deserialize, Debug)]
+    /// struct Persons {
+    ///     all: Vec<Person>,
+    /// }
+    ///
+    /// #[derive(Deserialize, Debug)]
+    /// struct Person {
+    ///     name: String,
+    /// }
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     let client = Client::new(vec!["http://127.0.0.1:19080", "http://127.0.0.1:29080"])
+    ///         .expect("Dgraph client");
+    ///     let persons: Persons = client
+    ///         .query_hedged("{ all(func: has(name)) { name } }")
+    ///         .await
+    ///         .expect("Persons");
+    ///     println!("{:?}", persons);
+    /// }
+    /// ```
+    ///
+    pub async fn query_hedged<Q, T>(&self, query: Q) -> Result<T>
+    where
+        Q: Into<String>,
+        T: DeserializeOwned,
+    {
+        let query = query.into();
+        let clients = self.extra.all_clients().into_iter().enumerate();
+        let queries = clients.map(|(index, client)| {
+            let stub = self.stub(client).with_outstanding(self.outstanding(index));
+            let mut txn = TxnType::new(stub).read_only();
+            let query = query.to_owned();
+            async move { txn.query(query).await }.boxed()
+        });
+        let (response, _) = select_ok(queries).await?;
+        Ok(response.try_into_owned()?)
+    }
+
+    ///
+    /// The /alter endpoint is used to create or change the schema.
+    ///
+    /// # Arguments
+    ///
+    /// - `op`: Alter operation
+    ///
+    /// # Errors
+    ///
+    /// * gRPC error
+    /// * DB reject alter command
+    ///
+    /// # Example
+    ///
+    /// Install a schema into dgraph. A `name` predicate is string type and has exact index.
+    ///
+    /// ```
+    /// use dgraph_tonic::{Client, Operation};
+    /// #[cfg(feature = "acl")]
+    /// use dgraph_tonic::{AclClientType, LazyChannel};
+    ///
+    /// #[cfg(not(feature = "acl"))]
+    /// async fn client() -> Client {
+    ///     Client::new("http://127.0.0.1:19080").expect("Dgraph client")
+    /// }
+    ///
+    /// #[cfg(feature = "acl")]
+    /// async fn client() -> AclClientType<LazyChannel> {
+    ///     let default = Client::new("http://127.0.0.1:19080").unwrap();
+    ///     default.login("groot", "password").await.expect("Acl client")
+    /// }
+    ///
+    /// #[tokio::main]
+    /// async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    ///     let client = client().await;
+    ///     let op = Operation {
+    ///         schema: "name: string @index(exact) .".into(),
+    ///         ..Default::default()
+    ///     };
+    ///     client.alter(op).await.expect("Schema is not updated");
+    ///     Ok(())
+    /// }
+    /// ```
+    ///
+    pub async fn alter(&self, op: Operation) -> Result<Payload> {
+        let mut stub = self.any_stub();
+        stub.retry(|stub| stub.alter(op.clone())).await
+    }
+
+    ///
+    /// Run any alter operation in background.
+    ///
+    /// Operation is sent with `run_in_background` flag, so Dgraph returns before operation is
+    /// finished.
+    ///
+    /// # Arguments
+    ///
+    /// - `op`: Alter operation
+    ///
+    /// # Errors
+    ///
+    /// * gRPC error
+    /// * DB reject alter command
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use dgraph_tonic::{Client, Operation};
+    /// #[cfg(feature = "acl")]
+    /// use dgraph_tonic::{AclClientType, LazyChannel};
+    ///
+    /// #[cfg(not(feature = "acl"))]
+    /// async fn client() -> Client {
+    ///     Client::new("http://127.0.0.1:19080").expect("Dgraph client")
+    /// }
+    ///
+    /// #[cfg(feature = "acl")]
+    /// async fn client() -> AclClientType<LazyChannel> {
+    ///     let default = Client::new("http://127.0.0.1:19080").unwrap();
+    ///     default.login("groot", "password").await.expect("Acl client")
+    /// }
+    ///
+    /// #[tokio::main]
+    /// async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    ///     let client = client().await;
+    ///     let op = Operation {
+    ///         drop_attr: "name".into(),
+    ///         ..Default::default()
+    ///     };
+    ///     client.alter_in_background(op).await.expect("Predicate is not dropped");
+    ///     Ok(())
+    /// }
+    /// ```
+    ///
+    #[cfg(any(
+        feature = "dgraph-1-1",
+        feature = "dgraph-21-03",
+        feature = "dgraph-24-02"
+    ))]
+    pub async fn alter_in_background(&self, mut op: Operation) -> Result<Payload> {
+        op.run_in_background = true;
+        self.alter(op).await
+    }
+
+    ///
+    /// Create or change the schema.
+    ///
+    /// # Arguments
+    ///
+    /// - `schema`: Schema modification, it can be produced by `SchemaBuilder`
+    ///
+    /// # Errors
+    ///
+    /// * gRPC error
+    /// * DB reject alter command
+    ///
+    /// # Example
+    ///
+    /// Install a schema into dgraph. A `name` predicate is string type and has exact index.
+    ///
+    /// ```
+    /// use dgraph_tonic::{Client, Operation};
+    /// #[cfg(feature = "acl")]
+    /// use dgraph_tonic::{AclClientType, LazyChannel};
+    ///
+    /// #[cfg(not(feature = "acl"))]
+    /// async fn client() -> Client {
+    ///     Client::new("http://127.0.0.1:19080").expect("Dgraph client")
+    /// }
+    ///
+    /// #[cfg(feature = "acl")]
+    /// async fn client() -> AclClientType<LazyChannel> {
+    ///     let default = Client::new("http://127.0.0.1:19080").unwrap();
+    ///     default.login("groot", "password").await.expect("Acl client")
+    /// }
+    ///
+    /// #[tokio::main]
+    /// async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    ///     let client = client().await;
+    ///     client.set_schema("name: string @index(exact) .").await.expect("Schema is not updated");
+    ///     Ok(())
+    /// }
+    /// ```
+    ///
+    pub async fn set_schema<S: Into<String>>(&self, schema: S) -> Result<Payload> {
+        let op = Operation {
+            schema: schema.into(),
+            ..Default::default()
+        };
+        self.alter(op).await
+    }
+
+    ///
+    /// Create or change the schema in background.
+    ///
+    /// # Arguments
+    ///
+    /// - `schema`: Schema modification
+    ///
+    /// # Errors
+    ///
+    /// * gRPC error
+    /// * DB reject alter command
+    ///
+    /// # Example
+    ///
+    /// Install a schema into dgraph. A `name` predicate is string type and has exact index.
+    ///
+    /// ```
+    /// use dgraph_tonic::{Client, Operation};
+    /// #[cfg(feature = "acl")]
+    /// use dgraph_tonic::{AclClientType, LazyChannel};
+    ///
+    /// #[cfg(not(feature = "acl"))]
+    /// async fn client() -> Client {
+    ///     Client::new("http://127.0.0.1:19080").expect("Dgraph client")
+    /// }
+    ///
+    /// #[cfg(feature = "acl")]
+    /// async fn client() -> AclClientType<LazyChannel> {
+    ///     let default = Client::new("http://127.0.0.1:19080").unwrap();
+    ///     default.login("groot", "password").await.expect("Acl client")
+    /// }
+    ///
+    /// #[tokio::main]
+    /// async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    ///     let client = client().await;
+    ///     client.set_schema_in_background("name: string @index(exact) .").await.expect("Schema is not updated");
+    ///     Ok(())
+    /// }
+    /// ```
+    ///
+    #[cfg(any(
+        feature = "dgraph-1-1",
+        feature = "dgraph-21-03",
+        feature = "dgraph-24-02"
+    ))]
+    pub async fn set_schema_in_background<S: Into<String>>(&self, schema: S) -> Result<Payload> {
+        let op = Operation {
+            schema: schema.into(),
+            run_in_background: true,
+            ..Default::default()
+        };
+        self.alter(op).await
+    }
+
+    ///
+    /// Create or change the schema in background and wait until all its indexes are built.
+    ///
+    /// Schema of indexed predicates is polled until Dgraph reports every index defined by
+    /// `schema`, so queries sent afterwards can use them. Schema without indexes is not polled.
+    ///
+    /// # Arguments
+    ///
+    /// - `schema`: Schema modification
+    /// - `poll_interval`: delay between two schema queries
+    /// - `timeout`: maximal time of waiting for indexes
     ///
-    /// Create new transaction which can only do queries.
+    /// # Errors
     ///
-    /// Read-only transactions are useful to increase read speed because they can circumvent the
-    /// usual consensus protocol.
+    /// * gRPC error
+    /// * DB reject alter command
+    /// * `Timeout`: indexes were not built before timeout expired
     ///
-    pub fn new_read_only_txn(&self) -> TxnReadOnlyType<C::Client> {
-        self.new_txn().read_only()
-    }
-
+    /// # Example
     ///
-    /// Create new transaction which can only do queries in best effort mode.
+    /// ```
+    /// use std::time::Duration;
+    /// use dgraph_tonic::{Client, Operation};
+    /// #[cfg(feature = "acl")]
+    /// use dgraph_tonic::{AclClientType, LazyChannel};
     ///
-    /// Read-only queries can optionally be set as best-effort. Using this flag will ask the
-    /// Dgraph Alpha to try to get timestamps from memory on a best-effort basis to reduce the number
-    /// of outbound requests to Zero. This may yield improved latencies in read-bound workloads where
-    /// linearizable reads are not strictly needed.
+    /// #[cfg(not(feature = "acl"))]
+    /// async fn client() -> Client {
+    ///     Client::new("http://127.0.0.1:19080").expect("Dgraph client")
+    /// }
     ///
-    pub fn new_best_effort_txn(&self) -> TxnBestEffortType<C::Client> {
-        self.new_read_only_txn().best_effort()
-    }
-
+    /// #[cfg(feature = "acl")]
+    /// async fn client() -> AclClientType<LazyChannel> {
+    ///     let default = Client::new("http://127.0.0.1:19080").unwrap();
+    ///     default.login("groot", "password").await.expect("Acl client")
+    /// }
     ///
-    /// Create new transaction which can do mutate, commit and discard operations
+    /// #[tokio::main]
+    /// async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    ///     let client = client().await;
+    ///     client
+    ///         .set_schema_and_wait(
+    ///             "name: string @index(exact) .",
+    ///             Duration::from_millis(100),
+    ///             Duration::from_secs(30),
+    ///         )
+    ///         .await
+    ///         .expect("Index is not built");
+    ///     Ok(())
+    /// }
+    /// ```
     ///
-    pub fn new_mutated_txn(&self) -> TxnMutatedType<C::Client> {
-        self.new_txn().mutated()
+    #[cfg(any(
+        feature = "dgraph-1-1",
+        feature = "dgraph-21-03",
+        feature = "dgraph-24-02"
+    ))]
+    pub async fn set_schema_and_wait<S: Into<String>>(
+        &self,
+        schema: S,
+        poll_interval: Duration,
+        timeout: Duration,
+    ) -> Result<Payload> {
+        let deadline = Instant::now() + timeout;
+        let schema = schema.into();
+        let expected = schema_indexes(&schema);
+        let payload = self.set_schema_in_background(schema).await?;
+        if expected.is_empty() {
+            return Ok(payload);
+        }
+        let predicates: Vec<&str> = expected
+            .iter()
+            .map(|(predicate, _)| predicate.as_str())
+            .collect();
+        let query = format!(
+            "schema(pred: [{}]) {{ predicate tokenizer }}",
+            predicates.join(", ")
+        );
+        loop {
+            let response = self.new_read_only_txn().query(query.as_str()).await?;
+            let reported: SchemaBlock = response.try_into_owned()?;
+            if indexes_built(&expected, &reported) {
+                return Ok(payload);
+            }
+            if Instant::now() + poll_interval > deadline {
+                anyhow::bail!(DgraphError::Timeout);
+            }
+            tokio::time::sleep(poll_interval).await;
+        }
     }
 
     ///
-    /// The /alter endpoint is used to create or change the schema.
-    ///
-    /// # Arguments
+    /// Drop all data in DB
     ///
-    /// - `op`: Alter operation
     ///
     /// # Errors
     ///
@@ -231,7 +1428,6 @@ impl<C: IClient> ClientVariant<C> {
     ///
     /// # Example
     ///
-    /// Install a schema into dgraph. A `name` predicate is string type and has exact index.
     ///
     /// ```
     /// use dgraph_tonic::{Client, Operation};
@@ -252,26 +1448,21 @@ impl<C: IClient> ClientVariant<C> {
     /// #[tokio::main]
     /// async fn main() -> Result<(), Box<dyn std::error::Error>> {
     ///     let client = client().await;
-    ///     let op = Operation {
-    ///         schema: "name: string @index(exact) .".into(),
-    ///         ..Default::default()
-    ///     };
-    ///     client.alter(op).await.expect("Schema is not updated");
+    ///     client.drop_all().await.expect("Data not dropped");
     ///     Ok(())
     /// }
     /// ```
     ///
-    pub async fn alter(&self, op: Operation) -> Result<Payload> {
-        let mut stub = self.any_stub();
-        stub.alter(op).await
+    pub async fn drop_all(&self) -> Result<Payload> {
+        let op = Operation {
+            drop_all: true,
+            ..Default::default()
+        };
+        self.alter(op).await
     }
 
     ///
-    /// Create or change the schema.
-    ///
-    /// # Arguments
-    ///
-    /// - `schema`: Schema modification
+    /// Drop all data in DB in background
     ///
     /// # Errors
     ///
@@ -280,8 +1471,6 @@ impl<C: IClient> ClientVariant<C> {
     ///
     /// # Example
     ///
-    /// Install a schema into dgraph. A `name` predicate is string type and has exact index.
-    ///
     /// ```
     /// use dgraph_tonic::{Client, Operation};
     /// #[cfg(feature = "acl")]
@@ -301,25 +1490,26 @@ impl<C: IClient> ClientVariant<C> {
     /// #[tokio::main]
     /// async fn main() -> Result<(), Box<dyn std::error::Error>> {
     ///     let client = client().await;
-    ///     client.set_schema("name: string @index(exact) .").await.expect("Schema is not updated");
+    ///     client.drop_all_in_background().await.expect("Data not dropped");
     ///     Ok(())
     /// }
     /// ```
     ///
-    pub async fn set_schema<S: Into<String>>(&self, schema: S) -> Result<Payload> {
+    #[cfg(any(
+        feature = "dgraph-1-1",
+        feature = "dgraph-21-03",
+        feature = "dgraph-24-02"
+    ))]
+    pub async fn drop_all_in_background(&self) -> Result<Payload> {
         let op = Operation {
-            schema: schema.into(),
+            drop_all: true,
             ..Default::default()
         };
-        self.alter(op).await
+        self.alter_in_background(op).await
     }
 
     ///
-    /// Create or change the schema in background.
-    ///
-    /// # Arguments
-    ///
-    /// - `schema`: Schema modification
+    /// Drop all data in DB, but keep schema and types.
     ///
     /// # Errors
     ///
@@ -328,10 +1518,8 @@ impl<C: IClient> ClientVariant<C> {
     ///
     /// # Example
     ///
-    /// Install a schema into dgraph. A `name` predicate is string type and has exact index.
-    ///
     /// ```
-    /// use dgraph_tonic::{Client, Operation};
+    /// use dgraph_tonic::Client;
     /// #[cfg(feature = "acl")]
     /// use dgraph_tonic::{AclClientType, LazyChannel};
     ///
@@ -349,35 +1537,43 @@ impl<C: IClient> ClientVariant<C> {
     /// #[tokio::main]
     /// async fn main() -> Result<(), Box<dyn std::error::Error>> {
     ///     let client = client().await;
-    ///     client.set_schema_in_background("name: string @index(exact) .").await.expect("Schema is not updated");
+    ///     client.drop_data().await.expect("Data not dropped");
     ///     Ok(())
     /// }
     /// ```
     ///
-    #[cfg(any(feature = "dgraph-1-1", feature = "dgraph-21-03"))]
-    pub async fn set_schema_in_background<S: Into<String>>(&self, schema: S) -> Result<Payload> {
+    #[cfg(any(
+        feature = "dgraph-1-1",
+        feature = "dgraph-21-03",
+        feature = "dgraph-24-02"
+    ))]
+    pub async fn drop_data(&self) -> Result<Payload> {
         let op = Operation {
-            schema: schema.into(),
-            run_in_background: true,
+            drop_op: DropOp::Data as i32,
             ..Default::default()
         };
         self.alter(op).await
     }
 
     ///
-    /// Drop all data in DB
+    /// Drop all data, data and schema of predicate or type.
+    ///
+    /// # Arguments
     ///
+    /// - `op`: what is dropped
+    /// - `value`: name of predicate for `DropOp::Attr` or name of type for `DropOp::Type`, it
+    ///   must be `None` for `DropOp::All` and `DropOp::Data`
     ///
     /// # Errors
     ///
+    /// * `InvalidDropOp`: value is missing or it is not expected by operation
     /// * gRPC error
     /// * DB reject alter command
     ///
     /// # Example
     ///
-    ///
     /// ```
-    /// use dgraph_tonic::{Client, Operation};
+    /// use dgraph_tonic::{Client, DropOp};
     /// #[cfg(feature = "acl")]
     /// use dgraph_tonic::{AclClientType, LazyChannel};
     ///
@@ -395,22 +1591,30 @@ impl<C: IClient> ClientVariant<C> {
     /// #[tokio::main]
     /// async fn main() -> Result<(), Box<dyn std::error::Error>> {
     ///     let client = client().await;
-    ///     client.drop_all().await.expect("Data not dropped");
+    ///     client.set_schema("nickname: string .").await.expect("Schema");
+    ///     client
+    ///         .alter_drop(DropOp::Attr, Some("nickname".to_string()))
+    ///         .await
+    ///         .expect("Predicate not dropped");
     ///     Ok(())
     /// }
     /// ```
     ///
-    pub async fn drop_all(&self) -> Result<Payload> {
-        let op = Operation {
-            drop_all: true,
-            ..Default::default()
-        };
+    #[cfg(any(
+        feature = "dgraph-1-1",
+        feature = "dgraph-21-03",
+        feature = "dgraph-24-02"
+    ))]
+    pub async fn alter_drop(&self, op: DropOp, value: Option<String>) -> Result<Payload> {
+        let op = drop_operation(op, value)?;
         self.alter(op).await
     }
 
     ///
     /// Check DB version
     ///
+    /// Version is cached when client was created with `with_version_cache`.
+    ///
     /// # Errors
     ///
     /// * gRPC error
@@ -430,8 +1634,128 @@ impl<C: IClient> ClientVariant<C> {
     /// ```
     ///
     pub async fn check_version(&self) -> Result<Version> {
+        let cache = self.state.version_cache.as_ref();
+        if let Some(version) = cache.and_then(VersionCache::get) {
+            return Ok(version);
+        }
         let mut stub = self.any_stub();
-        stub.check_version().await
+        let version = stub.retry(|stub| stub.check_version()).await?;
+        if let Some(cache) = cache {
+            cache.set(&version);
+        }
+        Ok(version)
+    }
+
+    ///
+    /// Return connected gRPC channel of one endpoint.
+    ///
+    /// Channel is shared with client, so it can be used by other gRPC services without opening
+    /// new connection. Endpoint is selected same way as for transactions.
+    ///
+    /// # Errors
+    ///
+    /// * `ConnectionFailed`: endpoint cannot be reached
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use dgraph_tonic::Client;
+    ///
+    /// #[tokio::main]
+    /// async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    ///     let client = Client::new("http://127.0.0.1:19080").expect("Dgraph client");
+    ///     let channel = client.channel().await.expect("Channel");
+    ///     // create other gRPC client with channel
+    ///     Ok(())
+    /// }
+    /// ```
+    ///
+    pub async fn channel(&self) -> Result<Channel> {
+        let mut channel = self.select_client().0.channel();
+        channel.channel().await
+    }
+
+    ///
+    /// Return true if channel of at least one endpoint was already connected.
+    ///
+    /// Channels are connected lazily on first request, so new client is not connected. Broken
+    /// connection is reconnected on next request and it is still reported as connected, so use
+    /// `check_version` to check health of DB.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use dgraph_tonic::Client;
+    ///
+    /// let client = Client::new("http://127.0.0.1:19080").expect("Dgraph client");
+    /// assert!(!client.is_connected());
+    /// ```
+    ///
+    pub fn is_connected(&self) -> bool {
+        self.extra
+            .all_clients()
+            .into_iter()
+            .any(|client| client.channel().is_connected())
+    }
+
+    ///
+    /// Gracefully shut down client.
+    ///
+    /// New requests of client and all its transactions are rejected with `ShutDown` error
+    /// immediately. Then requests in flight are awaited and open transactions with mutations are
+    /// aborted, so they do not stay open on server until its timeout. Requests still in flight
+    /// when timeout expires are cancelled and fail with `ShutDown` error. Abort is best effort and
+    /// its result is ignored. Aborts not finished before timeout keep running in background.
+    ///
+    /// # Arguments
+    ///
+    /// - `timeout`: maximal time of waiting for requests in flight and aborts
+    ///
+    /// # Errors
+    ///
+    /// * `Timeout`: some request was still in flight when timeout expired
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use std::time::Duration;
+    /// use dgraph_tonic::Client;
+    ///
+    /// #[tokio::main]
+    /// async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    ///     let client = Client::new("http://127.0.0.1:19080").expect("Dgraph client");
+    ///     client.shutdown(Duration::from_secs(5)).await.expect("Drained");
+    ///     Ok(())
+    /// }
+    /// ```
+    ///
+    pub async fn shutdown(&self, timeout: Duration) -> Result<()>
+    where
+        C::Client: 'static,
+    {
+        let deadline = Instant::now() + timeout;
+        let lifecycle = &self.state.lifecycle;
+        lifecycle.close();
+        let drained = tokio::time::timeout(timeout, lifecycle.drained())
+            .await
+            .is_ok();
+        if !drained {
+            lifecycle.cancel();
+        }
+        let aborts = lifecycle.take_open_txns().into_iter().map(|mut context| {
+            context.aborted = true;
+            let mut stub = self.any_stub().with_lifecycle(None);
+            tokio::spawn(async move {
+                let _ = stub.commit_or_abort(context).await;
+            })
+        });
+        let aborts = futures::future::join_all(aborts);
+        let _ = tokio::time::timeout_at(deadline.into(), aborts).await;
+        if drained {
+            Ok(())
+        } else {
+            Err(DgraphError::Timeout.into())
+        }
     }
 }
 
@@ -464,6 +1788,205 @@ mod tests {
         assert!(response.is_ok());
     }
 
+    #[cfg(any(
+        feature = "dgraph-1-1",
+        feature = "dgraph-21-03",
+        feature = "dgraph-24-02"
+    ))]
+    #[test]
+    fn drop_operation() {
+        let op = super::drop_operation(DropOp::Type, Some("Person".to_string())).unwrap();
+        assert_eq!(op.drop_op, DropOp::Type as i32);
+        assert_eq!(op.drop_value, "Person");
+        let op = super::drop_operation(DropOp::Data, None).unwrap();
+        assert_eq!(op.drop_op, DropOp::Data as i32);
+        assert!(op.drop_value.is_empty());
+        let invalid = [
+            (DropOp::Attr, None),
+            (DropOp::Type, Some(String::new())),
+            (DropOp::All, Some("name".to_string())),
+            (DropOp::None, None),
+        ];
+        for (op, value) in invalid.iter() {
+            let err = super::drop_operation(*op, value.to_owned()).unwrap_err();
+            assert!(matches!(
+                err.downcast_ref::<ClientError>(),
+                Some(ClientError::InvalidDropOp(_))
+            ));
+        }
+    }
+
+    #[cfg(any(
+        feature = "dgraph-1-1",
+        feature = "dgraph-21-03",
+        feature = "dgraph-24-02"
+    ))]
+    #[tokio::test]
+    async fn alter_drop() {
+        let client = client().await;
+        client
+            .set_schema("nickname: string .")
+            .await
+            .expect("Schema");
+        let response = client
+            .alter_drop(DropOp::Attr, Some("nickname".to_string()))
+            .await;
+        assert!(response.is_ok());
+    }
+
+    #[tokio::test]
+    async fn alter_payload() {
+        let client = client().await;
+        let payload = client
+            .set_schema("name: string @index(exact) .")
+            .await
+            .expect("Schema is not updated");
+        let data = payload.as_str().expect("UTF-8 payload");
+        if !data.is_empty() {
+            payload.json::<serde_json::Value>().expect("JSON payload");
+        }
+    }
+
+    #[cfg(any(
+        feature = "dgraph-1-1",
+        feature = "dgraph-21-03",
+        feature = "dgraph-24-02"
+    ))]
+    #[tokio::test]
+    async fn set_schema_and_wait() {
+        let client = client().await;
+        client
+            .set_schema_and_wait(
+                "name: string @index(exact, term) .\nage: int .",
+                Duration::from_millis(50),
+                Duration::from_secs(30),
+            )
+            .await
+            .expect("Index is built");
+        let response = client
+            .new_read_only_txn()
+            .query(r#"{ q(func: anyofterms(name, "Alice")) { uid } }"#)
+            .await;
+        assert!(response.is_ok());
+    }
+
+    #[cfg(any(
+        feature = "dgraph-1-1",
+        feature = "dgraph-21-03",
+        feature = "dgraph-24-02"
+    ))]
+    #[test]
+    fn schema_indexes() {
+        let schema = "<name>: string @index(exact, term) @lang .\n\
+                      age: int .\n\
+                      email: string @index(hash) @upsert .\n\
+                      type Person {\n  name\n}";
+        assert_eq!(
+            super::schema_indexes(schema),
+            vec![
+                (
+                    "name".to_string(),
+                    vec!["exact".to_string(), "term".to_string()]
+                ),
+                ("email".to_string(), vec!["hash".to_string()]),
+            ]
+        );
+        let schema = "embedding: float32vector @index(hnsw(metric:\"euclidean\")) .\n\
+                      <tagged>: [string] @index(hnsw(metric:\"cosine\", exponent:\"4\"), exact) .\n\
+                      broken: string @index(exact .";
+        assert_eq!(
+            super::schema_indexes(schema),
+            vec![
+                ("embedding".to_string(), vec!["hnsw".to_string()]),
+                (
+                    "tagged".to_string(),
+                    vec!["hnsw".to_string(), "exact".to_string()]
+                ),
+            ]
+        );
+    }
+
+    #[cfg(any(
+        feature = "dgraph-1-1",
+        feature = "dgraph-21-03",
+        feature = "dgraph-24-02"
+    ))]
+    #[test]
+    fn indexes_built() {
+        let expected = super::schema_indexes("name: string @index(exact, term) .");
+        let reported: SchemaBlock = serde_json::from_str(
+            r#"{"schema": [{"predicate": "name", "type": "string", "tokenizer": ["exact"]}]}"#,
+        )
+        .unwrap();
+        assert!(!super::indexes_built(&expected, &reported));
+        let reported: SchemaBlock = serde_json::from_str(
+            r#"{"schema": [{"predicate": "name", "tokenizer": ["term", "exact"]}]}"#,
+        )
+        .unwrap();
+        assert!(super::indexes_built(&expected, &reported));
+        let reported: SchemaBlock = serde_json::from_str("{}").unwrap();
+        assert!(!super::indexes_built(&expected, &reported));
+    }
+
+    #[tokio::test]
+    async fn shutdown_rejects_requests() {
+        let client = Client::new("http://127.0.0.1:1").unwrap();
+        client
+            .shutdown(Duration::from_millis(100))
+            .await
+            .expect("Drained");
+        let err = client.check_version().await.expect_err("Shut down");
+        assert!(matches!(
+            err.downcast_ref::<ClientError>(),
+            Some(ClientError::ShutDown)
+        ));
+    }
+
+    #[tokio::test]
+    async fn shutdown_timeout() {
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        let server = tokio::spawn(async move {
+            // connection is accepted, but no response is ever sent
+            let (_socket, _) = listener.accept().await.unwrap();
+            std::future::pending::<()>().await;
+        });
+        let client = Arc::new(Client::new(format!("http://{}", addr)).unwrap());
+        let in_flight = Arc::clone(&client);
+        let request = tokio::spawn(async move { in_flight.check_version().await });
+        tokio::time::sleep(Duration::from_millis(50)).await;
+        let err = client
+            .shutdown(Duration::from_millis(100))
+            .await
+            .expect_err("Request in flight");
+        assert!(matches!(
+            err.downcast_ref::<DgraphError>(),
+            Some(DgraphError::Timeout)
+        ));
+        request.abort();
+        server.abort();
+    }
+
+    #[tokio::test]
+    async fn shutdown_aborts_open_txn() {
+        use crate::Mutate;
+
+        let client = client().await;
+        let mut txn = client.new_mutated_txn();
+        let mut mu = crate::Mutation::new();
+        mu.set_set_nquads(r#"_:alice <name> "Alice" ."#);
+        txn.mutate(mu).await.expect("Mutated");
+        client
+            .shutdown(Duration::from_secs(5))
+            .await
+            .expect("Drained");
+        let err = txn.commit().await.expect_err("Shut down");
+        assert!(matches!(
+            err.downcast_ref::<ClientError>(),
+            Some(ClientError::ShutDown)
+        ));
+    }
+
     #[tokio::test]
     async fn drop_all() {
         let client = client().await;
@@ -471,10 +1994,381 @@ mod tests {
         assert!(response.is_ok());
     }
 
+    #[cfg(any(
+        feature = "dgraph-1-1",
+        feature = "dgraph-21-03",
+        feature = "dgraph-24-02"
+    ))]
+    #[tokio::test]
+    async fn drop_data() {
+        use crate::{Mutate, Mutation};
+
+        let client = client().await;
+        client
+            .set_schema("drop_data_name: string @index(exact) .")
+            .await
+            .expect("Schema");
+        let txn = client.new_mutated_txn();
+        let mut mu = Mutation::new();
+        mu.set_set_nquads(r#"_:node <drop_data_name> "Alice" ."#);
+        txn.mutate_and_commit_now(mu).await.expect("Mutation");
+        client.drop_data().await.expect("Data dropped");
+        let mut txn = client.new_read_only_txn();
+        let response = txn
+            .query(r#"{ q(func: eq(drop_data_name, "Alice")) { uid } }"#)
+            .await
+            .expect("Query");
+        let json: serde_json::Value = serde_json::from_slice(&response.json).unwrap();
+        assert_eq!(json["q"], serde_json::json!([]));
+        let response = txn
+            .query("schema(pred: [drop_data_name]) { type }")
+            .await
+            .expect("Schema query");
+        let json: serde_json::Value = serde_json::from_slice(&response.json).unwrap();
+        assert_eq!(json["schema"][0]["type"], "string");
+    }
+
     #[tokio::test]
     async fn check_version() {
         let client = client().await;
         let response = client.check_version().await;
         assert!(response.is_ok());
     }
+
+    #[tokio::test]
+    async fn channel() {
+        let client = client().await;
+        let channel = client.channel().await.expect("Channel");
+        let mut grpc = DClient::new(channel);
+        let response = grpc.check_version(crate::Check {}).await;
+        assert!(response.is_ok());
+    }
+
+    #[tokio::test]
+    async fn connection_failed() {
+        let client = Client::new("http://127.0.0.1:1").unwrap();
+        let err = client
+            .check_version()
+            .await
+            .expect_err("Connection refused");
+        assert!(matches!(
+            err.downcast_ref::<ClientError>(),
+            Some(ClientError::ConnectionFailed { .. })
+        ));
+    }
+
+    #[test]
+    fn balance_list_empty_endpoint() {
+        for endpoints in &[vec![""], vec!["  "], vec!["http://a", "\t"], vec!["\n\r"]] {
+            let err = balance_list(endpoints.clone()).expect_err("Empty endpoint");
+            assert!(matches!(
+                err.downcast_ref::<ClientError>(),
+                Some(ClientError::EmptyEndpoint)
+            ));
+        }
+        let err = balance_list(String::from(" ")).expect_err("Empty endpoint");
+        assert!(matches!(
+            err.downcast_ref::<ClientError>(),
+            Some(ClientError::EmptyEndpoint)
+        ));
+        let err = balance_list(Vec::<&str>::new()).expect_err("No endpoints");
+        assert!(matches!(
+            err.downcast_ref::<ClientError>(),
+            Some(ClientError::NoEndpointsDefined)
+        ));
+    }
+
+    #[test]
+    fn balance_list_invalid_endpoint() {
+        for endpoints in &[vec!["http://a b"], vec!["http://a", "\"\""]] {
+            let err = balance_list(endpoints.clone()).expect_err("Invalid endpoint");
+            assert!(matches!(
+                err.downcast_ref::<ClientError>(),
+                Some(ClientError::InvalidEndpoint)
+            ));
+        }
+        let err = balance_list(Vec::<u8>::new()).expect_err("Invalid endpoint");
+        assert!(matches!(
+            err.downcast_ref::<ClientError>(),
+            Some(ClientError::InvalidEndpoint)
+        ));
+        let list = balance_list(b"http://a".to_vec()).expect("Endpoint without Display");
+        assert_eq!(list.len(), 1);
+    }
+
+    #[test]
+    fn balance_list_dedup() {
+        let list = balance_list(vec!["http://a", "http://a", "http://b"]).unwrap();
+        assert_eq!(list, vec!["http://a", "http://b"]);
+        let client = Client::new(vec!["http://a", "http://a", "http://b"]).unwrap();
+        let uris: Vec<String> = client
+            .extra
+            .all_clients()
+            .iter()
+            .map(|client| client.uri().to_string())
+            .collect();
+        assert_eq!(uris, vec!["http://a/", "http://b/"]);
+    }
+
+    #[test]
+    fn endpoint_selector() {
+        let endpoints = vec!["http://a", "http://b", "http://c"];
+        let client = Client::new(endpoints.clone())
+            .unwrap()
+            .with_endpoint_selector(crate::FixedSelector(1));
+        assert_eq!(client.select_client().0.uri(), "http://b/");
+        let client = Client::new(endpoints)
+            .unwrap()
+            .with_endpoint_selector(crate::FixedSelector(5));
+        assert_eq!(client.select_client().0.uri(), "http://c/");
+    }
+
+    #[test]
+    fn least_outstanding_selector() {
+        let client = Client::new(vec!["http://a", "http://b"])
+            .unwrap()
+            .with_endpoint_selector(crate::LeastOutstandingSelector::default());
+        let (_, outstanding) = client.select_client();
+        let busy = outstanding.expect("Counter");
+        busy.fetch_add(1, std::sync::atomic::Ordering::SeqCst);
+        for _ in 0..10 {
+            let (_, idle) = client.select_client();
+            assert!(!Arc::ptr_eq(&busy, &idle.expect("Counter")));
+        }
+    }
+
+    #[tokio::test]
+    async fn query() {
+        let client = client().await;
+        let response: serde_json::Value = client
+            .query("{ q(func: uid(0x1)) { uid } }")
+            .await
+            .expect("Query");
+        assert!(response.get("q").is_some());
+        let response = client
+            .query_raw("{ q(func: uid(0x1)) { uid } }")
+            .await
+            .expect("Query");
+        assert!(!response.json.is_empty());
+    }
+
+    #[tokio::test]
+    async fn outage_policy() {
+        let endpoints = vec!["http://127.0.0.1:1", "http://127.0.0.1:2"];
+        let window = Duration::from_millis(500);
+        let client = Client::new(endpoints.clone()).unwrap();
+        let start = Instant::now();
+        let _ = client.query_raw("{ q(func: uid(0x1)) { uid } }").await;
+        assert!(start.elapsed() < window);
+        let client = Client::new(endpoints)
+            .unwrap()
+            .with_outage_policy(OutagePolicy::RetryFor(window));
+        let start = Instant::now();
+        let err = client
+            .query_raw("{ q(func: uid(0x1)) { uid } }")
+            .await
+            .expect_err("Outage");
+        assert!(start.elapsed() >= window);
+        assert!(matches!(
+            err.downcast_ref::<DgraphError>(),
+            Some(DgraphError::GrpcError(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn outage_policy_switches_endpoint() {
+        use crate::fake_server::{serve, FakeReply};
+        use crate::{Response, TxnContext};
+
+        // every endpoint is tried once before stub pauses between rounds
+        let window = Duration::from_secs(5);
+        for live in 0..3 {
+            for selected in 0..3 {
+                let (addr, server) = serve(|_| {
+                    FakeReply::new(&Response {
+                        txn: Some(TxnContext {
+                            start_ts: 1,
+                            ..Default::default()
+                        }),
+                        ..Default::default()
+                    })
+                })
+                .await;
+                let mut endpoints = vec![
+                    "http://127.0.0.1:1".to_string(),
+                    "http://127.0.0.1:2".to_string(),
+                ];
+                endpoints.insert(live, addr);
+                let client = Client::new(endpoints)
+                    .unwrap()
+                    .with_endpoint_selector(crate::FixedSelector(selected))
+                    .with_outage_policy(OutagePolicy::RetryFor(window));
+                let start = Instant::now();
+                let response = client.query_raw("{ q(func: uid(0x1)) { uid } }").await;
+                assert!(response.is_ok(), "live: {}, selected: {}", live, selected);
+                assert!(
+                    start.elapsed() < Duration::from_millis(150),
+                    "live: {}, selected: {}",
+                    live,
+                    selected
+                );
+                server.abort();
+            }
+        }
+    }
+
+    #[tokio::test]
+    async fn query_hedged() {
+        let client = Client::new(vec!["http://127.0.0.1:1", "http://127.0.0.1:19080"]).unwrap();
+        let response: serde_json::Value = client
+            .query_hedged("{ q(func: uid(0x1)) { uid } }")
+            .await
+            .expect("Hedged query");
+        assert!(response.get("q").is_some());
+    }
+
+    #[tokio::test]
+    async fn query_hedged_all_failed() {
+        let client = Client::new(vec!["http://127.0.0.1:1", "http://127.0.0.1:2"]).unwrap();
+        let err = client
+            .query_hedged::<_, serde_json::Value>("{ q(func: uid(0x1)) { uid } }")
+            .await
+            .expect_err("Connection refused");
+        assert!(matches!(
+            err.downcast_ref::<crate::errors::DgraphError>(),
+            Some(crate::errors::DgraphError::GrpcError(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn new_eager_connection_failed() {
+        let err = Client::new_eager(vec!["http://127.0.0.1:1", "http://127.0.0.1:2"])
+            .await
+            .expect_err("Connection refused");
+        assert!(matches!(
+            err.downcast_ref::<ClientError>(),
+            Some(ClientError::ConnectionFailed { .. })
+        ));
+        assert!(err.to_string().contains("http://127.0.0.1:2/"));
+    }
+
+    #[cfg(feature = "uds")]
+    #[tokio::test]
+    async fn uds_connect() {
+        let path = std::env::temp_dir().join(format!("dgraph tonic-{}.sock", std::process::id()));
+        let _ = std::fs::remove_file(&path);
+        let listener = tokio::net::UnixListener::bind(&path).unwrap();
+        let client = Client::new_uds(&path)
+            .unwrap()
+            .with_deadline_source(|| Some(Instant::now() + std::time::Duration::from_millis(200)));
+        let (accepted, _) = tokio::join!(listener.accept(), client.check_version());
+        assert!(accepted.is_ok());
+        std::fs::remove_file(&path).unwrap();
+        let client = Client::new_uds(&path).unwrap();
+        let err = client.check_version().await.expect_err("Socket removed");
+        assert!(matches!(
+            err.downcast_ref::<ClientError>(),
+            Some(ClientError::ConnectionFailed { .. })
+        ));
+    }
+
+    #[cfg(feature = "uds")]
+    #[test]
+    fn uds_relative_path() {
+        assert!(Client::new_uds("dgraph.sock").is_err());
+        assert!(Client::new_uds("unix://dgraph.sock").is_err());
+    }
+
+    #[cfg(feature = "uds")]
+    #[test]
+    fn uds_endpoints() {
+        let uri = uds_uri(Path::new("/var/run/my dir/dgraph%.sock")).unwrap();
+        assert_eq!(uri, "unix://localhost/var/run/my%20dir/dgraph%25.sock");
+        assert_eq!(
+            uds_path(&uri).unwrap(),
+            PathBuf::from("/var/run/my dir/dgraph%.sock")
+        );
+        let list = balance_list("unix://localhost/var/run/my%20dir/dgraph.sock").unwrap();
+        assert_eq!(
+            uds_path(&list[0]).unwrap(),
+            PathBuf::from("/var/run/my dir/dgraph.sock")
+        );
+        assert!(balance_list("unix://localhost/var/run/dgraph%2.sock").is_err());
+        assert!(uds_path(&"http://localhost/a%20b".parse().unwrap()).is_none());
+        let client = Client::new_uds("unix:///var/run/dgraph.sock").unwrap();
+        let uris: Vec<String> = client
+            .extra
+            .all_clients()
+            .iter()
+            .map(|client| client.uri().to_string())
+            .collect();
+        assert_eq!(uris, vec!["unix://localhost/var/run/dgraph.sock"]);
+    }
+
+    #[tokio::test]
+    async fn check_version_with_expired_deadline() {
+        let client = client()
+            .await
+            .with_deadline_source(|| Some(Instant::now() - std::time::Duration::from_secs(1)));
+        let response = client.check_version().await;
+        assert!(response.is_err());
+    }
+
+    #[test]
+    fn version_cache() {
+        let cache = VersionCache::new(Duration::from_millis(50));
+        assert!(cache.get().is_none());
+        let version = Version {
+            tag: "v21.03.0".to_string(),
+        };
+        cache.set(&version);
+        assert_eq!(cache.get(), Some(version));
+        std::thread::sleep(Duration::from_millis(60));
+        assert!(cache.get().is_none());
+    }
+
+    #[tokio::test]
+    async fn check_version_cached() {
+        let client = client().await.with_version_cache(Duration::from_secs(60));
+        let version = client.check_version().await.expect("Version");
+        assert_eq!(client.version_cache.as_ref().unwrap().get(), Some(version));
+    }
+
+    #[tokio::test]
+    async fn check_version_with_deadline() {
+        let client = client()
+            .await
+            .with_deadline_source(|| Some(Instant::now() + std::time::Duration::from_secs(5)));
+        let response = client.check_version().await;
+        assert!(response.is_ok());
+    }
+
+    #[cfg(any(
+        feature = "dgraph-1-1",
+        feature = "dgraph-21-03",
+        feature = "dgraph-24-02"
+    ))]
+    #[tokio::test]
+    async fn drop_all_in_background() {
+        use crate::Query;
+        let client = client().await;
+        client
+            .set_schema("color: string @index(exact) .")
+            .await
+            .expect("Schema is not updated");
+        let response = client.drop_all_in_background().await;
+        assert!(response.is_ok());
+        let mut dropped = false;
+        for _ in 0..50 {
+            let mut txn = client.new_read_only_txn();
+            let response = txn.query("schema(pred: [color]) {}").await.expect("Schema");
+            let schema = serde_json::Value::from(response);
+            if schema.get("schema").is_none() {
+                dropped = true;
+                break;
+            }
+            tokio::time::sleep(std::time::Duration::from_millis(100)).await;
+        }
+        assert!(dropped);
+    }
 }
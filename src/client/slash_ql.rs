@@ -1,4 +1,5 @@
 use std::convert::TryInto;
+use std::fmt::Debug;
 use std::sync::Arc;
 
 use anyhow::Result;
@@ -13,6 +14,7 @@ use crate::api::dgraph_client::DgraphClient as DClient;
 use crate::client::lazy::{ILazyChannel, ILazyClient};
 use crate::client::tls::LazyTlsChannel;
 use crate::client::{rnd_item, ClientVariant, DgraphClient, DgraphInterceptorClient, IClient};
+use crate::errors::ClientError;
 use crate::{
     EndpointConfig, Endpoints, Status, TlsClient, TxnBestEffortType, TxnMutatedType,
     TxnReadOnlyType, TxnType,
@@ -33,6 +35,39 @@ impl Interceptor for SlashQlInterceptor {
 
 pub type DgraphSlashQlClient = DgraphInterceptorClient<SlashQlInterceptor>;
 
+const CLOUD_DOMAIN: &str = ".cloud.dgraph.io";
+
+///
+/// Transform GraphQL url of Dgraph Cloud backend into endpoint of its gRPC API.
+///
+/// `https://app.eu-central-1.aws.cloud.dgraph.io/graphql` is transformed into
+/// `http://app.grpc.eu-central-1.aws.cloud.dgraph.io:443`.
+///
+pub(crate) fn grpc_endpoint_from_graphql_url(graphql_url: &str) -> Result<String> {
+    let uri: Uri = match graphql_url.trim().parse() {
+        Ok(uri) => uri,
+        Err(_err) => return Err(ClientError::InvalidEndpoint.into()),
+    };
+    if uri.scheme_str() != Some("https") || uri.path().trim_end_matches('/') != "/graphql" {
+        return Err(ClientError::InvalidEndpoint.into());
+    }
+    let host = uri.host().unwrap_or_default().to_lowercase();
+    let (name, region) = match host
+        .strip_suffix(CLOUD_DOMAIN)
+        .and_then(|h| h.split_once('.'))
+    {
+        Some((name, region)) if !name.is_empty() && !region.is_empty() => (name, region),
+        _ => return Err(ClientError::InvalidEndpoint.into()),
+    };
+    if region.split('.').next() == Some("grpc") {
+        return Err(ClientError::InvalidEndpoint.into());
+    }
+    Ok(format!(
+        "http://{}.grpc.{}{}:443",
+        name, region, CLOUD_DOMAIN
+    ))
+}
+
 ///
 /// SlashQL gRPC lazy Dgraph client
 ///
@@ -82,6 +117,10 @@ impl ILazyClient for LazySlashQlClient {
     fn channel(self) -> Self::Channel {
         self.channel
     }
+
+    fn uri(&self) -> &Uri {
+        self.channel.uri()
+    }
 }
 
 ///
@@ -90,7 +129,7 @@ impl ILazyClient for LazySlashQlClient {
 #[derive(Debug)]
 #[doc(hidden)]
 pub struct SlashQl {
-    clients: Vec<LazySlashQlClient>,
+    clients: Arc<[LazySlashQlClient]>,
 }
 
 #[async_trait]
@@ -103,7 +142,15 @@ impl IClient for SlashQl {
     }
 
     fn clients(self) -> Vec<Self::Client> {
-        self.clients
+        self.clients.to_vec()
+    }
+
+    fn all_clients(&self) -> Vec<Self::Client> {
+        self.clients.to_vec()
+    }
+
+    fn shared_clients(&self) -> Arc<[Self::Client]> {
+        Arc::clone(&self.clients)
     }
 }
 
@@ -143,7 +190,7 @@ impl TlsClient {
                 let channel = client.channel();
                 LazySlashQlClient::new(channel, Arc::clone(&api_key))
             })
-            .collect::<Vec<LazySlashQlClient>>();
+            .collect::<Arc<[LazySlashQlClient]>>();
         Ok(SlashQlClient {
             state: tls_client.state,
             extra: SlashQl { clients },
@@ -182,7 +229,7 @@ impl TlsClient {
     /// }
     /// ```
     ///
-    pub fn for_slash_ql<S: TryInto<Uri>, E: Into<Endpoints<S>>, T: Into<String>>(
+    pub fn for_slash_ql<S: TryInto<Uri> + Debug, E: Into<Endpoints<S>>, T: Into<String>>(
         endpoints: E,
         api_key: T,
     ) -> Result<SlashQlClient> {
@@ -191,6 +238,45 @@ impl TlsClient {
         Self::lift_client(api_key, tls_client)
     }
 
+    ///
+    /// New gRPC [SlashQL](https://dgraph.io/slash-graphql) client for GraphQL url of backend.
+    ///
+    /// gRPC endpoint is derived from GraphQL url, e.g. for
+    /// `https://app.eu-central-1.aws.cloud.dgraph.io/graphql` is client connected to
+    /// `http://app.grpc.eu-central-1.aws.cloud.dgraph.io:443`.
+    ///
+    /// # Arguments
+    ///
+    /// * `graphql_url` - GraphQL url of Dgraph Cloud backend
+    /// * `api_key` -  API Key for SlashQL
+    ///
+    /// # Errors
+    ///
+    /// * `InvalidEndpoint`: url does not match GraphQL url of Dgraph Cloud backend
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use dgraph_tonic::TlsClient;
+    ///
+    /// #[tokio::main]
+    /// async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    ///     let client = TlsClient::for_slash_ql_from_graphql_url(
+    ///             "https://app.eu-central-1.aws.cloud.dgraph.io/graphql",
+    ///             "API_KEY",
+    ///         ).expect("Dgraph client");
+    ///     // now you can use client for all operations over DB
+    ///     Ok(())
+    /// }
+    /// ```
+    ///
+    pub fn for_slash_ql_from_graphql_url<T: Into<String>>(
+        graphql_url: &str,
+        api_key: T,
+    ) -> Result<SlashQlClient> {
+        Self::for_slash_ql(grpc_endpoint_from_graphql_url(graphql_url)?, api_key)
+    }
+
     ///
     /// New gRPC [SlashQL](https://dgraph.io/slash-graphql) client with endpoint config.
     ///
@@ -237,7 +323,7 @@ impl TlsClient {
     /// ```
     ///
     pub fn for_slash_ql_with_endpoint_config<
-        S: TryInto<Uri>,
+        S: TryInto<Uri> + Debug,
         E: Into<Endpoints<S>>,
         T: Into<String>,
         C: EndpointConfig + 'static,
@@ -254,9 +340,11 @@ impl TlsClient {
 
 #[cfg(test)]
 mod tests {
-    use crate::{Endpoint, EndpointConfig, TlsClient};
+    use crate::{ClientError, Endpoint, EndpointConfig, TlsClient};
     use std::time::Duration;
 
+    use super::grpc_endpoint_from_graphql_url;
+
     #[derive(Debug, Default)]
     struct EndpointWithTimeout {}
 
@@ -291,4 +379,34 @@ mod tests {
         let version = client.check_version().await;
         assert!(version.is_ok());
     }
+
+    #[test]
+    fn graphql_url_to_grpc_endpoint() {
+        let urls = [
+            "https://app.eu-central-1.aws.cloud.dgraph.io/graphql",
+            "https://app.eu-central-1.aws.cloud.dgraph.io/graphql/",
+        ];
+        for url in &urls {
+            assert_eq!(
+                grpc_endpoint_from_graphql_url(url).unwrap(),
+                "http://app.grpc.eu-central-1.aws.cloud.dgraph.io:443"
+            );
+        }
+        let invalid = [
+            "http://app.eu-central-1.aws.cloud.dgraph.io/graphql",
+            "https://app.eu-central-1.aws.cloud.dgraph.io/admin",
+            "https://app.grpc.eu-central-1.aws.cloud.dgraph.io/graphql",
+            "https://cloud.dgraph.io/graphql",
+            "https://app.example.com/graphql",
+            "",
+        ];
+        for url in &invalid {
+            let err = grpc_endpoint_from_graphql_url(url).expect_err("Invalid url");
+            assert!(matches!(
+                err.downcast_ref::<ClientError>(),
+                Some(ClientError::InvalidEndpoint)
+            ));
+        }
+        assert!(TlsClient::for_slash_ql_from_graphql_url(urls[0], "API_KEY").is_ok());
+    }
 }
@@ -1,25 +1,38 @@
 use std::convert::TryInto;
+use std::fmt::Debug;
+use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::Arc;
 
 use anyhow::Result;
 use async_trait::async_trait;
 use http::Uri;
+use tokio::sync::Mutex;
 use tonic::transport::{Certificate, Channel, ClientTlsConfig, Endpoint, Identity};
+use tower::service_fn;
 
-use crate::client::lazy::{ILazyChannel, LazyClient};
+use crate::client::lazy::{ILazyChannel, ILazyClient, LazyClient};
+use crate::client::proxy::{env_proxy, parse_proxy, tunnel};
 use crate::client::{balance_list, rnd_item, ClientState, ClientVariant, EndpointConfig, IClient};
+use crate::errors::ClientError;
 use crate::{Endpoints, TxnBestEffortType, TxnMutatedType, TxnReadOnlyType, TxnType};
 
 ///
 /// Lazy initialization of gRPC channel with TLS
 ///
+/// Connected channel is shared by all clones, so clients lifted by `login` reuse established
+/// connection.
+///
+/// Broken connection is reconnected by channel itself, same as for `LazyChannel`.
+///
 #[derive(Clone, Debug)]
 #[doc(hidden)]
 pub struct LazyTlsChannel {
     uri: Uri,
     endpoint_config: Option<Arc<dyn EndpointConfig>>,
     tls: Arc<ClientTlsConfig>,
-    channel: Option<Channel>,
+    channel: Arc<Mutex<Option<Channel>>>,
+    connected: Arc<AtomicBool>,
+    proxy: Option<Uri>,
 }
 
 impl LazyTlsChannel {
@@ -27,8 +40,10 @@ impl LazyTlsChannel {
         Self {
             uri,
             tls,
-            channel: None,
+            channel: Arc::new(Mutex::new(None)),
+            connected: Arc::new(AtomicBool::new(false)),
             endpoint_config: None,
+            proxy: None,
         }
     }
 
@@ -36,26 +51,98 @@ impl LazyTlsChannel {
         self.endpoint_config = endpoint_config;
         self
     }
+
+    ///
+    /// Channel will be connected through proxy.
+    ///
+    pub(crate) fn with_proxy(mut self, proxy: Option<Uri>) -> Self {
+        self.proxy = proxy;
+        self.channel = Arc::new(Mutex::new(None));
+        self.connected = Arc::new(AtomicBool::new(false));
+        self
+    }
+
+    ///
+    /// Server certificate will be verified against given domain name instead of host of uri.
+    ///
+    fn with_domain_name(mut self, domain_name: &str) -> Self {
+        self.tls = Arc::new(self.tls.as_ref().clone().domain_name(domain_name));
+        self.channel = Arc::new(Mutex::new(None));
+        self.connected = Arc::new(AtomicBool::new(false));
+        self
+    }
+
+    ///
+    /// Return clone of connected channel. Channel is connected if it is not already.
+    ///
+    /// # Errors
+    ///
+    /// * `ConnectionFailed`: endpoint cannot be reached
+    ///
+    pub async fn connected_channel(&mut self) -> Result<Channel> {
+        self.channel().await
+    }
+
+    ///
+    /// Return true if channel was already connected.
+    ///
+    /// Connected channel is not dropped when its connection is broken, so it is not a health
+    /// check of endpoint. It only distinguishes channel which was never connected.
+    ///
+    pub fn is_connected(&self) -> bool {
+        self.connected.load(Ordering::SeqCst)
+    }
 }
 
 #[async_trait]
 impl ILazyChannel for LazyTlsChannel {
     async fn channel(&mut self) -> Result<Channel> {
-        if let Some(channel) = &self.channel {
+        let mut shared = self.channel.lock().await;
+        if let Some(channel) = shared.as_ref() {
             Ok(channel.to_owned())
         } else {
             let mut endpoint: Endpoint = self.uri.to_owned().into();
             if let Some(endpoint_config) = &self.endpoint_config {
                 endpoint = endpoint_config.configure_endpoint(endpoint);
             }
-            let channel = endpoint
-                .tls_config(self.tls.as_ref().clone())?
-                .connect()
-                .await?;
-            self.channel.replace(channel.to_owned());
+            let endpoint = match endpoint.tls_config(self.tls.as_ref().clone()) {
+                Ok(endpoint) => endpoint,
+                Err(source) => return Err(ClientError::TlsError { source }.into()),
+            };
+            let channel = match &self.proxy {
+                Some(proxy) => {
+                    let proxy = proxy.to_owned();
+                    endpoint
+                        .connect_with_connector(service_fn(move |uri: Uri| {
+                            tunnel(proxy.to_owned(), uri)
+                        }))
+                        .await
+                }
+                None => endpoint.connect().await,
+            };
+            let channel = match channel {
+                Ok(channel) => channel,
+                Err(source) => {
+                    return Err(ClientError::ConnectionFailed {
+                        uri: self.uri.to_owned(),
+                        source,
+                    }
+                    .into());
+                }
+            };
+            shared.replace(channel.to_owned());
+            self.connected.store(true, Ordering::SeqCst);
             Ok(channel)
         }
     }
+
+    fn is_connected(&self) -> bool {
+        LazyTlsChannel::is_connected(self)
+    }
+
+    fn uri(&self) -> &Uri {
+        &self.uri
+    }
 }
 
 ///
@@ -64,7 +151,7 @@ impl ILazyChannel for LazyTlsChannel {
 #[derive(Debug)]
 #[doc(hidden)]
 pub struct Tls {
-    clients: Vec<LazyClient<LazyTlsChannel>>,
+    clients: Arc<[LazyClient<LazyTlsChannel>]>,
 }
 
 #[async_trait]
@@ -77,7 +164,15 @@ impl IClient for Tls {
     }
 
     fn clients(self) -> Vec<Self::Client> {
-        self.clients
+        self.clients.to_vec()
+    }
+
+    fn all_clients(&self) -> Vec<Self::Client> {
+        self.clients.to_vec()
+    }
+
+    fn shared_clients(&self) -> Arc<[Self::Client]> {
+        Arc::clone(&self.clients)
     }
 }
 
@@ -120,7 +215,7 @@ impl TlsClient {
         Arc::new(tls)
     }
 
-    pub(crate) fn init<S: TryInto<Uri>, E: Into<Endpoints<S>>>(
+    pub(crate) fn init<S: TryInto<Uri> + Debug, E: Into<Endpoints<S>>>(
         endpoints: E,
         tls: Arc<ClientTlsConfig>,
         endpoint_config: Option<Arc<dyn EndpointConfig>>,
@@ -144,6 +239,7 @@ impl TlsClient {
     /// Create new Dgraph client authorized with SSL cert and custom endpoint configuration for interacting v DB.
     ///
     /// The client can be backed by multiple endpoints (to the same server, or multiple servers in a cluster).
+    /// Duplicated endpoints are used only once.
     ///
     /// # Arguments
     ///
@@ -192,7 +288,7 @@ impl TlsClient {
     /// ```
     ///
     pub fn new_with_endpoint_config<
-        S: TryInto<Uri>,
+        S: TryInto<Uri> + Debug,
         E: Into<Endpoints<S>>,
         V: Into<Vec<u8>>,
         C: EndpointConfig + 'static,
@@ -211,6 +307,7 @@ impl TlsClient {
     /// Create new Dgraph client authorized with SSL cert for interacting v DB.
     ///
     /// The client can be backed by multiple endpoints (to the same server, or multiple servers in a cluster).
+    /// Duplicated endpoints are used only once.
     ///
     /// # Arguments
     ///
@@ -244,7 +341,7 @@ impl TlsClient {
     /// }
     /// ```
     ///
-    pub fn new<S: TryInto<Uri>, E: Into<Endpoints<S>>, V: Into<Vec<u8>>>(
+    pub fn new<S: TryInto<Uri> + Debug, E: Into<Endpoints<S>>, V: Into<Vec<u8>>>(
         endpoints: E,
         server_root_ca_cert: V,
         client_cert: V,
@@ -253,4 +350,116 @@ impl TlsClient {
         let tls = Self::init_tls(server_root_ca_cert, client_cert, client_key);
         Self::init(endpoints, tls, None)
     }
+
+    ///
+    /// Verify certificates of all endpoints against given domain name.
+    ///
+    /// By default, domain name is taken from host of endpoint uri. Behind load balancer, host of
+    /// connection differs from name in certificate of Dgraph, so verification fails without
+    /// this override.
+    ///
+    /// # Arguments
+    ///
+    /// * `domain_name` - name in server certificate, e.g. `dgraph.example.com`
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use dgraph_tonic::TlsClient;
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     let server_root_ca_cert = tokio::fs::read("path/to/ca.crt").await.expect("CA cert");
+    ///     let client_cert = tokio::fs::read("path/to/client.crt").await.expect("Client cert");
+    ///     let client_key = tokio::fs::read("path/to/ca.key").await.expect("Client key");
+    ///     let client = TlsClient::new(
+    ///             "https://10.0.0.1:9080",
+    ///             server_root_ca_cert,
+    ///             client_cert,
+    ///             client_key)
+    ///         .expect("Dgraph TLS client")
+    ///         .with_domain_name("dgraph.example.com");
+    /// }
+    /// ```
+    ///
+    pub fn with_domain_name<D: AsRef<str>>(mut self, domain_name: D) -> Self {
+        self.extra.clients = self
+            .extra
+            .clients
+            .iter()
+            .cloned()
+            .map(|client| LazyClient::new(client.channel().with_domain_name(domain_name.as_ref())))
+            .collect();
+        self
+    }
+
+    ///
+    /// Connect all endpoints through HTTP proxy.
+    ///
+    /// Connection is tunneled with HTTP `CONNECT` request and TLS is negotiated with Dgraph inside
+    /// of tunnel, so proxy cannot read it. Only `http://` proxies without authentication are
+    /// supported.
+    ///
+    /// # Arguments
+    ///
+    /// * `proxy` - uri of proxy, e.g. `http://proxy:3128`
+    ///
+    /// # Errors
+    ///
+    /// * `InvalidProxy`: proxy cannot be converted into Uri or it is not supported
+    ///
+    pub fn with_proxy<U: TryInto<Uri>>(mut self, proxy: U) -> Result<Self> {
+        let proxy = parse_proxy(proxy)?;
+        self.extra.clients = self
+            .extra
+            .clients
+            .iter()
+            .cloned()
+            .map(|client| LazyClient::new(client.channel().with_proxy(Some(proxy.to_owned()))))
+            .collect();
+        Ok(self)
+    }
+
+    ///
+    /// Connect endpoints through proxy defined by environment.
+    ///
+    /// Proxy is read from `HTTPS_PROXY` or `https_proxy` variable. Hosts listed in `NO_PROXY` or
+    /// `no_proxy` variable are connected directly. Same restrictions as in `with_proxy` apply.
+    ///
+    /// # Errors
+    ///
+    /// * `InvalidProxy`: proxy from environment is not supported
+    ///
+    pub fn with_proxy_from_env(mut self) -> Result<Self> {
+        let mut clients = Vec::new();
+        for client in self.extra.clients.iter().cloned() {
+            let proxy = env_proxy(client.uri(), true)?;
+            clients.push(LazyClient::new(client.channel().with_proxy(proxy)));
+        }
+        self.extra.clients = clients.into();
+        Ok(self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::client::lazy::ILazyClient;
+    use crate::TlsClient;
+
+    #[test]
+    fn with_domain_name() {
+        let client = TlsClient::new(
+            vec!["https://10.0.0.1:9080", "https://10.0.0.2:9080"],
+            Vec::new(),
+            Vec::new(),
+            Vec::new(),
+        )
+        .unwrap()
+        .with_domain_name("dgraph.example.com");
+        for client in client.extra.clients.iter().cloned() {
+            let channel = client.channel();
+            assert!(format!("{:?}", channel.tls).contains(r#"domain: Some("dgraph.example.com")"#));
+            assert!(!channel.is_connected());
+        }
+    }
 }
@@ -1,4 +1,6 @@
+use http::Uri;
 use thiserror::Error as Fail;
+use tonic::transport::Error as TransportError;
 
 use crate::Status;
 
@@ -9,22 +11,63 @@ use crate::Status;
 pub enum Error {
     #[error("Client: invalid endpoint")]
     InvalidEndpoint,
+    #[error("Client: endpoint is empty")]
+    EmptyEndpoint,
+    #[error("Client: invalid proxy, {0}")]
+    InvalidProxy(String),
     #[error("Client: no endpoints defined")]
     NoEndpointsDefined,
-    #[error("Client: cannot do alter on DB.\n{0:?}")]
-    CannotAlter(Status),
-    #[error("Client: cannot login.\n{0:?}")]
-    CannotLogin(Status),
-    #[error("Client: cannot refresh login.\n{0:?}")]
-    CannotRefreshLogin(Status),
-    #[error("Client: cannot query.\n{0:?}")]
-    CannotQuery(Status),
-    #[error("Client: cannot mutate.\n{0:?}")]
-    CannotMutate(Status),
-    #[error("Client: cannot do request.\n{0:?}")]
-    CannotDoRequest(Status),
-    #[error("Client: cannot commit or abort.\n{0:?}")]
-    CannotCommitOrAbort(Status),
-    #[error("Client: cannot check version.\n{0:?}")]
-    CannotCheckVersion(Status),
+    #[error("Client: cannot connect to {uri}.\n{source:?}")]
+    ConnectionFailed {
+        uri: Uri,
+        #[source]
+        source: TransportError,
+    },
+    #[cfg(feature = "tls")]
+    #[error("Client: invalid TLS configuration.\n{source:?}")]
+    TlsError {
+        #[source]
+        source: TransportError,
+    },
+    #[error("Client: cannot do alter on DB")]
+    CannotAlter(#[source] Status),
+    #[error("Client: cannot login")]
+    CannotLogin(#[source] Status),
+    #[error("Client: cannot refresh login")]
+    CannotRefreshLogin(#[source] Status),
+    #[error("Client: cannot query")]
+    CannotQuery(#[source] Status),
+    #[error("Client: cannot mutate")]
+    CannotMutate(#[source] Status),
+    #[error("Client: cannot do request")]
+    CannotDoRequest(#[source] Status),
+    #[error("Client: cannot commit or abort")]
+    CannotCommitOrAbort(#[source] Status),
+    #[error("Client: cannot check version")]
+    CannotCheckVersion(#[source] Status),
+    #[error("Client: message of {size} bytes exceeds limit of {limit} bytes")]
+    MessageTooLarge { size: usize, limit: usize },
+    #[error("Client: client is shut down")]
+    ShutDown,
+    #[error("Client: invalid drop operation, {0}")]
+    InvalidDropOp(String),
+}
+
+impl Error {
+    ///
+    /// Return gRPC status returned by Dgraph, if error was caused by failed request.
+    ///
+    pub fn status(&self) -> Option<&Status> {
+        match self {
+            Error::CannotAlter(status)
+            | Error::CannotLogin(status)
+            | Error::CannotRefreshLogin(status)
+            | Error::CannotQuery(status)
+            | Error::CannotMutate(status)
+            | Error::CannotDoRequest(status)
+            | Error::CannotCommitOrAbort(status)
+            | Error::CannotCheckVersion(status) => Some(status),
+            _ => None,
+        }
+    }
 }
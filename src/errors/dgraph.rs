@@ -1,6 +1,18 @@
+use std::fmt::{Display, Formatter};
+
 use anyhow::Error as Failure;
 use thiserror::Error as Fail;
 
+use crate::errors::ClientError;
+#[cfg(feature = "debug-requests")]
+use crate::Request;
+use crate::Status;
+
+///
+/// Maximal number of characters of server message or query printed in error message.
+///
+const MAX_DISPLAY_LEN: usize = 200;
+
 ///
 /// Possible Dgraph errors
 ///
@@ -8,12 +20,189 @@ use thiserror::Error as Fail;
 pub enum Error {
     #[error("Dgraph: Txn start mismatch")]
     StartTsMismatch,
-    #[error("Dgraph: gRPC communication Error.")]
+    #[error("Dgraph: gRPC communication Error.{}", GrpcSummary(.0))]
     GrpcError(#[from] Failure),
+    #[error("Dgraph: Client error. {0}")]
+    ClientError(#[source] Failure),
+    #[error("Dgraph: Request was cancelled before response was received")]
+    Cancelled,
     #[error("Dgraph: Txn is empty")]
     EmptyTxn,
     #[error("Dgraph: Missing Txn context")]
     MissingTxnContext,
     #[error("Dgraph: Txn is already committed")]
     TxnCommitted,
+    #[error("Dgraph: Txn is inconsistent, because mutation was cancelled")]
+    InconsistentTxn,
+    #[error("Dgraph: Missing uid in query result")]
+    MissingUid,
+    #[error("Dgraph: Missing value of order predicate {0} in stream item")]
+    MissingCursor(String),
+    #[error("Dgraph: Invalid RDF on line {0}")]
+    InvalidRdf(usize),
+    #[error("Dgraph: Invalid predicate name {0:?}")]
+    InvalidPredicate(String),
+    #[error("Dgraph: Invalid vector component {0}")]
+    InvalidVector(f32),
+    #[error("Dgraph: Operation timed out")]
+    Timeout,
+    #[error("Dgraph: Upsert validation failed: {message}")]
+    UpsertValidation { message: String },
+    #[error("Dgraph: Failed to deserialize {raw_len}-byte response: {source}")]
+    JsonDeserialize {
+        raw_len: usize,
+        #[source]
+        source: serde_json::Error,
+    },
+}
+
+///
+/// Query and start timestamp of request which caused gRPC error.
+///
+/// Context is attached to source of `GrpcError` returned by queries and mutations of
+/// transactions and it can be obtained with `downcast_ref::<RequestContext>()`.
+///
+/// With `debug-requests` feature, context holds exact request sent to Dgraph, so failed request
+/// can be reproduced. Its debug output is appended to error message.
+///
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct RequestContext {
+    pub query: String,
+    pub start_ts: u64,
+    #[cfg(feature = "debug-requests")]
+    pub request: Option<Request>,
+}
+
+impl Display for RequestContext {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "start_ts: {}", self.start_ts)?;
+        if !self.query.is_empty() {
+            write!(f, ", query: {}", shorten(&self.query))?;
+        }
+        #[cfg(feature = "debug-requests")]
+        if let Some(request) = &self.request {
+            write!(f, ", request: {:?}", request)?;
+        }
+        Ok(())
+    }
+}
+
+///
+/// One line summary of gRPC status and request context of error.
+///
+struct GrpcSummary<'a>(&'a Failure);
+
+impl Display for GrpcSummary<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let status = self.0.chain().find_map(|err| {
+            err.downcast_ref::<ClientError>()
+                .and_then(ClientError::status)
+                .or_else(|| err.downcast_ref::<Status>())
+        });
+        if let Some(status) = status {
+            write!(
+                f,
+                " code: {:?}, message: {}",
+                status.code(),
+                shorten(status.message())
+            )?;
+        }
+        if let Some(context) = self.0.downcast_ref::<RequestContext>() {
+            write!(f, " ({})", context)?;
+        }
+        Ok(())
+    }
+}
+
+///
+/// Collapse whitespaces into single space and cut text into `MAX_DISPLAY_LEN` characters.
+///
+fn shorten(text: &str) -> String {
+    let text = text.split_whitespace().collect::<Vec<_>>().join(" ");
+    match text.char_indices().nth(MAX_DISPLAY_LEN) {
+        Some((end, _)) => format!("{}...", &text[..end]),
+        None => text,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn grpc_error_display() {
+        let query = format!(
+            "{{\n    q(func: has(name)) {{\n{}    }}\n}}",
+            "  name\n".repeat(100)
+        );
+        let err = Failure::from(ClientError::CannotQuery(Status::invalid_argument(
+            "  while parsing query:\n  Some variables are defined but not used\n",
+        )))
+        .context(RequestContext {
+            query,
+            start_ts: 42,
+            #[cfg(feature = "debug-requests")]
+            request: None,
+        });
+        let display = Error::GrpcError(err).to_string();
+        assert!(display.starts_with(
+            "Dgraph: gRPC communication Error. code: InvalidArgument, message: while parsing \
+             query: Some variables are defined but not used (start_ts: 42, query: { q(func: \
+             has(name)) { name name"
+        ));
+        assert!(display.ends_with("...)"));
+        assert!(!display.contains('\n'));
+    }
+
+    #[cfg(feature = "debug-requests")]
+    #[test]
+    fn grpc_error_display_with_request() {
+        let request = Request {
+            query: "{ q(func: has(name)) { name } }\n".to_string(),
+            start_ts: 42,
+            ..Default::default()
+        };
+        let err = Failure::from(ClientError::CannotQuery(Status::invalid_argument("lexer")))
+            .context(RequestContext {
+                query: request.query.clone(),
+                start_ts: request.start_ts,
+                request: Some(request),
+            });
+        let display = Error::GrpcError(err).to_string();
+        assert!(display.contains("request: Request { start_ts: 42"));
+        // trailing newline of sent query is visible
+        assert!(display.contains(r#"query: "{ q(func: has(name)) { name } }\n""#));
+        assert!(!display.contains('\n'));
+    }
+
+    #[test]
+    fn grpc_error_display_without_status() {
+        let err = Error::GrpcError(anyhow::anyhow!("unavailable"));
+        assert_eq!(err.to_string(), "Dgraph: gRPC communication Error.");
+    }
+
+    #[test]
+    fn std_error_source_chain() {
+        let err: Box<dyn std::error::Error + Send + Sync + 'static> = Box::new(Error::GrpcError(
+            ClientError::CannotQuery(Status::unavailable("down")).into(),
+        ));
+        let client = err.source().expect("Client error");
+        assert!(client.downcast_ref::<ClientError>().is_some());
+        assert_eq!(client.to_string(), "Client: cannot query");
+        let status = client.source().expect("Status");
+        assert_eq!(
+            status.downcast_ref::<Status>().map(Status::code),
+            Some(tonic::Code::Unavailable)
+        );
+    }
+
+    #[test]
+    fn client_error_source_chain() {
+        let err = Error::ClientError(ClientError::NoEndpointsDefined.into());
+        let source = std::error::Error::source(&err).expect("Client error");
+        assert!(matches!(
+            source.downcast_ref::<ClientError>(),
+            Some(ClientError::NoEndpointsDefined)
+        ));
+    }
 }
@@ -3,3 +3,4 @@ mod dgraph;
 
 pub use crate::errors::client::Error as ClientError;
 pub use crate::errors::dgraph::Error as DgraphError;
+pub use crate::errors::dgraph::RequestContext;
@@ -0,0 +1,114 @@
+use std::convert::Infallible;
+use std::sync::Arc;
+
+use hyper::body::Bytes;
+use prost::Message;
+use tokio::net::TcpListener;
+use tokio::task::JoinHandle;
+
+///
+/// gRPC call received by fake server.
+///
+pub(crate) struct FakeRequest {
+    // read only by tests of optional features
+    #[allow(dead_code)]
+    pub(crate) path: String,
+    #[allow(dead_code)]
+    pub(crate) headers: http::HeaderMap,
+    pub(crate) body: Bytes,
+}
+
+impl FakeRequest {
+    ///
+    /// Decode uncompressed message of call.
+    ///
+    pub(crate) fn message<M: Message + Default>(&self) -> M {
+        // skip compression flag and length prefix of gRPC frame
+        M::decode(&self.body[5..]).expect("gRPC message")
+    }
+}
+
+///
+/// Successful gRPC response of fake server.
+///
+pub(crate) struct FakeReply {
+    message: Vec<u8>,
+    trailers: http::HeaderMap,
+}
+
+impl FakeReply {
+    pub(crate) fn new<M: Message>(message: &M) -> Self {
+        let mut trailers = http::HeaderMap::new();
+        trailers.insert("grpc-status", "0".parse().unwrap());
+        Self {
+            message: message.encode_to_vec(),
+            trailers,
+        }
+    }
+
+    pub(crate) fn with_trailer(mut self, name: &'static str, value: &str) -> Self {
+        self.trailers.insert(name, value.parse().unwrap());
+        self
+    }
+
+    fn into_response(self) -> hyper::Response<hyper::Body> {
+        let mut frame = vec![0u8];
+        frame.extend_from_slice(&(self.message.len() as u32).to_be_bytes());
+        frame.extend_from_slice(&self.message);
+        let (mut sender, body) = hyper::Body::channel();
+        let trailers = self.trailers;
+        tokio::spawn(async move {
+            sender.send_data(frame.into()).await.unwrap();
+            sender.send_trailers(trailers).await.unwrap();
+        });
+        hyper::Response::builder()
+            .header("content-type", "application/grpc")
+            .body(body)
+            .unwrap()
+    }
+}
+
+///
+/// Start fake Dgraph on random local port. Return its endpoint and handle of server task.
+///
+/// See `serve_on`.
+///
+pub(crate) async fn serve<F>(handler: F) -> (String, JoinHandle<()>)
+where
+    F: Fn(FakeRequest) -> FakeReply + Send + Sync + 'static,
+{
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let addr = listener.local_addr().unwrap();
+    (format!("http://{}", addr), serve_on(listener, handler))
+}
+
+///
+/// Serve one connection of listener with fake Dgraph, which answers every gRPC call with reply
+/// of handler.
+///
+pub(crate) fn serve_on<F>(listener: TcpListener, handler: F) -> JoinHandle<()>
+where
+    F: Fn(FakeRequest) -> FakeReply + Send + Sync + 'static,
+{
+    let handler = Arc::new(handler);
+    tokio::spawn(async move {
+        let (stream, _) = listener.accept().await.unwrap();
+        let service = hyper::service::service_fn(move |request: hyper::Request<hyper::Body>| {
+            let handler = Arc::clone(&handler);
+            async move {
+                let (parts, body) = request.into_parts();
+                let body = hyper::body::to_bytes(body).await.unwrap();
+                let request = FakeRequest {
+                    path: parts.uri.path().to_string(),
+                    headers: parts.headers,
+                    body,
+                };
+                Ok::<_, Infallible>(handler(request).into_response())
+            }
+        });
+        let _ = hyper::server::conn::Http::new()
+            .http2_only(true)
+            .serve_connection(stream, service)
+            .await;
+    })
+}
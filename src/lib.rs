@@ -1,15 +1,27 @@
+#[cfg(feature = "capture-metadata")]
+pub use tonic::metadata::MetadataMap;
 pub use tonic::transport::{Certificate, Channel, ClientTlsConfig, Endpoint, Identity};
 pub use tonic::Status;
 
+pub use crate::api::value;
 #[cfg(feature = "dgraph-1-0")]
 pub use crate::api::Assigned;
 use crate::api::IDgraphClient;
-#[cfg(any(feature = "dgraph-1-1", feature = "dgraph-21-03"))]
-pub use crate::api::Metrics;
+#[cfg(any(
+    feature = "dgraph-1-1",
+    feature = "dgraph-21-03",
+    feature = "dgraph-24-02"
+))]
+pub use crate::api::{operation::DropOp, Metrics};
 pub use crate::api::{
-    Check, Latency, LoginRequest, Mutation, Operation, Payload, Request, Response, TxnContext,
+    Check, FacetValue, Facets, Latency, LoginRequest, Mutation, NQuad, NodeFacets, Operation,
+    Payload, PredicateBuilder, Request, Response, SchemaBuilder, TxnContext, TypeBuilder, Value,
     Version,
 };
+#[cfg(feature = "gzip")]
+pub use crate::client::Compression;
+#[cfg(feature = "metrics")]
+pub use crate::client::EndpointStats;
 #[cfg(feature = "acl")]
 pub use crate::client::{
     AclClient, AclClientType, LazyChannel, TxnAcl, TxnAclBestEffort, TxnAclMutated, TxnAclReadOnly,
@@ -19,7 +31,7 @@ pub use crate::client::{
     AclTlsClient, TxnAclTls, TxnAclTlsBestEffort, TxnAclTlsMutated, TxnAclTlsReadOnly,
 };
 pub use crate::client::{
-    Client, ClientVariant, EndpointConfig, Endpoints, Http, IClient, Txn, TxnBestEffort,
+    BulkStats, Client, ClientVariant, EndpointConfig, Endpoints, Http, IClient, Txn, TxnBestEffort,
     TxnMutated, TxnReadOnly,
 };
 #[cfg(feature = "slash-ql")]
@@ -28,20 +40,39 @@ pub use crate::client::{
 };
 #[cfg(feature = "tls")]
 pub use crate::client::{Tls, TlsClient, TxnTls, TxnTlsBestEffort, TxnTlsMutated, TxnTlsReadOnly};
-pub use crate::errors::{ClientError, DgraphError};
+pub use crate::errors::{ClientError, DgraphError, RequestContext};
+pub use crate::observer::{OpKind, RequestObserver};
+pub use crate::retry::{ExponentialBackoff, NoRetry, OutagePolicy, RetryPolicy};
+pub use crate::selector::{
+    EndpointSelector, FixedSelector, LeastOutstandingSelector, RandomSelector,
+};
+#[cfg(feature = "experimental")]
+pub use crate::stream::CancellableStream;
 pub use crate::txn::{
-    Mutate, MutationResponse, Query, TxnBestEffortType, TxnMutatedType, TxnReadOnlyType, TxnState,
-    TxnType, TxnVariant,
+    Committed, Mutate, MutationResponse, Query, ReadPool, TxnBestEffortType, TxnMutatedType,
+    TxnReadOnlyType, TxnState, TxnType, TxnVariant,
 };
+pub use crate::vars::ToDgraphVar;
+#[cfg(feature = "experimental")]
+pub use crate::watch::WatchEvent;
 
 mod api;
 mod client;
 mod errors;
+#[cfg(test)]
+mod fake_server;
+mod lifecycle;
+mod observer;
+mod retry;
+mod selector;
 #[cfg(feature = "experimental")]
 mod stream;
 mod stub;
 #[cfg(feature = "sync")]
 pub mod sync;
 mod txn;
+mod vars;
+#[cfg(feature = "experimental")]
+mod watch;
 
 pub type StdError = Box<dyn std::error::Error + Send + Sync + 'static>;
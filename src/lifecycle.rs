@@ -0,0 +1,219 @@
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex, Weak};
+
+use tokio::sync::Notify;
+
+use crate::TxnContext;
+
+type TxnSlot = Mutex<Option<TxnContext>>;
+
+///
+/// Shared state of client which allows its graceful shutdown.
+///
+/// Counts gRPC calls in flight, which can be cancelled, and holds weak references to contexts of open transactions with
+/// mutations, so they can be aborted when client is shut down.
+///
+#[derive(Debug, Default)]
+pub(crate) struct Lifecycle {
+    closed: AtomicBool,
+    cancelled: AtomicBool,
+    cancel: Notify,
+    in_flight: AtomicUsize,
+    drained: Notify,
+    txns: Mutex<Vec<Weak<TxnSlot>>>,
+}
+
+impl Lifecycle {
+    ///
+    /// Return true when client was shut down and no new call can be started.
+    ///
+    pub(crate) fn is_closed(&self) -> bool {
+        self.closed.load(Ordering::SeqCst)
+    }
+
+    ///
+    /// Reject all calls started from now on.
+    ///
+    pub(crate) fn close(&self) {
+        self.closed.store(true, Ordering::SeqCst);
+    }
+
+    ///
+    /// Cancel all calls in flight, they fail with `ShutDown` error.
+    ///
+    pub(crate) fn cancel(&self) {
+        self.cancelled.store(true, Ordering::SeqCst);
+        self.cancel.notify_waiters();
+    }
+
+    ///
+    /// Wait until calls in flight are cancelled.
+    ///
+    pub(crate) async fn cancelled(&self) {
+        // waiter is registered before check, so notification cannot be missed
+        let notified = self.cancel.notified();
+        if self.cancelled.load(Ordering::SeqCst) {
+            return;
+        }
+        notified.await;
+    }
+
+    ///
+    /// Count gRPC call in flight until returned guard is dropped.
+    ///
+    pub(crate) fn enter(self: &Arc<Self>) -> InFlight {
+        self.in_flight.fetch_add(1, Ordering::SeqCst);
+        InFlight(Arc::clone(self))
+    }
+
+    ///
+    /// Wait until there is no gRPC call in flight.
+    ///
+    pub(crate) async fn drained(&self) {
+        loop {
+            // waiter is registered before check, so notification cannot be missed
+            let notified = self.drained.notified();
+            if self.in_flight.load(Ordering::SeqCst) == 0 {
+                return;
+            }
+            notified.await;
+        }
+    }
+
+    ///
+    /// Start tracking of new transaction.
+    ///
+    pub(crate) fn track(self: &Arc<Self>) -> OpenTxn {
+        let slot = Arc::new(Mutex::new(None));
+        let mut txns = self.txns.lock().expect("Tracked txns");
+        txns.retain(|txn| txn.strong_count() > 0);
+        txns.push(Arc::downgrade(&slot));
+        OpenTxn {
+            lifecycle: Arc::clone(self),
+            slot,
+        }
+    }
+
+    ///
+    /// Take contexts of all live transactions which are not finished yet.
+    ///
+    pub(crate) fn take_open_txns(&self) -> Vec<TxnContext> {
+        self.txns
+            .lock()
+            .expect("Tracked txns")
+            .iter()
+            .filter_map(Weak::upgrade)
+            .filter_map(|slot| slot.lock().expect("Txn context").take())
+            .collect()
+    }
+}
+
+///
+/// Guard of one gRPC call in flight.
+///
+pub(crate) struct InFlight(Arc<Lifecycle>);
+
+impl Drop for InFlight {
+    fn drop(&mut self) {
+        if self.0.in_flight.fetch_sub(1, Ordering::SeqCst) == 1 {
+            self.0.drained.notify_waiters();
+        }
+    }
+}
+
+///
+/// Context of transaction tracked by client. Tracking ends when it is dropped.
+///
+#[derive(Debug)]
+pub(crate) struct OpenTxn {
+    lifecycle: Arc<Lifecycle>,
+    slot: Arc<TxnSlot>,
+}
+
+impl OpenTxn {
+    ///
+    /// Remember actual context of transaction with pending mutations.
+    ///
+    pub(crate) fn set(&self, context: &TxnContext) {
+        self.slot
+            .lock()
+            .expect("Txn context")
+            .replace(context.to_owned());
+    }
+
+    ///
+    /// Transaction is finished, so there is nothing to abort.
+    ///
+    pub(crate) fn clear(&self) {
+        self.slot.lock().expect("Txn context").take();
+    }
+}
+
+impl Clone for OpenTxn {
+    fn clone(&self) -> Self {
+        self.lifecycle.track()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+    use std::time::Duration;
+
+    use super::Lifecycle;
+    use crate::TxnContext;
+
+    #[tokio::test]
+    async fn drained() {
+        let lifecycle = Arc::new(Lifecycle::default());
+        lifecycle.drained().await;
+        let in_flight = lifecycle.enter();
+        let waiting = tokio::time::timeout(Duration::from_millis(50), lifecycle.drained()).await;
+        assert!(waiting.is_err());
+        tokio::spawn(async move {
+            tokio::time::sleep(Duration::from_millis(20)).await;
+            drop(in_flight);
+        });
+        tokio::time::timeout(Duration::from_secs(1), lifecycle.drained())
+            .await
+            .expect("Drained");
+    }
+
+    #[tokio::test]
+    async fn cancelled() {
+        let lifecycle = Arc::new(Lifecycle::default());
+        let waiting = tokio::time::timeout(Duration::from_millis(20), lifecycle.cancelled()).await;
+        assert!(waiting.is_err());
+        let waiter = {
+            let lifecycle = Arc::clone(&lifecycle);
+            tokio::spawn(async move { lifecycle.cancelled().await })
+        };
+        tokio::task::yield_now().await;
+        lifecycle.cancel();
+        tokio::time::timeout(Duration::from_secs(1), waiter)
+            .await
+            .expect("Cancelled")
+            .unwrap();
+        lifecycle.cancelled().await;
+    }
+
+    #[test]
+    fn open_txns() {
+        let lifecycle = Arc::new(Lifecycle::default());
+        let context = TxnContext {
+            start_ts: 1,
+            ..Default::default()
+        };
+        let first = lifecycle.track();
+        let second = lifecycle.track();
+        let dropped = lifecycle.track();
+        let _cloned = first.clone();
+        first.set(&context);
+        second.set(&context);
+        second.clear();
+        dropped.set(&context);
+        drop(dropped);
+        assert_eq!(lifecycle.take_open_txns(), vec![context]);
+        assert!(lifecycle.take_open_txns().is_empty());
+    }
+}
@@ -0,0 +1,43 @@
+use std::fmt::Debug;
+use std::time::Duration;
+
+use crate::errors::DgraphError;
+
+///
+/// Kind of gRPC operation which is reported to observer.
+///
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum OpKind {
+    Login,
+    Query,
+    Mutate,
+    Alter,
+    CommitOrAbort,
+    CheckVersion,
+}
+
+///
+/// Observe every gRPC request sent by client and all its transactions.
+///
+/// Observer is attached to client with `with_observer` and it can be used to collect own
+/// metrics, e.g. with Prometheus or statsd. Every attempt of retried operation is reported.
+///
+pub trait RequestObserver: Send + Sync + Debug {
+    ///
+    /// Called before request is sent.
+    ///
+    fn on_request(&self, op: OpKind);
+
+    ///
+    /// Called when response or error is received.
+    ///
+    /// # Arguments
+    ///
+    /// - `op`: kind of finished operation
+    /// - `latency`: duration of request
+    /// - `result`: error of request, errors returned by Dgraph or gRPC transport are passed
+    ///   wrapped as `GrpcError`, other errors of client as `ClientError` and request which was
+    ///   dropped before response was received as `Cancelled`
+    ///
+    fn on_response(&self, op: OpKind, latency: Duration, result: Result<(), &DgraphError>);
+}
@@ -0,0 +1,338 @@
+use std::fmt::Debug;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use anyhow::Result;
+use futures::future::BoxFuture;
+
+use crate::errors::{ClientError, DgraphError};
+
+///
+/// Decide if failed operation should be repeated.
+///
+/// Policy is attached to client with `with_retry_policy` and it is consulted by `query`, `mutate`,
+/// `alter` and `commit` operations of client and all its transactions. Mutations with
+/// `commit_now` are never repeated, because data can be already committed when only response
+/// was lost.
+///
+pub trait RetryPolicy: Send + Sync + Debug {
+    ///
+    /// Return delay before next attempt or `None` if error should be returned to caller.
+    ///
+    /// # Arguments
+    ///
+    /// - `attempt`: number of failed attempts, first failure has number 1
+    /// - `error`: error of last attempt
+    ///
+    fn should_retry(&self, attempt: usize, error: &DgraphError) -> Option<Duration>;
+}
+
+///
+/// Never repeat failed operation.
+///
+#[derive(Clone, Copy, Debug, Default)]
+pub struct NoRetry;
+
+impl RetryPolicy for NoRetry {
+    fn should_retry(&self, _attempt: usize, _error: &DgraphError) -> Option<Duration> {
+        None
+    }
+}
+
+///
+/// Repeat failed operation with exponentially growing delay.
+///
+/// Delay before n-th retry is `base * 2^(n-1)`, but at most `max`. Operation is executed at most
+/// `max_attempts` times.
+///
+/// Only transient errors are repeated, i.e. endpoint cannot be reached or it replied with
+/// `Unavailable` status, so request was not processed. Other errors are returned immediately.
+///
+#[derive(Clone, Copy, Debug)]
+pub struct ExponentialBackoff {
+    pub max_attempts: usize,
+    pub base: Duration,
+    pub max: Duration,
+}
+
+impl Default for ExponentialBackoff {
+    fn default() -> Self {
+        Self {
+            max_attempts: 3,
+            base: Duration::from_millis(100),
+            max: Duration::from_secs(5),
+        }
+    }
+}
+
+impl RetryPolicy for ExponentialBackoff {
+    fn should_retry(&self, attempt: usize, error: &DgraphError) -> Option<Duration> {
+        if attempt >= self.max_attempts || !is_transient(error) {
+            return None;
+        }
+        let exponent = attempt.saturating_sub(1).min(31) as u32;
+        let delay = self
+            .base
+            .checked_mul(1 << exponent)
+            .unwrap_or(self.max)
+            .min(self.max);
+        Some(delay)
+    }
+}
+
+///
+/// Decide what happens when endpoint of request is not reachable.
+///
+/// Policy is attached to client with `with_outage_policy`. It is consulted after retry policy
+/// gave up, so it handles outage of whole cluster (e.g. during leader election) and not errors
+/// of single request.
+///
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Default)]
+pub enum OutagePolicy {
+    ///
+    /// Return error immediately.
+    ///
+    #[default]
+    Fail,
+    ///
+    /// Repeat request on other endpoints of client until it succeeds or given time elapses.
+    ///
+    RetryFor(Duration),
+}
+
+///
+/// Return `true` if request failed, because its endpoint cannot be reached.
+///
+pub(crate) fn is_outage(err: &anyhow::Error) -> bool {
+    match err.downcast_ref::<ClientError>() {
+        Some(ClientError::ConnectionFailed { .. }) => true,
+        Some(err) => err
+            .status()
+            .is_some_and(|status| status.code() == tonic::Code::Unavailable),
+        None => false,
+    }
+}
+
+///
+/// Return `true` if error is worth repeating, because request was not processed by Dgraph.
+///
+fn is_transient(err: &DgraphError) -> bool {
+    match err {
+        DgraphError::GrpcError(err) => is_outage(err),
+        _ => false,
+    }
+}
+
+///
+/// Return `true` if request was canceled before it was sent, because connection was closed.
+///
+fn is_unsent(err: &anyhow::Error) -> bool {
+    err.chain().any(|cause| {
+        cause
+            .downcast_ref::<hyper::Error>()
+            .is_some_and(hyper::Error::is_canceled)
+    })
+}
+
+///
+/// Execute `op` on `subject` until it succeeds or policy stops retrying.
+///
+/// Errors are passed into policy wrapped as `GrpcError`, but returned error is the original one.
+///
+/// Request which was not sent, because cached connection was closed (e.g. after server restart),
+/// is repeated once immediately without policy. Channel reconnects itself, so repeated request
+/// is sent over new connection.
+///
+/// Operation is not repeated when next attempt would start after `deadline`.
+///
+pub(crate) async fn retry<S, T, F>(
+    policy: Option<&Arc<dyn RetryPolicy>>,
+    deadline: Option<Instant>,
+    subject: &mut S,
+    mut op: F,
+) -> Result<T>
+where
+    F: FnMut(&mut S) -> BoxFuture<'_, Result<T>>,
+{
+    let mut attempt = 0;
+    let mut resent = false;
+    loop {
+        attempt += 1;
+        let err = match op(subject).await {
+            Ok(value) => return Ok(value),
+            Err(err) => err,
+        };
+        if !resent && is_unsent(&err) {
+            resent = true;
+            attempt -= 1;
+            continue;
+        }
+        let policy = match policy {
+            Some(policy) => policy,
+            None => return Err(err),
+        };
+        let err = DgraphError::GrpcError(err);
+        let delay = policy
+            .should_retry(attempt, &err)
+            .filter(|delay| deadline.is_none_or(|deadline| Instant::now() + *delay < deadline));
+        match delay {
+            Some(delay) => tokio::time::sleep(delay).await,
+            None => match err {
+                DgraphError::GrpcError(err) => return Err(err),
+                err => return Err(err.into()),
+            },
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use futures::FutureExt;
+
+    use super::*;
+
+    struct FlakyStub {
+        failures: usize,
+        calls: usize,
+        code: tonic::Code,
+    }
+
+    impl Default for FlakyStub {
+        fn default() -> Self {
+            Self {
+                failures: 0,
+                calls: 0,
+                code: tonic::Code::Unavailable,
+            }
+        }
+    }
+
+    impl FlakyStub {
+        fn call(&mut self) -> BoxFuture<'_, Result<usize>> {
+            async move {
+                self.calls += 1;
+                if self.calls <= self.failures {
+                    return Err(
+                        ClientError::CannotQuery(tonic::Status::new(self.code, "failed")).into(),
+                    );
+                }
+                Ok(self.calls)
+            }
+            .boxed()
+        }
+    }
+
+    fn backoff() -> Arc<dyn RetryPolicy> {
+        Arc::new(ExponentialBackoff {
+            max_attempts: 3,
+            base: Duration::from_millis(1),
+            max: Duration::from_millis(2),
+        })
+    }
+
+    #[tokio::test]
+    async fn retry_until_success() {
+        let mut stub = FlakyStub {
+            failures: 2,
+            ..Default::default()
+        };
+        let policy = backoff();
+        let result = retry(Some(&policy), None, &mut stub, |stub| stub.call()).await;
+        assert_eq!(result.expect("Success"), 3);
+    }
+
+    #[tokio::test]
+    async fn retry_exhausted() {
+        let mut stub = FlakyStub {
+            failures: 3,
+            ..Default::default()
+        };
+        let policy = backoff();
+        let result = retry(Some(&policy), None, &mut stub, |stub| stub.call()).await;
+        let err = result.expect_err("Failure");
+        assert!(is_outage(&err));
+        assert_eq!(stub.calls, 3);
+    }
+
+    #[tokio::test]
+    async fn no_retry_of_permanent_error() {
+        let mut stub = FlakyStub {
+            failures: 1,
+            code: tonic::Code::InvalidArgument,
+            ..Default::default()
+        };
+        let policy = backoff();
+        let result = retry(Some(&policy), None, &mut stub, |stub| stub.call()).await;
+        let err = result.expect_err("Failure");
+        assert!(matches!(
+            err.downcast_ref::<ClientError>(),
+            Some(ClientError::CannotQuery(status)) if status.code() == tonic::Code::InvalidArgument
+        ));
+        assert_eq!(stub.calls, 1);
+    }
+
+    #[tokio::test]
+    async fn no_retry_after_deadline() {
+        let mut stub = FlakyStub {
+            failures: 2,
+            ..Default::default()
+        };
+        let policy = backoff();
+        let deadline = Some(Instant::now());
+        let result = retry(Some(&policy), deadline, &mut stub, |stub| stub.call()).await;
+        assert!(result.is_err());
+        assert_eq!(stub.calls, 1);
+    }
+
+    #[tokio::test]
+    async fn no_retry() {
+        let mut stub = FlakyStub {
+            failures: 1,
+            ..Default::default()
+        };
+        let policy: Arc<dyn RetryPolicy> = Arc::new(NoRetry);
+        let result = retry(Some(&policy), None, &mut stub, |stub| stub.call()).await;
+        assert!(result.is_err());
+        assert_eq!(stub.calls, 1);
+    }
+
+    #[test]
+    fn outage() {
+        let unavailable = ClientError::CannotQuery(tonic::Status::unavailable("down"));
+        assert!(is_outage(&unavailable.into()));
+        let invalid = ClientError::CannotQuery(tonic::Status::invalid_argument("query"));
+        assert!(!is_outage(&invalid.into()));
+        assert!(!is_outage(&anyhow::anyhow!("unavailable")));
+        assert_eq!(OutagePolicy::default(), OutagePolicy::Fail);
+    }
+
+    #[test]
+    fn exponential_backoff_delay() {
+        let policy = ExponentialBackoff {
+            max_attempts: 10,
+            base: Duration::from_millis(100),
+            max: Duration::from_millis(500),
+        };
+        let err = DgraphError::GrpcError(
+            ClientError::CannotQuery(tonic::Status::unavailable("down")).into(),
+        );
+        assert_eq!(
+            policy.should_retry(1, &err),
+            Some(Duration::from_millis(100))
+        );
+        assert_eq!(
+            policy.should_retry(3, &err),
+            Some(Duration::from_millis(400))
+        );
+        assert_eq!(
+            policy.should_retry(4, &err),
+            Some(Duration::from_millis(500))
+        );
+        assert_eq!(policy.should_retry(10, &err), None);
+        assert_eq!(policy.should_retry(1, &DgraphError::EmptyTxn), None);
+        let deadline = DgraphError::GrpcError(
+            ClientError::CannotQuery(tonic::Status::deadline_exceeded("slow")).into(),
+        );
+        assert_eq!(policy.should_retry(1, &deadline), None);
+    }
+}
@@ -0,0 +1,145 @@
+use std::fmt::Debug;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+
+use rand::Rng;
+
+///
+/// Decide which endpoint of client is used by new transaction or operation.
+///
+/// Selector is attached to client with `with_endpoint_selector`. Without it, endpoints are
+/// selected randomly.
+///
+pub trait EndpointSelector: Send + Sync + Debug {
+    ///
+    /// Return index of endpoint which should be used.
+    ///
+    /// Index out of range is wrapped around number of endpoints.
+    ///
+    /// # Arguments
+    ///
+    /// - `count`: number of endpoints of client, it is never zero
+    ///
+    fn select(&self, count: usize) -> usize;
+
+    ///
+    /// Return counter of requests in flight to endpoint with given index.
+    ///
+    /// Client increments counter before each call made through endpoint and decrements it when
+    /// call is finished. Selectors which do not need load of endpoints return `None`.
+    ///
+    /// # Arguments
+    ///
+    /// - `index`: index of endpoint, it is always lower than number of endpoints
+    ///
+    fn outstanding(&self, _index: usize) -> Option<Arc<AtomicUsize>> {
+        None
+    }
+}
+
+///
+/// Select random endpoint.
+///
+#[derive(Clone, Copy, Debug, Default)]
+pub struct RandomSelector;
+
+impl EndpointSelector for RandomSelector {
+    fn select(&self, count: usize) -> usize {
+        rand::thread_rng().gen_range(0..count)
+    }
+}
+
+///
+/// Always select endpoint with given index. Useful for deterministic tests.
+///
+#[derive(Clone, Copy, Debug, Default)]
+pub struct FixedSelector(pub usize);
+
+impl EndpointSelector for FixedSelector {
+    fn select(&self, _count: usize) -> usize {
+        self.0
+    }
+}
+
+///
+/// Select endpoint with the fewest requests in flight.
+///
+/// Slow endpoints accumulate outstanding requests, so new requests are routed to faster ones.
+/// Ties are broken randomly. One selector should be attached to one client only, because counts
+/// are tracked by index of endpoint.
+///
+#[derive(Debug, Default)]
+pub struct LeastOutstandingSelector {
+    outstanding: Mutex<Vec<Arc<AtomicUsize>>>,
+}
+
+impl LeastOutstandingSelector {
+    ///
+    /// Return counters of first `count` endpoints, missing counters are created.
+    ///
+    fn counters(&self, count: usize) -> Vec<Arc<AtomicUsize>> {
+        let mut outstanding = self.outstanding.lock().expect("Outstanding requests");
+        if outstanding.len() < count {
+            outstanding.resize_with(count, Default::default);
+        }
+        outstanding[..count].to_vec()
+    }
+}
+
+impl EndpointSelector for LeastOutstandingSelector {
+    fn select(&self, count: usize) -> usize {
+        let counters = self.counters(count);
+        // scan starts at random offset, so idle endpoints are used evenly
+        let offset = rand::thread_rng().gen_range(0..count);
+        (0..count)
+            .map(|i| (i + offset) % count)
+            .min_by_key(|&index| counters[index].load(Ordering::SeqCst))
+            .unwrap_or_default()
+    }
+
+    fn outstanding(&self, index: usize) -> Option<Arc<AtomicUsize>> {
+        self.counters(index + 1).pop()
+    }
+}
+
+///
+/// Guard of one request in flight to endpoint of selector.
+///
+pub(crate) struct Outstanding(Arc<AtomicUsize>);
+
+impl Outstanding {
+    pub(crate) fn enter(counter: &Arc<AtomicUsize>) -> Self {
+        counter.fetch_add(1, Ordering::SeqCst);
+        Self(Arc::clone(counter))
+    }
+}
+
+impl Drop for Outstanding {
+    fn drop(&mut self) {
+        self.0.fetch_sub(1, Ordering::SeqCst);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{EndpointSelector, LeastOutstandingSelector, Outstanding};
+
+    #[test]
+    fn least_outstanding() {
+        let selector = LeastOutstandingSelector::default();
+        let first = selector.outstanding(0).expect("Counter");
+        let second = selector.outstanding(1).expect("Counter");
+        let _busy = (Outstanding::enter(&first), Outstanding::enter(&first));
+        let pending = Outstanding::enter(&second);
+        assert_eq!(selector.select(3), 2);
+        assert_eq!(selector.select(2), 1);
+        drop(pending);
+        assert_eq!(selector.select(2), 1);
+        let third = selector.outstanding(2).expect("Counter");
+        let _pending: Vec<_> = [&second, &third, &second, &third, &second, &third]
+            .iter()
+            .map(|counter| Outstanding::enter(counter))
+            .collect();
+        assert_eq!(selector.select(3), 0);
+    }
+}
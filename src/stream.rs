@@ -1,13 +1,18 @@
 use std::collections::HashMap;
 use std::hash::Hash;
+use std::pin::Pin;
+use std::sync::Arc;
+use std::task::{Context, Poll};
 
 use anyhow::Result;
 use async_stream::try_stream;
-use futures::stream::Stream;
+use futures::stream::{FusedStream, Stream};
 use serde::de::DeserializeOwned;
 use serde::Deserialize;
+use serde_json::Value;
 
 use crate::client::ILazyClient;
+use crate::errors::DgraphError;
 use crate::{Query, TxnReadOnlyType};
 
 #[derive(Deserialize)]
@@ -23,6 +28,104 @@ impl<T: DeserializeOwned> Default for Chunk<T> {
     }
 }
 
+///
+/// Stream which owns transaction and releases it deterministically.
+///
+/// Transaction is dropped as soon as stream is exhausted, yields an error or `cancel` is called,
+/// not only when stream itself is dropped.
+///
+/// Adapters like `take(n)` stop polling stream after last requested item, so stream cannot notice
+/// it is not needed anymore. Call `cancel` through adapter, e.g. `take.get_mut().cancel()`, or
+/// drop adapter to release transaction in that case.
+///
+pub struct CancellableStream<S> {
+    inner: Option<Pin<Box<S>>>,
+}
+
+impl<S> CancellableStream<S> {
+    fn new(inner: S) -> Self {
+        Self {
+            inner: Some(Box::pin(inner)),
+        }
+    }
+
+    ///
+    /// Stop stream and release underlying transaction immediately.
+    ///
+    /// Every next poll of stream returns `None`.
+    ///
+    pub fn cancel(&mut self) {
+        self.inner.take();
+    }
+
+    ///
+    /// Return `true` if underlying transaction was already released.
+    ///
+    pub fn is_released(&self) -> bool {
+        self.inner.is_none()
+    }
+}
+
+impl<S, T, E> Stream for CancellableStream<S>
+where
+    S: Stream<Item = Result<T, E>>,
+{
+    type Item = S::Item;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let inner = match self.inner.as_mut() {
+            Some(inner) => inner,
+            None => return Poll::Ready(None),
+        };
+        let item = inner.as_mut().poll_next(cx);
+        if let Poll::Ready(None | Some(Err(_))) = item {
+            self.cancel();
+        }
+        item
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        match self.inner.as_ref() {
+            Some(inner) => inner.size_hint(),
+            None => (0, Some(0)),
+        }
+    }
+}
+
+impl<S, T, E> FusedStream for CancellableStream<S>
+where
+    S: Stream<Item = Result<T, E>>,
+{
+    fn is_terminated(&self) -> bool {
+        self.is_released()
+    }
+}
+
+///
+/// Format value of order predicate from item as `$after` variable.
+///
+fn cursor(item: &Value, order_predicate: &str) -> Option<String> {
+    match item.get(order_predicate)? {
+        Value::String(value) => Some(value.to_owned()),
+        Value::Number(value) => Some(value.to_string()),
+        Value::Bool(value) => Some(value.to_string()),
+        _ => None,
+    }
+}
+
+///
+/// Deserialize one item of query block into T.
+///
+/// Error is reported as `JsonDeserialize` with length of serialized item, same as for whole
+/// response.
+///
+pub(crate) fn from_item<T: DeserializeOwned>(item: &Value) -> Result<T, DgraphError> {
+    T::deserialize(item).map_err(|source| DgraphError::JsonDeserialize {
+        raw_len: item.to_string().len(),
+        source,
+    })
+}
+
 impl<C: ILazyClient> TxnReadOnlyType<C> {
     async fn fetch_chunk<Q, T>(&mut self, query: Q, vars: HashMap<String, String>) -> Result<Vec<T>>
     where
@@ -43,6 +146,7 @@ impl<C: ILazyClient> TxnReadOnlyType<C> {
     ///
     /// Stream contains deserialized items returned from query.
     /// Stream item is Ok(T) if **items** query data can be serialized into Vec<T>.
+    /// Transaction is released when stream is exhausted, fails or is cancelled.
     ///
     /// # Arguments
     ///
@@ -100,7 +204,11 @@ impl<C: ILazyClient> TxnReadOnlyType<C> {
     /// }
     /// ```
     ///
-    pub fn into_stream<Q, T>(self, query: Q, first: usize) -> impl Stream<Item = Result<T>>
+    pub fn into_stream<Q, T>(
+        self,
+        query: Q,
+        first: usize,
+    ) -> CancellableStream<impl Stream<Item = Result<T>>>
     where
         Q: Into<String> + Send + Sync,
         T: Unpin + DeserializeOwned,
@@ -118,6 +226,7 @@ impl<C: ILazyClient> TxnReadOnlyType<C> {
     ///
     /// Stream contains deserialized items returned from query.
     /// Stream item is Ok(T) if **items** query data can be serialized into Vec<T>.
+    /// Transaction is released when stream is exhausted, fails or is cancelled.
     ///
     /// # Arguments
     ///
@@ -179,11 +288,11 @@ impl<C: ILazyClient> TxnReadOnlyType<C> {
     /// ```
     ///
     pub fn into_stream_with_vars<Q, T, K, V>(
-        mut self,
+        self,
         query: Q,
         vars: HashMap<K, V>,
         first: usize,
-    ) -> impl Stream<Item = Result<T>>
+    ) -> CancellableStream<impl Stream<Item = Result<T>>>
     where
         Q: Into<String> + Send + Sync,
         T: Unpin + DeserializeOwned,
@@ -199,8 +308,108 @@ impl<C: ILazyClient> TxnReadOnlyType<C> {
             tmp
         });
         vars.insert(String::from("$first"), format!("{}", first));
-        let query = query.into();
-        try_stream! {
+        self.paginate(query.into(), vars, first, None)
+    }
+
+    ///
+    /// Readonly transaction is transformed into async stream, which reports progress of export.
+    ///
+    /// Works like `into_stream`, but `on_page` is called after every fetched non-empty page with
+    /// total number of items fetched so far, e.g. to print "exported N so far". Callback is
+    /// called before items of page are yielded.
+    ///
+    /// # Arguments
+    ///
+    /// - `query`: GraphQL+- query segment.
+    /// - `first`:  number of items returned in one chunk
+    /// - `on_page`: progress callback
+    ///
+    /// # Errors
+    ///
+    /// * gRPC error
+    /// * If transaction is not initialized properly, return `EmptyTxn` error.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use std::sync::Arc;
+    /// use anyhow::Result;
+    /// use futures::pin_mut;
+    /// use futures::stream::StreamExt;
+    /// use dgraph_tonic::Client;
+    /// use serde::Deserialize;
+    /// #[cfg(feature = "acl")]
+    /// use dgraph_tonic::{AclClientType, LazyChannel};
+    ///
+    /// #[cfg(not(feature = "acl"))]
+    /// async fn client() -> Client {
+    ///     Client::new("http://127.0.0.1:19080").expect("Dgraph client")
+    /// }
+    ///
+    /// #[cfg(feature = "acl")]
+    /// async fn client() -> AclClientType<LazyChannel> {
+    ///     let default = Client::new("http://127.0.0.1:19080").unwrap();
+    ///     default.login("groot", "password").await.expect("Acl client")
+    /// }
+    ///
+    /// #[derive(Deserialize, Debug)]
+    /// struct Person {
+    ///   uid: String,
+    ///   name: String,
+    /// }
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     let query = r#"query stream($first: string, $offset: string) {
+    ///         items(func: has(name), first: $first, offset: $offset) {
+    ///             uid
+    ///             name
+    ///         }
+    ///     }"#;
+    ///
+    ///   let client = client().await;
+    ///   let on_page = Arc::new(|exported| println!("exported {} so far", exported));
+    ///   let stream = client
+    ///       .new_read_only_txn()
+    ///       .into_stream_with_progress(query, 100, on_page);
+    ///   pin_mut!(stream);
+    ///   let persons: Vec<Result<Person>> = stream.collect().await;
+    /// }
+    /// ```
+    ///
+    pub fn into_stream_with_progress<Q, T>(
+        self,
+        query: Q,
+        first: usize,
+        on_page: Arc<dyn Fn(usize) + Send + Sync>,
+    ) -> CancellableStream<impl Stream<Item = Result<T>>>
+    where
+        Q: Into<String> + Send + Sync,
+        T: Unpin + DeserializeOwned,
+    {
+        assert_ne!(
+            first, 0,
+            "First attribute for stream must not be eq to zero"
+        );
+        let mut vars = HashMap::new();
+        vars.insert(String::from("$first"), format!("{}", first));
+        self.paginate(query.into(), vars, first, Some(on_page))
+    }
+
+    ///
+    /// Fetch pages of query by offset until page shorter than `first` is returned.
+    ///
+    fn paginate<T>(
+        mut self,
+        query: String,
+        mut vars: HashMap<String, String>,
+        first: usize,
+        on_page: Option<Arc<dyn Fn(usize) + Send + Sync>>,
+    ) -> CancellableStream<impl Stream<Item = Result<T>>>
+    where
+        T: Unpin + DeserializeOwned,
+    {
+        CancellableStream::new(try_stream! {
             let mut offset = 0;
             loop {
                 vars.insert(String::from("$offset"), format!("{}", offset));
@@ -211,6 +420,9 @@ impl<C: ILazyClient> TxnReadOnlyType<C> {
                     break;
                 };
                 let chunk_len = chunk.len();
+                if let Some(on_page) = &on_page {
+                    on_page(offset + chunk_len);
+                }
                 for item in chunk {
                     offset += 1;
                     yield item
@@ -219,24 +431,147 @@ impl<C: ILazyClient> TxnReadOnlyType<C> {
                     break;
                 }
             }
-        }
+        })
+    }
+
+    ///
+    /// Readonly transaction is transformed into async stream which is paginated by cursor.
+    ///
+    /// Input `query` must accept **$first: string, $after: string** arguments, order items by
+    /// `order_predicate` and return only items with value greater than `$after`, e.g. with
+    /// `orderasc: name` and `@filter(gt(name, $after))`. Use `after: $after` for ordering by
+    /// `uid`. `$after` is not sent for first page, so it must have default value in query, which
+    /// is smaller than all values, e.g. `$after: string = ""`. Stream items must be returned in
+    /// query block named **items** and they must contain `order_predicate`.
+    ///
+    /// Unlike `into_stream`, which skips or repeats items when data are mutated between pages
+    /// of offset pagination, every next page starts right after last returned item. Values of
+    /// `order_predicate` must be unique, because items with same value as last item of page are
+    /// skipped.
+    ///
+    /// # Return
+    ///
+    /// Stream contains deserialized items returned from query.
+    /// Stream item is Ok(T) if **items** query data can be serialized into Vec<T>.
+    /// Transaction is released when stream is exhausted, fails or is cancelled.
+    ///
+    /// # Arguments
+    ///
+    /// - `query`: GraphQL+- query segment.
+    /// - `order_predicate`: predicate by which items are ordered
+    /// - `first`:  number of items returned in one chunk
+    ///
+    /// # Errors
+    ///
+    /// * gRPC error
+    /// * If transaction is not initialized properly, return `EmptyTxn` error.
+    /// * `MissingCursor`: last item of page does not contain value of `order_predicate`
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use anyhow::Result;
+    /// use futures::pin_mut;
+    /// use futures::stream::StreamExt;
+    /// use dgraph_tonic::Client;
+    /// use serde::Deserialize;
+    /// #[cfg(feature = "acl")]
+    /// use dgraph_tonic::{AclClientType, LazyChannel};
+    ///
+    /// #[cfg(not(feature = "acl"))]
+    /// async fn client() -> Client {
+    ///     Client::new("http://127.0.0.1:19080").expect("Dgraph client")
+    /// }
+    ///
+    /// #[cfg(feature = "acl")]
+    /// async fn client() -> AclClientType<LazyChannel> {
+    ///     let default = Client::new("http://127.0.0.1:19080").unwrap();
+    ///     default.login("groot", "password").await.expect("Acl client")
+    /// }
+    ///
+    /// #[derive(Deserialize, Debug)]
+    /// struct Person {
+    ///   uid: String,
+    ///   name: String,
+    /// }
+    ///
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     let query = r#"query stream($first: string, $after: string = "") {
+    ///         items(func: has(name), orderasc: name, first: $first) @filter(gt(name, $after)) {
+    ///             uid
+    ///             name
+    ///         }
+    ///     }"#;
+    ///
+    ///   let client = client().await;
+    ///   let stream = client.new_read_only_txn().into_stream_after(query, "name", 100);
+    ///   pin_mut!(stream);
+    ///   let people: Vec<Result<Person>> = stream.collect().await;
+    /// }
+    /// ```
+    ///
+    pub fn into_stream_after<Q, T>(
+        mut self,
+        query: Q,
+        order_predicate: &str,
+        first: usize,
+    ) -> CancellableStream<impl Stream<Item = Result<T>>>
+    where
+        Q: Into<String> + Send + Sync,
+        T: Unpin + DeserializeOwned,
+    {
+        assert_ne!(
+            first, 0,
+            "First attribute for stream must not be eq to zero"
+        );
+        let mut vars = HashMap::new();
+        vars.insert(String::from("$first"), format!("{}", first));
+        let query = query.into();
+        let order_predicate = order_predicate.to_owned();
+        CancellableStream::new(try_stream! {
+            loop {
+                let chunk: Vec<Value> = self
+                    .fetch_chunk(query.to_owned(), vars.to_owned())
+                    .await?;
+                let chunk_len = chunk.len();
+                if let Some(last) = chunk.last() {
+                    let after = cursor(last, &order_predicate)
+                        .ok_or_else(|| DgraphError::MissingCursor(order_predicate.to_owned()))?;
+                    vars.insert(String::from("$after"), after);
+                }
+                for item in chunk {
+                    yield from_item(&item)?
+                }
+                if chunk_len < first {
+                    break;
+                }
+            }
+        })
     }
 }
 
 #[cfg(test)]
 mod tests {
     use std::collections::HashMap;
+    use std::sync::atomic::{AtomicBool, Ordering};
+    use std::sync::{Arc, Mutex};
 
     use anyhow::Result;
     use futures::pin_mut;
-    use futures::stream::StreamExt;
+    use futures::stream::{self, FusedStream, Stream, StreamExt};
     use serde_derive::{Deserialize, Serialize};
+    use serde_json::json;
 
     use crate::client::Client;
     #[cfg(feature = "acl")]
     use crate::client::{AclClientType, LazyChannel};
     use crate::{Mutate, Mutation};
 
+    use super::{from_item, CancellableStream};
+    use crate::errors::DgraphError;
+
     #[cfg(not(feature = "acl"))]
     async fn client() -> Client {
         Client::new("http://127.0.0.1:19080").unwrap()
@@ -304,6 +639,41 @@ mod tests {
         assert!(cars.iter().all(|car| car.is_ok()))
     }
 
+    #[tokio::test]
+    async fn stream_with_progress() {
+        let client = client().await;
+        client.drop_all().await.expect("Data not dropped");
+        let data: Vec<Car> = ["A", "B", "C"]
+            .iter()
+            .map(|color| Car {
+                uid: format!("_:{}", color),
+                color: color.to_string(),
+            })
+            .collect();
+        let mut mu = Mutation::new();
+        mu.set_set_json(&data).expect("Invalid JSON");
+        let txn = client.new_mutated_txn();
+        txn.mutate_and_commit_now(mu).await.expect("Mutated");
+        let progress = Arc::new(Mutex::new(Vec::new()));
+        let recorder = Arc::clone(&progress);
+        let stream = client.new_read_only_txn().into_stream_with_progress(
+            r#"
+            query stream($first: string, $offset: string) {
+                items(func: has(color), first: $first, offset: $offset) {
+                    uid
+                    color
+                }
+            }
+        "#,
+            2,
+            Arc::new(move |exported| recorder.lock().unwrap().push(exported)),
+        );
+        pin_mut!(stream);
+        let cars: Vec<Result<Car>> = stream.collect().await;
+        assert_eq!(cars.len(), 3);
+        assert_eq!(*progress.lock().unwrap(), vec![2, 3]);
+    }
+
     #[tokio::test]
     async fn stream_with_vars() {
         let client = client().await;
@@ -351,6 +721,68 @@ mod tests {
         assert!(cars.iter().all(|car| car.is_ok()))
     }
 
+    #[tokio::test]
+    async fn stream_after() {
+        let client = client().await;
+        client.drop_all().await.expect("Data not dropped");
+        client
+            .set_schema("color: string @index(exact) .")
+            .await
+            .expect("Schema is not updated");
+        let txn = client.new_mutated_txn();
+        let data: Vec<Car> = ["C", "A", "B"]
+            .iter()
+            .map(|color| Car {
+                uid: format!("_:{}", color),
+                color: color.to_string(),
+            })
+            .collect();
+        let mut mu = Mutation::new();
+        mu.set_set_json(&data).expect("Invalid JSON");
+        let response = txn.mutate_and_commit_now(mu).await;
+        assert!(response.is_ok());
+        let stream = client.new_read_only_txn().into_stream_after(
+            r#"
+            query stream($first: string, $after: string = "") {
+                items(func: has(color), orderasc: color, first: $first) @filter(gt(color, $after)) {
+                    uid
+                    color
+                }
+            }
+        "#,
+            "color",
+            2,
+        );
+        pin_mut!(stream);
+        let cars: Vec<Car> = stream.map(|car| car.expect("Car")).collect().await;
+        let colors: Vec<&str> = cars.iter().map(|car| car.color.as_str()).collect();
+        assert_eq!(colors, vec!["A", "B", "C"]);
+    }
+
+    #[test]
+    fn cursor() {
+        let item = json!({"uid": "0x1", "name": "Alice", "age": 25, "cool": true, "tags": []});
+        assert_eq!(super::cursor(&item, "uid"), Some("0x1".to_string()));
+        assert_eq!(super::cursor(&item, "name"), Some("Alice".to_string()));
+        assert_eq!(super::cursor(&item, "age"), Some("25".to_string()));
+        assert_eq!(super::cursor(&item, "cool"), Some("true".to_string()));
+        assert_eq!(super::cursor(&item, "tags"), None);
+        assert_eq!(super::cursor(&item, "missing"), None);
+    }
+
+    #[test]
+    fn item_deserialize_error() {
+        let item = json!({"uid": "0x1", "color": 1});
+        let car: Result<Car, DgraphError> = from_item(&json!({"uid": "0x1", "color": "A"}));
+        assert_eq!(car.expect("Car").color, "A");
+        match from_item::<Car>(&item) {
+            Err(DgraphError::JsonDeserialize { raw_len, .. }) => {
+                assert_eq!(raw_len, item.to_string().len())
+            }
+            other => panic!("unexpected result {:?}", other),
+        }
+    }
+
     #[tokio::test]
     async fn invalid_data_in_stream() {
         let client = client().await;
@@ -394,4 +826,68 @@ mod tests {
         assert_eq!(cars.len(), 1);
         assert!(cars.iter().all(|car| car.is_err()))
     }
+
+    struct Guard(Arc<AtomicBool>);
+
+    impl Drop for Guard {
+        fn drop(&mut self) {
+            self.0.store(true, Ordering::SeqCst);
+        }
+    }
+
+    fn guarded_stream(
+        released: Arc<AtomicBool>,
+    ) -> CancellableStream<impl Stream<Item = Result<u8, u8>>> {
+        let guard = Guard(released);
+        CancellableStream::new(
+            stream::iter(vec![Ok(1), Ok(2), Err(3), Ok(4)]).map(move |item| {
+                let _ = &guard;
+                item
+            }),
+        )
+    }
+
+    #[tokio::test]
+    async fn cancel_stream() {
+        let released = Arc::new(AtomicBool::new(false));
+        let mut stream = guarded_stream(Arc::clone(&released));
+        assert_eq!(stream.next().await, Some(Ok(1)));
+        assert!(!released.load(Ordering::SeqCst));
+        stream.cancel();
+        assert!(released.load(Ordering::SeqCst));
+        assert!(stream.is_released());
+        assert_eq!(stream.next().await, None);
+    }
+
+    #[tokio::test]
+    async fn release_exhausted_stream() {
+        let released = Arc::new(AtomicBool::new(false));
+        let mut stream = guarded_stream(Arc::clone(&released));
+        while stream.next().await.is_some() {}
+        assert!(released.load(Ordering::SeqCst));
+        assert!(stream.is_terminated());
+    }
+
+    #[tokio::test]
+    async fn release_failed_stream() {
+        let released = Arc::new(AtomicBool::new(false));
+        let mut stream = guarded_stream(Arc::clone(&released));
+        assert_eq!(stream.next().await, Some(Ok(1)));
+        assert_eq!(stream.next().await, Some(Ok(2)));
+        assert!(!released.load(Ordering::SeqCst));
+        assert_eq!(stream.next().await, Some(Err(3)));
+        assert!(released.load(Ordering::SeqCst));
+        assert_eq!(stream.next().await, None);
+    }
+
+    #[tokio::test]
+    async fn release_taken_stream() {
+        let released = Arc::new(AtomicBool::new(false));
+        let mut taken = guarded_stream(Arc::clone(&released)).take(1);
+        assert_eq!(taken.next().await, Some(Ok(1)));
+        assert_eq!(taken.next().await, None);
+        assert!(!released.load(Ordering::SeqCst));
+        taken.get_mut().cancel();
+        assert!(released.load(Ordering::SeqCst));
+    }
 }
@@ -1,10 +1,34 @@
+use std::fmt::{Debug, Formatter};
+use std::future::Future;
+use std::sync::atomic::AtomicUsize;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
 use anyhow::Result;
 use async_trait::async_trait;
+use futures::future::BoxFuture;
+use prost::Message;
+#[cfg(feature = "capture-metadata")]
+use tonic::metadata::MetadataMap;
 use tonic::Request;
-use tracing::trace;
+#[cfg(feature = "capture-metadata")]
+use tonic::{Response, Status};
+#[cfg(feature = "tracing")]
 use tracing_attributes::instrument;
 
+#[cfg(feature = "metrics")]
+use http::Uri;
+
+#[cfg(feature = "metrics")]
+use crate::client::metrics::EndpointsStats;
+#[cfg(feature = "gzip")]
+use crate::client::Compression;
 use crate::client::{DgraphClient, ILazyClient};
+use crate::errors::DgraphError;
+use crate::lifecycle::{InFlight, Lifecycle, OpenTxn};
+use crate::observer::{OpKind, RequestObserver};
+use crate::retry::{is_outage, retry, OutagePolicy, RetryPolicy};
+use crate::selector::Outstanding;
 #[cfg(feature = "dgraph-1-0")]
 use crate::{Assigned, Mutation};
 use crate::{
@@ -12,147 +36,916 @@ use crate::{
     Response as DgraphResponse, TxnContext, Version,
 };
 
+///
+/// Delay before next round of requests, when all endpoints are unreachable.
+///
+const OUTAGE_RETRY_DELAY: Duration = Duration::from_millis(200);
+
+///
+/// Source of ambient deadline which is applied to every gRPC call.
+///
+#[derive(Clone)]
+pub(crate) struct DeadlineSource(Arc<dyn Fn() -> Option<Instant> + Send + Sync>);
+
+impl DeadlineSource {
+    pub(crate) fn new<F>(source: F) -> Self
+    where
+        F: Fn() -> Option<Instant> + Send + Sync + 'static,
+    {
+        Self(Arc::new(source))
+    }
+
+    fn deadline(&self) -> Option<Instant> {
+        (self.0)()
+    }
+}
+
+impl Debug for DeadlineSource {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_tuple("DeadlineSource").finish()
+    }
+}
+
+type InterceptorFn = dyn Fn(&mut Request<()>) + Send + Sync;
+
+///
+/// User defined function which is called for every gRPC request before it is sent.
+///
+#[derive(Clone)]
+pub(crate) struct RequestInterceptor(Arc<InterceptorFn>);
+
+impl RequestInterceptor {
+    pub(crate) fn new<F>(interceptor: F) -> Self
+    where
+        F: Fn(&mut Request<()>) + Send + Sync + 'static,
+    {
+        Self(Arc::new(interceptor))
+    }
+
+    fn intercept(&self, request: &mut Request<()>) {
+        (self.0)(request)
+    }
+}
+
+impl Debug for RequestInterceptor {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_tuple("RequestInterceptor").finish()
+    }
+}
+
+///
+/// Measure one gRPC call and report it into stats and observer.
+///
+/// Call which is dropped before it is finished, e.g. because its future was cancelled, is
+/// reported to observer as `Cancelled`.
+///
+struct CallTimer {
+    op: OpKind,
+    start: Instant,
+    finished: bool,
+    observer: Option<Arc<dyn RequestObserver>>,
+    #[cfg(feature = "metrics")]
+    stats: Option<(Arc<EndpointsStats>, Uri)>,
+    _in_flight: Option<InFlight>,
+    _outstanding: Option<Outstanding>,
+}
+
+impl CallTimer {
+    fn finish<T>(mut self, result: Result<T>) -> Result<T> {
+        self.finished = true;
+        let latency = self.start.elapsed();
+        #[cfg(feature = "metrics")]
+        if let Some((stats, uri)) = &self.stats {
+            stats.record(uri, latency, result.is_ok());
+        }
+        #[cfg(feature = "tracing")]
+        if let Err(err) = &result {
+            let code = err
+                .downcast_ref::<ClientError>()
+                .and_then(ClientError::status)
+                .map(|status| status.code());
+            tracing::error!(?code, ?latency, error = %err, "gRPC call failed");
+        }
+        let observer = match &self.observer {
+            Some(observer) => observer,
+            None => return result,
+        };
+        match result {
+            Ok(value) => {
+                observer.on_response(self.op, latency, Ok(()));
+                Ok(value)
+            }
+            Err(err) => {
+                if let Some(dgraph) = err.downcast_ref::<DgraphError>() {
+                    observer.on_response(self.op, latency, Err(dgraph));
+                    return Err(err);
+                }
+                let err = if is_grpc(&err) {
+                    DgraphError::GrpcError(err)
+                } else {
+                    DgraphError::ClientError(err)
+                };
+                observer.on_response(self.op, latency, Err(&err));
+                match err {
+                    DgraphError::GrpcError(err) | DgraphError::ClientError(err) => Err(err),
+                    err => Err(err.into()),
+                }
+            }
+        }
+    }
+}
+
+impl Drop for CallTimer {
+    fn drop(&mut self) {
+        if self.finished {
+            return;
+        }
+        if let Some(observer) = &self.observer {
+            observer.on_response(self.op, self.start.elapsed(), Err(&DgraphError::Cancelled));
+        }
+    }
+}
+
+///
+/// Execute call until it finishes or lifecycle cancels all calls in flight.
+///
+async fn cancellable<T, F>(lifecycle: Option<Arc<Lifecycle>>, call: F) -> Result<T>
+where
+    F: Future<Output = Result<T>>,
+{
+    let lifecycle = match lifecycle {
+        Some(lifecycle) => lifecycle,
+        None => return call.await,
+    };
+    tokio::select! {
+        result = call => result,
+        _ = lifecycle.cancelled() => Err(ClientError::ShutDown.into()),
+    }
+}
+
+///
+/// Return `true` if error was returned by Dgraph or by gRPC transport.
+///
+fn is_grpc(err: &anyhow::Error) -> bool {
+    err.downcast_ref::<ClientError>().is_some_and(|err| {
+        err.status().is_some() || matches!(err, ClientError::ConnectionFailed { .. })
+    })
+}
+
 ///
 /// Hold channel connection do Dgraph and implement calls for Dgraph API operations.
 ///
 #[derive(Clone, Debug)]
 pub struct Stub<C: ILazyClient> {
     client: C,
+    deadline: Option<Instant>,
+    deadline_source: Option<DeadlineSource>,
+    retry_policy: Option<Arc<dyn RetryPolicy>>,
+    outage_policy: OutagePolicy,
+    endpoints: Arc<[C]>,
+    interceptors: Vec<RequestInterceptor>,
+    max_message_size: Option<usize>,
+    observer: Option<Arc<dyn RequestObserver>>,
+    lifecycle: Option<Arc<Lifecycle>>,
+    outstanding: Option<Arc<AtomicUsize>>,
+    #[cfg(feature = "gzip")]
+    compression: Compression,
+    #[cfg(feature = "gzip")]
+    compression_threshold: Option<usize>,
+    #[cfg(feature = "metrics")]
+    stats: Option<Arc<EndpointsStats>>,
+    #[cfg(feature = "capture-metadata")]
+    metadata: Option<MetadataMap>,
 }
 
 impl<C: ILazyClient> Stub<C> {
     pub fn new(client: C) -> Self {
-        Self { client }
+        Self {
+            client,
+            deadline: None,
+            deadline_source: None,
+            retry_policy: None,
+            outage_policy: OutagePolicy::Fail,
+            endpoints: Vec::new().into(),
+            interceptors: Vec::new(),
+            max_message_size: None,
+            observer: None,
+            lifecycle: None,
+            outstanding: None,
+            #[cfg(feature = "gzip")]
+            compression: Compression::None,
+            #[cfg(feature = "gzip")]
+            compression_threshold: None,
+            #[cfg(feature = "metrics")]
+            stats: None,
+            #[cfg(feature = "capture-metadata")]
+            metadata: None,
+        }
+    }
+
+    ///
+    /// Every request created by stub will have timeout according to deadline from source.
+    ///
+    pub(crate) fn with_deadline_source(mut self, deadline_source: Option<DeadlineSource>) -> Self {
+        self.deadline_source = deadline_source;
+        self
+    }
+
+    ///
+    /// Every request created by stub will have timeout according to fixed deadline.
+    ///
+    /// Earlier of this deadline and deadline from source is used.
+    ///
+    pub(crate) fn with_deadline(mut self, deadline: Option<Instant>) -> Self {
+        self.deadline = deadline;
+        self
+    }
+
+    ///
+    /// Return `Timeout` error when fixed deadline of stub is already exceeded.
+    ///
+    pub(crate) fn check_deadline(&self) -> Result<(), DgraphError> {
+        match self.deadline {
+            Some(deadline) if deadline <= Instant::now() => Err(DgraphError::Timeout),
+            _ => Ok(()),
+        }
+    }
+
+    ///
+    /// Calls executed with `retry` will be repeated according to policy.
+    ///
+    pub(crate) fn with_retry_policy(mut self, retry_policy: Option<Arc<dyn RetryPolicy>>) -> Self {
+        self.retry_policy = retry_policy;
+        self
+    }
+
+    ///
+    /// Unreachable endpoint will be replaced by other endpoints according to outage policy.
+    ///
+    pub(crate) fn with_outage_policy(mut self, policy: OutagePolicy, endpoints: Arc<[C]>) -> Self {
+        self.outage_policy = policy;
+        self.endpoints = endpoints;
+        self
+    }
+
+    ///
+    /// Every request created by stub will be passed through interceptors in given order.
+    ///
+    pub(crate) fn with_interceptors(mut self, interceptors: Vec<RequestInterceptor>) -> Self {
+        self.interceptors = interceptors;
+        self
+    }
+
+    ///
+    /// Messages checked with `check_message_size` cannot be bigger than limit. gRPC client of
+    /// stub encodes and decodes messages up to limit.
+    ///
+    pub(crate) fn with_max_message_size(mut self, max_message_size: Option<usize>) -> Self {
+        self.max_message_size = max_message_size;
+        self
+    }
+
+    ///
+    /// Return `MessageTooLarge` error if encoded message exceeds limit of stub.
+    ///
+    pub(crate) fn check_message_size<M: Message>(&self, message: &M) -> Result<()> {
+        if let Some(limit) = self.max_message_size {
+            let size = message.encoded_len();
+            if size > limit {
+                return Err(ClientError::MessageTooLarge { size, limit }.into());
+            }
+        }
+        Ok(())
+    }
+
+    ///
+    /// Requests and responses of stub will be compressed.
+    ///
+    #[cfg(feature = "gzip")]
+    pub(crate) fn with_compression(mut self, compression: Compression) -> Self {
+        self.compression = compression;
+        self
+    }
+
+    ///
+    /// Mutations of stub bigger than threshold will be compressed with gzip.
+    ///
+    #[cfg(feature = "gzip")]
+    pub(crate) fn with_compression_threshold(mut self, threshold: Option<usize>) -> Self {
+        self.compression_threshold = threshold;
+        self
+    }
+
+    ///
+    /// Return true when encoded message exceeds compression threshold of stub.
+    ///
+    #[cfg(feature = "gzip")]
+    fn exceeds_compression_threshold<M: Message>(&self, message: &M) -> bool {
+        self.compression_threshold
+            .is_some_and(|threshold| message.encoded_len() > threshold)
+    }
+
+    ///
+    /// Execute operation without retry and outage policy. Operation is repeated only when it was
+    /// not sent at all, so it is safe for requests which must not be applied twice.
+    ///
+    pub(crate) async fn once<T, F>(&mut self, op: F) -> Result<T>
+    where
+        F: FnMut(&mut Self) -> BoxFuture<'_, Result<T>>,
+    {
+        let lifecycle = self.lifecycle.clone();
+        let deadline = self.deadline;
+        cancellable(lifecycle, retry(None, deadline, self, op)).await
+    }
+
+    ///
+    /// Execute operation and repeat it while retry policy allows it.
+    ///
+    /// When endpoint is unreachable, operation is repeated on other endpoints according to
+    /// outage policy. After every round over all endpoints, stub waits a moment.
+    /// Operation is cancelled with `ShutDown` error, when client is shut down and its timeout
+    /// elapses.
+    ///
+    pub(crate) async fn retry<T, F>(&mut self, op: F) -> Result<T>
+    where
+        F: FnMut(&mut Self) -> BoxFuture<'_, Result<T>>,
+    {
+        let lifecycle = self.lifecycle.clone();
+        cancellable(lifecycle, self.repeat(op)).await
+    }
+
+    async fn repeat<T, F>(&mut self, mut op: F) -> Result<T>
+    where
+        F: FnMut(&mut Self) -> BoxFuture<'_, Result<T>>,
+    {
+        let retry_policy = self.retry_policy.clone();
+        let deadline = match self.outage_policy {
+            OutagePolicy::RetryFor(window) if !self.endpoints.is_empty() => Instant::now() + window,
+            _ => return retry(retry_policy.as_ref(), self.deadline, self, op).await,
+        };
+        // endpoints are rotated starting from the one which failed first
+        let endpoints = Arc::clone(&self.endpoints);
+        let first = endpoints
+            .iter()
+            .position(|endpoint| endpoint.uri() == self.client.uri())
+            .unwrap_or(0);
+        let mut failed = 0;
+        loop {
+            let err = match retry(retry_policy.as_ref(), self.deadline, self, &mut op).await {
+                Ok(value) => return Ok(value),
+                Err(err) => err,
+            };
+            let now = Instant::now();
+            if now >= deadline || !is_outage(&err) || self.check_deadline().is_err() {
+                return Err(err);
+            }
+            failed += 1;
+            if failed % endpoints.len() == 0 {
+                tokio::time::sleep(OUTAGE_RETRY_DELAY.min(deadline - now)).await;
+            }
+            self.client = endpoints[(first + failed) % endpoints.len()].clone();
+            // requests in flight are counted for selected endpoint only
+            self.outstanding = None;
+        }
+    }
+
+    ///
+    /// Every call of stub will be recorded into endpoint stats.
+    ///
+    #[cfg(feature = "metrics")]
+    pub(crate) fn with_stats(mut self, stats: Arc<EndpointsStats>) -> Self {
+        self.stats = Some(stats);
+        self
+    }
+
+    ///
+    /// Every call of stub will be reported to observer.
+    ///
+    pub(crate) fn with_observer(mut self, observer: Option<Arc<dyn RequestObserver>>) -> Self {
+        self.observer = observer;
+        self
+    }
+
+    ///
+    /// Calls of stub will be counted in flight and rejected after shutdown of lifecycle.
+    ///
+    pub(crate) fn with_lifecycle(mut self, lifecycle: Option<Arc<Lifecycle>>) -> Self {
+        self.lifecycle = lifecycle;
+        self
+    }
+
+    ///
+    /// Calls of stub will be counted in counter of requests in flight to its endpoint.
+    ///
+    pub(crate) fn with_outstanding(mut self, outstanding: Option<Arc<AtomicUsize>>) -> Self {
+        self.outstanding = outstanding;
+        self
+    }
+
+    ///
+    /// Start tracking of transaction, which is aborted on shutdown of lifecycle.
+    ///
+    pub(crate) fn track_txn(&self) -> Option<OpenTxn> {
+        self.lifecycle.as_ref().map(Lifecycle::track)
+    }
+
+    ///
+    /// Start measuring of one gRPC call.
+    ///
+    fn timer(&self, op: OpKind) -> CallTimer {
+        if let Some(observer) = &self.observer {
+            observer.on_request(op);
+        }
+        CallTimer {
+            op,
+            start: Instant::now(),
+            finished: false,
+            observer: self.observer.clone(),
+            #[cfg(feature = "metrics")]
+            stats: self
+                .stats
+                .as_ref()
+                .map(|stats| (Arc::clone(stats), self.client.uri().to_owned())),
+            _in_flight: self.lifecycle.as_ref().map(Lifecycle::enter),
+            _outstanding: self.outstanding.as_ref().map(Outstanding::enter),
+        }
+    }
+
+    ///
+    /// Remember headers and trailers of response or failed call.
+    ///
+    #[cfg(feature = "capture-metadata")]
+    fn capture_metadata<T>(&mut self, response: &Result<Response<T>, Status>) {
+        let metadata = match response {
+            Ok(response) => response.metadata(),
+            Err(status) => status.metadata(),
+        };
+        self.metadata = Some(metadata.to_owned());
+    }
+
+    ///
+    /// Return metadata of last query, mutation or commit call of stub.
+    ///
+    #[cfg(feature = "capture-metadata")]
+    pub(crate) fn last_metadata(&self) -> Option<&MetadataMap> {
+        self.metadata.as_ref()
+    }
+
+    ///
+    /// Return gRPC client with message size and compression settings of stub.
+    ///
+    async fn grpc_client(&mut self) -> Result<DgraphClient> {
+        if self
+            .lifecycle
+            .as_ref()
+            .is_some_and(|lifecycle| lifecycle.is_closed())
+        {
+            return Err(ClientError::ShutDown.into());
+        }
+        let mut client = self.client.client().await?.to_owned();
+        if let Some(bytes) = self.max_message_size {
+            client = client.with_max_message_size(bytes);
+        }
+        #[cfg(feature = "gzip")]
+        let client = client.with_compression(self.compression);
+        Ok(client)
+    }
+
+    ///
+    /// Wrap message into gRPC request with all settings of stub.
+    ///
+    fn request<T>(&self, message: T) -> Request<T> {
+        let mut request = Request::new(message);
+        let source_deadline = self
+            .deadline_source
+            .as_ref()
+            .and_then(|source| source.deadline());
+        let deadline = match (self.deadline, source_deadline) {
+            (Some(deadline), Some(source_deadline)) => Some(deadline.min(source_deadline)),
+            (deadline, source_deadline) => deadline.or(source_deadline),
+        };
+        if let Some(deadline) = deadline {
+            request.set_timeout(deadline.saturating_duration_since(Instant::now()));
+        }
+        if self.interceptors.is_empty() {
+            return request;
+        }
+        let (metadata, extensions, message) = request.into_parts();
+        let mut bare = Request::from_parts(metadata, extensions, ());
+        for interceptor in self.interceptors.iter() {
+            interceptor.intercept(&mut bare);
+        }
+        let (metadata, extensions, _) = bare.into_parts();
+        Request::from_parts(metadata, extensions, message)
     }
 }
 
 #[async_trait]
 impl<C: ILazyClient> IDgraphClient for Stub<C> {
-    #[instrument(skip(self))]
+    #[cfg_attr(
+        feature = "tracing",
+        instrument(skip_all, fields(op = "login", endpoint = %self.client.uri()))
+    )]
     async fn login(&mut self, login: LoginRequest) -> Result<DgraphResponse> {
-        trace!("login");
-        let request = Request::new(login);
-        let client = self.client.client().await?;
-        let response = match client {
+        let request = self.request(login);
+        let timer = self.timer(OpKind::Login);
+        let mut client = match self.grpc_client().await {
+            Ok(client) => client,
+            Err(err) => return timer.finish(Err(err)),
+        };
+        let response = match &mut client {
             DgraphClient::Default { client } => client.login(request).await,
             #[cfg(feature = "acl")]
             DgraphClient::Acl { client } => client.login(request).await,
             #[cfg(feature = "slash-ql")]
             DgraphClient::SlashQl { client } => client.login(request).await,
         };
-        match response {
+        let response = match response {
             Ok(response) => Ok(response.into_inner()),
             Err(status) => Err(ClientError::CannotLogin(status).into()),
-        }
+        };
+        timer.finish(response)
     }
 
-    #[instrument(skip(self))]
+    #[cfg_attr(
+        feature = "tracing",
+        instrument(skip_all, fields(op = "query", endpoint = %self.client.uri(), start_ts = query.start_ts))
+    )]
     async fn query(&mut self, query: DgraphRequest) -> Result<DgraphResponse> {
-        trace!("query");
-        let request = Request::new(query);
-        let client = self.client.client().await?;
-        let response = match client {
+        let request = self.request(query);
+        let timer = self.timer(OpKind::Query);
+        let mut client = match self.grpc_client().await {
+            Ok(client) => client,
+            Err(err) => return timer.finish(Err(err)),
+        };
+        let response = match &mut client {
             DgraphClient::Default { client } => client.query(request).await,
             #[cfg(feature = "acl")]
             DgraphClient::Acl { client } => client.query(request).await,
             #[cfg(feature = "slash-ql")]
             DgraphClient::SlashQl { client } => client.query(request).await,
         };
-        match response {
+        #[cfg(feature = "capture-metadata")]
+        self.capture_metadata(&response);
+        let response = match response {
             Ok(response) => Ok(response.into_inner()),
             Err(status) => Err(ClientError::CannotQuery(status).into()),
-        }
+        };
+        timer.finish(response)
     }
 
-    #[instrument(skip(self))]
+    #[cfg_attr(
+        feature = "tracing",
+        instrument(skip_all, fields(op = "mutate", endpoint = %self.client.uri(), start_ts = mu.start_ts))
+    )]
     #[cfg(feature = "dgraph-1-0")]
     async fn mutate(&mut self, mu: Mutation) -> Result<Assigned> {
-        trace!("mutate");
-        let request = Request::new(mu);
-        let client = self.client.client().await?;
-        let response = match client {
+        #[cfg(feature = "gzip")]
+        let compress = self.exceeds_compression_threshold(&mu);
+        let request = self.request(mu);
+        let timer = self.timer(OpKind::Mutate);
+        let mut client = match self.grpc_client().await {
+            Ok(client) => client,
+            Err(err) => return timer.finish(Err(err)),
+        };
+        #[cfg(feature = "gzip")]
+        if compress {
+            client = client.with_compression(Compression::Gzip);
+        }
+        let response = match &mut client {
             DgraphClient::Default { client } => client.mutate(request).await,
             #[cfg(feature = "acl")]
             DgraphClient::Acl { client } => client.mutate(request).await,
             #[cfg(feature = "slash-ql")]
             DgraphClient::SlashQl { client } => client.mutate(request).await,
         };
-        match response {
+        #[cfg(feature = "capture-metadata")]
+        self.capture_metadata(&response);
+        let response = match response {
             Ok(response) => Ok(response.into_inner()),
             Err(status) => Err(ClientError::CannotMutate(status).into()),
-        }
+        };
+        timer.finish(response)
     }
 
-    #[instrument(skip(self))]
-    #[cfg(any(feature = "dgraph-1-1", feature = "dgraph-21-03"))]
+    #[cfg_attr(
+        feature = "tracing",
+        instrument(skip_all, fields(op = "mutate", endpoint = %self.client.uri(), start_ts = req.start_ts))
+    )]
+    #[cfg(any(
+        feature = "dgraph-1-1",
+        feature = "dgraph-21-03",
+        feature = "dgraph-24-02"
+    ))]
     async fn do_request(&mut self, req: DgraphRequest) -> Result<DgraphResponse> {
-        trace!("do_request");
-        let request = Request::new(req);
-        let client = self.client.client().await?;
-        let response = match client {
+        #[cfg(feature = "gzip")]
+        let compress = self.exceeds_compression_threshold(&req);
+        let request = self.request(req);
+        let timer = self.timer(OpKind::Mutate);
+        let mut client = match self.grpc_client().await {
+            Ok(client) => client,
+            Err(err) => return timer.finish(Err(err)),
+        };
+        #[cfg(feature = "gzip")]
+        if compress {
+            client = client.with_compression(Compression::Gzip);
+        }
+        let response = match &mut client {
             DgraphClient::Default { client } => client.query(request).await,
             #[cfg(feature = "acl")]
             DgraphClient::Acl { client } => client.query(request).await,
             #[cfg(feature = "slash-ql")]
             DgraphClient::SlashQl { client } => client.query(request).await,
         };
-        match response {
+        #[cfg(feature = "capture-metadata")]
+        self.capture_metadata(&response);
+        let response = match response {
             Ok(response) => Ok(response.into_inner()),
             Err(status) => Err(ClientError::CannotDoRequest(status).into()),
-        }
+        };
+        timer.finish(response)
     }
 
-    #[instrument(skip(self))]
+    #[cfg_attr(
+        feature = "tracing",
+        instrument(skip_all, fields(op = "alter", endpoint = %self.client.uri()))
+    )]
     async fn alter(&mut self, op: Operation) -> Result<Payload> {
-        trace!("alter");
-        let request = Request::new(op);
-        let client = self.client.client().await?;
-        let response = match client {
+        let request = self.request(op);
+        let timer = self.timer(OpKind::Alter);
+        let mut client = match self.grpc_client().await {
+            Ok(client) => client,
+            Err(err) => return timer.finish(Err(err)),
+        };
+        let response = match &mut client {
             DgraphClient::Default { client } => client.alter(request).await,
             #[cfg(feature = "acl")]
             DgraphClient::Acl { client } => client.alter(request).await,
             #[cfg(feature = "slash-ql")]
             DgraphClient::SlashQl { client } => client.alter(request).await,
         };
-        match response {
+        let response = match response {
             Ok(response) => Ok(response.into_inner()),
             Err(status) => Err(ClientError::CannotAlter(status).into()),
-        }
+        };
+        timer.finish(response)
     }
 
-    #[instrument(skip(self))]
+    #[cfg_attr(
+        feature = "tracing",
+        instrument(skip_all, fields(op = "commit_or_abort", endpoint = %self.client.uri(), start_ts = txn.start_ts))
+    )]
     async fn commit_or_abort(&mut self, txn: TxnContext) -> Result<TxnContext> {
-        trace!("commit_or_abort");
-        let request = Request::new(txn);
-        let client = self.client.client().await?;
-        let response = match client {
+        let request = self.request(txn);
+        let timer = self.timer(OpKind::CommitOrAbort);
+        let mut client = match self.grpc_client().await {
+            Ok(client) => client,
+            Err(err) => return timer.finish(Err(err)),
+        };
+        let response = match &mut client {
             DgraphClient::Default { client } => client.commit_or_abort(request).await,
             #[cfg(feature = "acl")]
             DgraphClient::Acl { client } => client.commit_or_abort(request).await,
             #[cfg(feature = "slash-ql")]
             DgraphClient::SlashQl { client } => client.commit_or_abort(request).await,
         };
-        match response {
+        #[cfg(feature = "capture-metadata")]
+        self.capture_metadata(&response);
+        let response = match response {
             Ok(response) => Ok(response.into_inner()),
             Err(status) => Err(ClientError::CannotCommitOrAbort(status).into()),
-        }
+        };
+        timer.finish(response)
     }
 
-    #[instrument(skip(self))]
+    #[cfg_attr(
+        feature = "tracing",
+        instrument(skip_all, fields(op = "check_version", endpoint = %self.client.uri()))
+    )]
     async fn check_version(&mut self) -> Result<Version> {
-        trace!("check_version");
-        let request = Request::new(Check {});
-        let client = self.client.client().await?;
-        let response = match client {
+        let request = self.request(Check {});
+        let timer = self.timer(OpKind::CheckVersion);
+        let mut client = match self.grpc_client().await {
+            Ok(client) => client,
+            Err(err) => return timer.finish(Err(err)),
+        };
+        let response = match &mut client {
             DgraphClient::Default { client } => client.check_version(request).await,
             #[cfg(feature = "acl")]
             DgraphClient::Acl { client } => client.check_version(request).await,
             #[cfg(feature = "slash-ql")]
             DgraphClient::SlashQl { client } => client.check_version(request).await,
         };
-        match response {
+        let response = match response {
             Ok(response) => Ok(response.into_inner()),
             Err(status) => Err(ClientError::CannotCheckVersion(status).into()),
+        };
+        timer.finish(response)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::{Arc, Mutex};
+    use std::time::Duration;
+
+    use crate::{Check, Client, ClientError, DgraphError, OpKind, RequestObserver};
+
+    #[derive(Debug, Default)]
+    struct RecordingObserver {
+        events: Mutex<Vec<(OpKind, Option<&'static str>)>>,
+    }
+
+    impl RequestObserver for RecordingObserver {
+        fn on_request(&self, op: OpKind) {
+            self.events.lock().unwrap().push((op, None));
+        }
+
+        fn on_response(&self, op: OpKind, _latency: Duration, result: Result<(), &DgraphError>) {
+            let outcome = match result {
+                Ok(()) => "ok",
+                Err(DgraphError::GrpcError(err)) => {
+                    assert!(err.downcast_ref::<ClientError>().is_some());
+                    "grpc"
+                }
+                Err(DgraphError::ClientError(_)) => "client",
+                Err(DgraphError::Cancelled) => "cancelled",
+                Err(err) => panic!("Unexpected error {:?}", err),
+            };
+            self.events.lock().unwrap().push((op, Some(outcome)));
+        }
+    }
+
+    #[test]
+    fn interceptors() {
+        let client = Client::new("http://127.0.0.1:19080")
+            .unwrap()
+            .with_interceptor(|request| {
+                request
+                    .metadata_mut()
+                    .insert("traceparent", "first".parse().unwrap());
+            })
+            .with_interceptor(|request| {
+                let first = request.metadata().get("traceparent").cloned().unwrap();
+                request.metadata_mut().insert("previous", first);
+            });
+        let request = client.any_stub().request(Check {});
+        assert_eq!(request.metadata().get("traceparent").unwrap(), "first");
+        assert_eq!(request.metadata().get("previous").unwrap(), "first");
+    }
+
+    #[cfg(any(feature = "dgraph-21-03", feature = "dgraph-24-02"))]
+    #[test]
+    fn namespace() {
+        let client = Client::new("http://127.0.0.1:19080")
+            .unwrap()
+            .for_namespace(42);
+        let request = client.any_stub().request(Check {});
+        assert_eq!(request.metadata().get("namespace").unwrap(), "42");
+    }
+
+    #[cfg(all(
+        feature = "gzip",
+        any(
+            feature = "dgraph-1-1",
+            feature = "dgraph-21-03",
+            feature = "dgraph-24-02"
+        )
+    ))]
+    #[tokio::test]
+    async fn compression_threshold() {
+        use crate::api::IDgraphClient;
+        use crate::fake_server::{serve, FakeReply};
+        use crate::{Mutation, Request, Response};
+
+        // send 10 byte and 1MB mutation, return grpc-encoding header, compressed flag of gRPC
+        // frame and length of received body
+        async fn send(threshold: Option<usize>) -> Vec<(Option<http::HeaderValue>, u8, usize)> {
+            let received = Arc::new(Mutex::new(Vec::new()));
+            let recorder = Arc::clone(&received);
+            let (addr, server) = serve(move |call| {
+                let encoding = call.headers.get("grpc-encoding").cloned();
+                recorder
+                    .lock()
+                    .unwrap()
+                    .push((encoding, call.body[0], call.body.len()));
+                FakeReply::new(&Response::default())
+            })
+            .await;
+            let mut client = Client::new(addr).unwrap();
+            if let Some(threshold) = threshold {
+                client = client.with_compression_threshold(threshold);
+            }
+            for size in &[10, 1024 * 1024] {
+                let mut mu = Mutation::new();
+                mu.set_set_nquads("a".repeat(*size));
+                let req = Request {
+                    mutations: vec![mu],
+                    ..Default::default()
+                };
+                client.any_stub().do_request(req).await.expect("Response");
+            }
+            server.abort();
+            let received = received.lock().unwrap();
+            received.clone()
         }
+
+        let received = send(Some(1024)).await;
+        let (small_encoding, small_flag, small_len) = &received[0];
+        let (big_encoding, big_flag, big_len) = &received[1];
+        assert!(small_encoding.is_none());
+        assert_eq!(*small_flag, 0);
+        assert!(*small_len > 10);
+        assert_eq!(big_encoding.as_ref().unwrap(), "gzip");
+        assert_eq!(*big_flag, 1);
+        assert!(*big_len < 1024 * 1024 / 100);
+        // without threshold nothing is compressed
+        let received = send(None).await;
+        assert!(received.iter().all(|(encoding, flag, _)| *flag == 0
+            && encoding
+                .as_ref()
+                .is_none_or(|encoding| encoding == "identity")));
+        assert!(received[1].2 > 1024 * 1024);
+    }
+
+    #[tokio::test]
+    async fn max_message_size() {
+        use crate::api::IDgraphClient;
+        use crate::fake_server::{serve, FakeReply};
+        use crate::{Request, Response};
+
+        let big = Response {
+            json: vec![b' '; 5 * 1024 * 1024],
+            ..Default::default()
+        };
+        let reply = big.clone();
+        let (addr, server) = serve(move |_| FakeReply::new(&reply)).await;
+        let client = Client::new(addr).unwrap();
+        let err = client
+            .any_stub()
+            .query(Request::default())
+            .await
+            .expect_err("Response over default limit");
+        assert!(matches!(
+            err.downcast_ref::<ClientError>(),
+            Some(ClientError::CannotQuery(status)) if status.code() == tonic::Code::OutOfRange
+        ));
+        server.abort();
+        let reply = big.clone();
+        let (addr, server) = serve(move |_| FakeReply::new(&reply)).await;
+        let client = Client::new(addr)
+            .unwrap()
+            .with_max_message_size(8 * 1024 * 1024);
+        let response = client
+            .any_stub()
+            .query(Request::default())
+            .await
+            .expect("Response under raised limit");
+        assert_eq!(response.json.len(), big.json.len());
+        server.abort();
+    }
+
+    #[tokio::test]
+    async fn observer() {
+        let observer = Arc::new(RecordingObserver::default());
+        let client = Client::new("http://127.0.0.1:1")
+            .unwrap()
+            .with_observer(Arc::clone(&observer) as Arc<dyn RequestObserver>);
+        let err = client
+            .check_version()
+            .await
+            .expect_err("Connection refused");
+        assert!(matches!(
+            err.downcast_ref::<ClientError>(),
+            Some(ClientError::ConnectionFailed { .. })
+        ));
+        assert_eq!(
+            *observer.events.lock().unwrap(),
+            vec![
+                (OpKind::CheckVersion, None),
+                (OpKind::CheckVersion, Some("grpc"))
+            ]
+        );
+    }
+
+    #[test]
+    fn observer_cancelled() {
+        let observer = Arc::new(RecordingObserver::default());
+        let client = Client::new("http://127.0.0.1:19080")
+            .unwrap()
+            .with_observer(Arc::clone(&observer) as Arc<dyn RequestObserver>);
+        let stub = client.any_stub();
+        drop(stub.timer(OpKind::Query));
+        let finished = stub.timer(OpKind::Alter);
+        let _ = finished.finish::<()>(Err(ClientError::ShutDown.into()));
+        assert_eq!(
+            *observer.events.lock().unwrap(),
+            vec![
+                (OpKind::Query, None),
+                (OpKind::Query, Some("cancelled")),
+                (OpKind::Alter, None),
+                (OpKind::Alter, Some("client"))
+            ]
+        );
     }
 }
@@ -6,6 +6,7 @@ use crate::client::lazy::ILazyChannel;
 #[cfg(feature = "tls")]
 use crate::client::tls::LazyTlsChannel;
 use crate::client::{AclClientType as AsyncAclClient, IClient as IAsyncClient, LazyChannel};
+use crate::stub::Stub;
 use crate::sync::client::{ClientVariant, IClient};
 use crate::sync::txn::TxnType as SyncTxn;
 use crate::sync::{TxnBestEffortType, TxnMutatedType, TxnReadOnlyType};
@@ -46,6 +47,10 @@ impl<C: ILazyChannel> IClient for Acl<C> {
         self.async_client_ref().new_txn()
     }
 
+    fn stub(&self) -> Stub<Self::Client> {
+        self.async_client_ref().any_stub()
+    }
+
     async fn login<T: Into<String> + Send + Sync>(
         self,
         _user_id: T,
@@ -54,7 +59,7 @@ impl<C: ILazyChannel> IClient for Acl<C> {
         Ok(self.async_client)
     }
 
-    #[cfg(feature = "dgraph-21-03")]
+    #[cfg(any(feature = "dgraph-21-03", feature = "dgraph-24-02"))]
     async fn login_into_namespace<T: Into<String> + Send + Sync>(
         self,
         _user_id: T,
@@ -129,6 +134,9 @@ impl<S: IClient> ClientVariant<S> {
     ///
     /// Try to login. If login is success than consume original client and return client with acl turn on.
     ///
+    /// Acl client has no own constructor, it keeps Tokio runtime of original client. Create
+    /// client with `new_with_runtime` to run Acl client on own runtime.
+    ///
     /// # Arguments
     ///
     /// * `user_id`: User ID
@@ -193,7 +201,7 @@ impl<S: IClient> ClientVariant<S> {
     /// }
     /// ```
     ///
-    #[cfg(feature = "dgraph-21-03")]
+    #[cfg(any(feature = "dgraph-21-03", feature = "dgraph-24-02"))]
     pub fn login_into_namespace<T: Into<String> + Send + Sync>(
         self,
         user_id: T,
@@ -261,7 +269,7 @@ mod tests {
     }
 
     #[test]
-    #[cfg(feature = "dgraph-21-03")]
+    #[cfg(any(feature = "dgraph-21-03", feature = "dgraph-24-02"))]
     fn login_into_namespace() {
         let client = Client::new("http://127.0.0.1:19080")
             .unwrap()
@@ -281,4 +289,31 @@ mod tests {
         let refresh = client.refresh_login();
         assert!(refresh.is_ok());
     }
+
+    #[test]
+    fn login_keeps_runtime() {
+        use std::sync::Arc;
+
+        use prost::Message;
+
+        use crate::api::{Jwt, Response};
+        use crate::fake_server::{serve, FakeReply};
+
+        let rt = Arc::new(tokio::runtime::Runtime::new().unwrap());
+        let (addr, _server) = rt.block_on(serve(|_| {
+            let jwt = Jwt {
+                access_jwt: "access".into(),
+                refresh_jwt: "refresh".into(),
+            };
+            FakeReply::new(&Response {
+                json: jwt.encode_to_vec(),
+                ..Default::default()
+            })
+        }));
+        let client = Client::new_with_runtime(addr, Arc::clone(&rt))
+            .unwrap()
+            .login("groot", "password")
+            .expect("logged");
+        assert!(Arc::ptr_eq(&client.rt, &rt));
+    }
 }
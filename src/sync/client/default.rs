@@ -1,14 +1,17 @@
 use std::convert::TryInto;
+use std::sync::Arc;
 
 use anyhow::Result;
 use async_trait::async_trait;
 use http::Uri;
 use std::fmt::Debug;
+use tokio::runtime::Runtime;
 
 use crate::client::lazy::LazyClient;
 #[cfg(feature = "acl")]
 use crate::client::AclClientType;
 use crate::client::{Client as AsyncClient, IClient as IAsyncClient, LazyChannel};
+use crate::stub::Stub;
 use crate::sync::client::{ClientState, ClientVariant, IClient};
 use crate::sync::txn::{TxnBestEffortType, TxnMutatedType, TxnReadOnlyType, TxnType as SyncTxn};
 use crate::txn::TxnType;
@@ -50,6 +53,10 @@ impl IClient for Default {
         self.async_client_ref().new_txn()
     }
 
+    fn stub(&self) -> Stub<Self::Client> {
+        self.async_client_ref().any_stub()
+    }
+
     #[cfg(feature = "acl")]
     async fn login<T: Into<String> + Send + Sync>(
         self,
@@ -59,7 +66,10 @@ impl IClient for Default {
         self.async_client.login(user_id, password).await
     }
 
-    #[cfg(all(feature = "acl", feature = "dgraph-21-03"))]
+    #[cfg(all(
+        feature = "acl",
+        any(feature = "dgraph-21-03", feature = "dgraph-24-02")
+    ))]
     async fn login_into_namespace<T: Into<String> + Send + Sync>(
         self,
         user_id: T,
@@ -102,6 +112,7 @@ impl Client {
     /// Create new Sync Dgraph client for interacting v DB.
     ///
     /// The client can be backed by multiple endpoints (to the same server, or multiple servers in a cluster).
+    /// Duplicated endpoints are used only once.
     ///
     /// # Arguments
     ///
@@ -123,7 +134,9 @@ impl Client {
     /// let client = Client::new("http://127.0.0.1:19080").expect("Dgraph client");
     /// ```
     ///
-    pub fn new<S: TryInto<Uri>, E: Into<Endpoints<S>> + Debug>(endpoints: E) -> Result<Self> {
+    pub fn new<S: TryInto<Uri> + Debug, E: Into<Endpoints<S>> + Debug>(
+        endpoints: E,
+    ) -> Result<Self> {
         let extra = Default {
             async_client: AsyncClient::new(endpoints)?,
         };
@@ -131,10 +144,49 @@ impl Client {
         Ok(Self { state, extra })
     }
 
+    ///
+    /// Create new Sync Dgraph client which executes requests on given Tokio runtime.
+    ///
+    /// By default, all sync clients share one global multi-thread runtime. Own runtime can be
+    /// used to isolate client or to run it on current-thread runtime.
+    ///
+    /// # Arguments
+    ///
+    /// * `endpoints` - one endpoint or vector of endpoints
+    /// * `rt` - Tokio runtime used for blocking calls of client and its transactions
+    ///
+    /// # Errors
+    ///
+    /// * endpoints vector is empty
+    /// * item in vector cannot by converted into Uri
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use std::sync::Arc;
+    /// use dgraph_tonic::sync::Client;
+    /// use tokio::runtime::Builder;
+    ///
+    /// let rt = Builder::new_current_thread().enable_all().build().expect("Tokio runtime");
+    /// let client = Client::new_with_runtime("http://127.0.0.1:19080", Arc::new(rt)).expect("Dgraph client");
+    /// ```
+    ///
+    pub fn new_with_runtime<S: TryInto<Uri> + Debug, E: Into<Endpoints<S>> + Debug>(
+        endpoints: E,
+        rt: Arc<Runtime>,
+    ) -> Result<Self> {
+        let extra = Default {
+            async_client: AsyncClient::new(endpoints)?,
+        };
+        let state = Box::new(ClientState::new_with_runtime(rt));
+        Ok(Self { state, extra })
+    }
+
     ///
     /// Create new Sync Dgraph client with custom endpoint configuration for interacting with DB.
     ///
     /// The client can be backed by multiple endpoints (to the same server, or multiple servers in a cluster).
+    /// Duplicated endpoints are used only once.
     ///
     /// # Arguments
     ///
@@ -172,7 +224,7 @@ impl Client {
     /// ```
     ///
     pub fn new_with_endpoint_config<
-        S: TryInto<Uri>,
+        S: TryInto<Uri> + Debug,
         E: Into<Endpoints<S>> + Debug,
         C: EndpointConfig + 'static,
     >(
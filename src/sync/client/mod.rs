@@ -7,7 +7,19 @@ use async_trait::async_trait;
 use lazy_static::lazy_static;
 use tokio::runtime::Runtime;
 
+#[cfg(any(
+    feature = "dgraph-1-1",
+    feature = "dgraph-21-03",
+    feature = "dgraph-24-02"
+))]
+use crate::api::operation::DropOp;
 use crate::api::IDgraphClient;
+#[cfg(any(
+    feature = "dgraph-1-1",
+    feature = "dgraph-21-03",
+    feature = "dgraph-24-02"
+))]
+use crate::client::drop_operation;
 use crate::client::lazy::ILazyChannel;
 #[cfg(feature = "acl")]
 use crate::client::AclClientType as AsyncAclClient;
@@ -63,6 +75,13 @@ impl ClientState {
             rt: Arc::clone(&*RT),
         }
     }
+
+    ///
+    /// Create new client state which runs async implementation on given runtime
+    ///
+    pub fn new_with_runtime(rt: Arc<Runtime>) -> Self {
+        Self { rt }
+    }
 }
 
 impl Default for ClientState {
@@ -87,6 +106,8 @@ pub trait IClient {
 
     fn new_txn(&self) -> AsyncTxn<Self::Client>;
 
+    fn stub(&self) -> Stub<Self::Client>;
+
     #[cfg(feature = "acl")]
     async fn login<T: Into<String> + Send + Sync>(
         self,
@@ -94,7 +115,10 @@ pub trait IClient {
         password: T,
     ) -> Result<AsyncAclClient<Self::Channel>>;
 
-    #[cfg(all(feature = "acl", feature = "dgraph-21-03"))]
+    #[cfg(all(
+        feature = "acl",
+        any(feature = "dgraph-21-03", feature = "dgraph-24-02")
+    ))]
     async fn login_into_namespace<T: Into<String> + Send + Sync>(
         self,
         user_id: T,
@@ -130,7 +154,7 @@ impl<C: IClient> ClientVariant<C> {
     /// Return new stub with grpc client implemented according to actual variant.
     ///
     fn any_stub(&self) -> Stub<C::Client> {
-        Stub::new(self.extra.client())
+        self.extra.stub()
     }
 
     ///
@@ -222,6 +246,63 @@ impl<C: IClient> ClientVariant<C> {
         self.rt.block_on(async move { stub.alter(op).await })
     }
 
+    ///
+    /// Run any alter operation in background.
+    ///
+    /// Operation is sent with `run_in_background` flag, so Dgraph returns before operation is
+    /// finished.
+    ///
+    /// # Arguments
+    ///
+    /// - `op`: Alter operation
+    ///
+    /// # Errors
+    ///
+    /// * gRPC error
+    /// * DB reject alter command
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use dgraph_tonic::Operation;
+    /// use dgraph_tonic::sync::Client;
+    /// #[cfg(feature = "acl")]
+    /// use dgraph_tonic::sync::AclClientType;
+    /// #[cfg(feature = "acl")]
+    /// use dgraph_tonic::LazyChannel;
+    ///
+    /// #[cfg(not(feature = "acl"))]
+    /// fn client() -> Client {
+    ///     Client::new("http://127.0.0.1:19080").expect("Dgraph client")
+    /// }
+    ///
+    /// #[cfg(feature = "acl")]
+    /// fn client() -> AclClientType<LazyChannel> {
+    ///     let default = Client::new("http://127.0.0.1:19080").unwrap();
+    ///     default.login("groot", "password").expect("Acl client")
+    /// }
+    ///
+    /// fn main() -> Result<(), Box<dyn std::error::Error>> {
+    ///     let client = client();
+    ///     let op = Operation {
+    ///         drop_attr: "name".into(),
+    ///         ..Default::default()
+    ///     };
+    ///     client.alter_in_background(op).expect("Predicate is not dropped");
+    ///     Ok(())
+    /// }
+    /// ```
+    ///
+    #[cfg(any(
+        feature = "dgraph-1-1",
+        feature = "dgraph-21-03",
+        feature = "dgraph-24-02"
+    ))]
+    pub fn alter_in_background(&self, mut op: Operation) -> Result<Payload> {
+        op.run_in_background = true;
+        self.alter(op)
+    }
+
     ///
     /// Create or change the schema.
     ///
@@ -314,7 +395,11 @@ impl<C: IClient> ClientVariant<C> {
     /// }
     /// ```
     ///
-    #[cfg(any(feature = "dgraph-1-1", feature = "dgraph-21-03"))]
+    #[cfg(any(
+        feature = "dgraph-1-1",
+        feature = "dgraph-21-03",
+        feature = "dgraph-24-02"
+    ))]
     pub fn set_schema_in_background<S: Into<String>>(&self, schema: S) -> Result<Payload> {
         let op = Operation {
             schema: schema.into(),
@@ -369,6 +454,156 @@ impl<C: IClient> ClientVariant<C> {
         self.alter(op)
     }
 
+    ///
+    /// Drop all data in DB in background
+    ///
+    /// # Errors
+    ///
+    /// * gRPC error
+    /// * DB reject alter command
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use dgraph_tonic::Operation;
+    /// use dgraph_tonic::sync::Client;
+    /// #[cfg(feature = "acl")]
+    /// use dgraph_tonic::sync::AclClientType;
+    /// #[cfg(feature = "acl")]
+    /// use dgraph_tonic::LazyChannel;
+    ///
+    /// #[cfg(not(feature = "acl"))]
+    /// fn client() -> Client {
+    ///     Client::new("http://127.0.0.1:19080").expect("Dgraph client")
+    /// }
+    ///
+    /// #[cfg(feature = "acl")]
+    /// fn client() -> AclClientType<LazyChannel> {
+    ///     let default = Client::new("http://127.0.0.1:19080").unwrap();
+    ///     default.login("groot", "password").expect("Acl client")
+    /// }
+    ///
+    /// fn main() -> Result<(), Box<dyn std::error::Error>> {
+    ///     let client = client();
+    ///     client.drop_all_in_background().expect("Data not dropped");
+    ///     Ok(())
+    /// }
+    /// ```
+    ///
+    #[cfg(any(
+        feature = "dgraph-1-1",
+        feature = "dgraph-21-03",
+        feature = "dgraph-24-02"
+    ))]
+    pub fn drop_all_in_background(&self) -> Result<Payload> {
+        let op = Operation {
+            drop_all: true,
+            ..Default::default()
+        };
+        self.alter_in_background(op)
+    }
+
+    ///
+    /// Drop all data in DB, but keep schema and types.
+    ///
+    /// # Errors
+    ///
+    /// * gRPC error
+    /// * DB reject alter command
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use dgraph_tonic::sync::Client;
+    /// #[cfg(feature = "acl")]
+    /// use dgraph_tonic::sync::AclClientType;
+    /// #[cfg(feature = "acl")]
+    /// use dgraph_tonic::LazyChannel;
+    ///
+    /// #[cfg(not(feature = "acl"))]
+    /// fn client() -> Client {
+    ///     Client::new("http://127.0.0.1:19080").expect("Dgraph client")
+    /// }
+    ///
+    /// #[cfg(feature = "acl")]
+    /// fn client() -> AclClientType<LazyChannel> {
+    ///     let default = Client::new("http://127.0.0.1:19080").unwrap();
+    ///     default.login("groot", "password").expect("Acl client")
+    /// }
+    ///
+    /// fn main() {
+    ///     let client = client();
+    ///     client.drop_data().expect("Data not dropped");
+    /// }
+    /// ```
+    ///
+    #[cfg(any(
+        feature = "dgraph-1-1",
+        feature = "dgraph-21-03",
+        feature = "dgraph-24-02"
+    ))]
+    pub fn drop_data(&self) -> Result<Payload> {
+        let op = Operation {
+            drop_op: DropOp::Data as i32,
+            ..Default::default()
+        };
+        self.alter(op)
+    }
+
+    ///
+    /// Drop all data, data and schema of predicate or type.
+    ///
+    /// # Arguments
+    ///
+    /// - `op`: what is dropped
+    /// - `value`: name of predicate or type, `None` when all data are dropped
+    ///
+    /// # Errors
+    ///
+    /// * `InvalidDropOp`: value does not match operation
+    /// * gRPC error
+    /// * DB reject alter command
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use dgraph_tonic::DropOp;
+    /// use dgraph_tonic::sync::Client;
+    /// #[cfg(feature = "acl")]
+    /// use dgraph_tonic::sync::AclClientType;
+    /// #[cfg(feature = "acl")]
+    /// use dgraph_tonic::LazyChannel;
+    ///
+    /// #[cfg(not(feature = "acl"))]
+    /// fn client() -> Client {
+    ///     Client::new("http://127.0.0.1:19080").expect("Dgraph client")
+    /// }
+    ///
+    /// #[cfg(feature = "acl")]
+    /// fn client() -> AclClientType<LazyChannel> {
+    ///     let default = Client::new("http://127.0.0.1:19080").unwrap();
+    ///     default.login("groot", "password").expect("Acl client")
+    /// }
+    ///
+    /// fn main() {
+    ///     let client = client();
+    ///     client.set_schema("nickname: string .").expect("Schema");
+    ///     client
+    ///         .alter_drop(DropOp::Attr, Some("nickname".to_string()))
+    ///         .expect("Predicate not dropped");
+    /// }
+    /// ```
+    ///
+    #[cfg(any(
+        feature = "dgraph-1-1",
+        feature = "dgraph-21-03",
+        feature = "dgraph-24-02"
+    ))]
+    pub fn alter_drop(&self, op: DropOp, value: Option<String>) -> Result<Payload> {
+        let op = drop_operation(op, value)?;
+        self.alter(op)
+    }
+
     ///
     /// Check DB version
     ///
@@ -414,6 +649,19 @@ mod tests {
         default.login("groot", "password").unwrap()
     }
 
+    #[test]
+    fn new_with_runtime() {
+        let rt = tokio::runtime::Builder::new_current_thread()
+            .enable_all()
+            .build()
+            .unwrap();
+        let rt = Arc::new(rt);
+        let client = Client::new_with_runtime("http://127.0.0.1:19080", Arc::clone(&rt)).unwrap();
+        assert!(Arc::ptr_eq(&client.rt, &rt));
+        let default = Client::new("http://127.0.0.1:19080").unwrap();
+        assert!(!Arc::ptr_eq(&default.rt, &rt));
+    }
+
     #[test]
     fn alter() {
         let client = client();
@@ -1,9 +1,11 @@
 use std::convert::TryInto;
+use std::sync::Arc;
 
 use anyhow::Result;
 use async_trait::async_trait;
 use http::Uri;
 use std::fmt::Debug;
+use tokio::runtime::Runtime;
 
 use crate::client::slash_ql::LazySlashQlClient;
 use crate::client::tls::LazyTlsChannel;
@@ -12,6 +14,7 @@ use crate::client::AclClientType;
 use crate::client::{
     IClient as IAsyncClient, SlashQlClient as AsyncSlashQlClient, TlsClient as AsyncTlsClient,
 };
+use crate::stub::Stub;
 use crate::sync::client::{ClientState, ClientVariant, IClient, TlsClient};
 use crate::sync::txn::{TxnBestEffortType, TxnMutatedType, TxnReadOnlyType, TxnType as SyncTxn};
 use crate::txn::TxnType;
@@ -52,6 +55,10 @@ impl IClient for SlashQl {
         self.async_client_ref().new_txn()
     }
 
+    fn stub(&self) -> Stub<Self::Client> {
+        self.async_client_ref().any_stub()
+    }
+
     #[cfg(feature = "acl")]
     async fn login<T: Into<String> + Send + Sync>(
         self,
@@ -61,7 +68,10 @@ impl IClient for SlashQl {
         self.async_client.login(user_id, password).await
     }
 
-    #[cfg(all(feature = "acl", feature = "dgraph-21-03"))]
+    #[cfg(all(
+        feature = "acl",
+        any(feature = "dgraph-21-03", feature = "dgraph-24-02")
+    ))]
     async fn login_into_namespace<T: Into<String> + Send + Sync>(
         self,
         user_id: T,
@@ -131,13 +141,73 @@ impl TlsClient {
     /// }
     /// ```
     ///
-    pub fn for_slash_ql<S: TryInto<Uri>, E: Into<Endpoints<S>>, T: Into<String>>(
+    pub fn for_slash_ql<S: TryInto<Uri> + Debug, E: Into<Endpoints<S>>, T: Into<String>>(
+        endpoints: E,
+        api_key: T,
+    ) -> Result<SlashQlClient> {
+        let extra = SlashQl {
+            async_client: AsyncTlsClient::for_slash_ql(endpoints, api_key)?,
+        };
+        let state = Box::new(ClientState::new());
+        Ok(SlashQlClient { state, extra })
+    }
+
+    ///
+    /// New gRPC [SlashQL](https://dgraph.io/slash-graphql) client which executes requests on
+    /// given Tokio runtime.
+    ///
+    /// By default, all sync clients share one global multi-thread runtime.
+    ///
+    /// # Arguments
+    ///
+    /// * `endpoints` - one endpoint or vector of endpoints
+    /// * `api_key` -  API Key for SlashQL
+    /// * `rt` - Tokio runtime used for blocking calls of client and its transactions
+    ///
+    /// # Errors
+    ///
+    /// * endpoints vector is empty
+    /// * item in vector cannot by converted into Uri
+    ///
+    pub fn for_slash_ql_with_runtime<
+        S: TryInto<Uri> + Debug,
+        E: Into<Endpoints<S>>,
+        T: Into<String>,
+    >(
         endpoints: E,
         api_key: T,
+        rt: Arc<Runtime>,
     ) -> Result<SlashQlClient> {
         let extra = SlashQl {
             async_client: AsyncTlsClient::for_slash_ql(endpoints, api_key)?,
         };
+        let state = Box::new(ClientState::new_with_runtime(rt));
+        Ok(SlashQlClient { state, extra })
+    }
+
+    ///
+    /// New gRPC [SlashQL](https://dgraph.io/slash-graphql) client for GraphQL url of backend.
+    ///
+    /// gRPC endpoint is derived from GraphQL url, e.g. for
+    /// `https://app.eu-central-1.aws.cloud.dgraph.io/graphql` is client connected to
+    /// `http://app.grpc.eu-central-1.aws.cloud.dgraph.io:443`.
+    ///
+    /// # Arguments
+    ///
+    /// * `graphql_url` - GraphQL url of Dgraph Cloud backend
+    /// * `api_key` -  API Key for SlashQL
+    ///
+    /// # Errors
+    ///
+    /// * `InvalidEndpoint`: url does not match GraphQL url of Dgraph Cloud backend
+    ///
+    pub fn for_slash_ql_from_graphql_url<T: Into<String>>(
+        graphql_url: &str,
+        api_key: T,
+    ) -> Result<SlashQlClient> {
+        let extra = SlashQl {
+            async_client: AsyncTlsClient::for_slash_ql_from_graphql_url(graphql_url, api_key)?,
+        };
         let state = Box::new(ClientState::new());
         Ok(SlashQlClient { state, extra })
     }
@@ -190,7 +260,7 @@ impl TlsClient {
     /// ```
     ///
     pub fn for_slash_ql_with_endpoint_config<
-        S: TryInto<Uri>,
+        S: TryInto<Uri> + Debug,
         E: Into<Endpoints<S>>,
         T: Into<String>,
         C: EndpointConfig + 'static,
@@ -213,8 +283,26 @@ impl TlsClient {
 
 #[cfg(test)]
 mod tests {
+    use std::sync::Arc;
+
     use crate::sync::TlsClient;
 
+    #[test]
+    fn for_slash_ql_with_runtime() {
+        let rt = tokio::runtime::Builder::new_current_thread()
+            .enable_all()
+            .build()
+            .unwrap();
+        let rt = Arc::new(rt);
+        let client = TlsClient::for_slash_ql_with_runtime(
+            "http://app.grpc.eu-central-1.aws.cloud.dgraph.io:443",
+            "API_KEY",
+            Arc::clone(&rt),
+        )
+        .unwrap();
+        assert!(Arc::ptr_eq(&client.rt, &rt));
+    }
+
     //#[test]
     #[allow(dead_code)]
     fn for_slash_ql() {
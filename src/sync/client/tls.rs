@@ -1,15 +1,18 @@
 use std::convert::TryInto;
+use std::sync::Arc;
 
 use anyhow::Result;
 use async_trait::async_trait;
 use http::Uri;
 use std::fmt::Debug;
+use tokio::runtime::Runtime;
 
 use crate::client::lazy::LazyClient;
 use crate::client::tls::LazyTlsChannel;
 #[cfg(feature = "acl")]
 use crate::client::AclClientType;
 use crate::client::{IClient as IAsyncClient, TlsClient as AsyncTlsClient};
+use crate::stub::Stub;
 use crate::sync::client::{ClientState, ClientVariant, IClient};
 use crate::sync::txn::{TxnBestEffortType, TxnMutatedType, TxnReadOnlyType, TxnType as SyncTxn};
 use crate::txn::TxnType;
@@ -50,6 +53,10 @@ impl IClient for Tls {
         self.async_client_ref().new_txn()
     }
 
+    fn stub(&self) -> Stub<Self::Client> {
+        self.async_client_ref().any_stub()
+    }
+
     #[cfg(feature = "acl")]
     async fn login<T: Into<String> + Send + Sync>(
         self,
@@ -59,7 +66,10 @@ impl IClient for Tls {
         self.async_client.login(user_id, password).await
     }
 
-    #[cfg(all(feature = "acl", feature = "dgraph-21-03"))]
+    #[cfg(all(
+        feature = "acl",
+        any(feature = "dgraph-21-03", feature = "dgraph-24-02")
+    ))]
     async fn login_into_namespace<T: Into<String> + Send + Sync>(
         self,
         user_id: T,
@@ -102,6 +112,7 @@ impl TlsClient {
     /// Create new Sync Dgraph client authorized with SSL cert for interacting v DB.
     ///
     /// The client can be backed by multiple endpoints (to the same server, or multiple servers in a cluster).
+    /// Duplicated endpoints are used only once.
     ///
     /// # Arguments
     ///
@@ -134,7 +145,7 @@ impl TlsClient {
     /// }
     /// ```
     ///
-    pub fn new<S: TryInto<Uri>, E: Into<Endpoints<S>>, V: Into<Vec<u8>>>(
+    pub fn new<S: TryInto<Uri> + Debug, E: Into<Endpoints<S>>, V: Into<Vec<u8>>>(
         endpoints: E,
         server_root_ca_cert: V,
         client_cert: V,
@@ -152,10 +163,71 @@ impl TlsClient {
         Ok(Self { state, extra })
     }
 
+    ///
+    /// Create new Sync Dgraph client authorized with SSL cert which executes requests on given
+    /// Tokio runtime.
+    ///
+    /// By default, all sync clients share one global multi-thread runtime.
+    ///
+    /// # Arguments
+    ///
+    /// * `endpoints` - one endpoint or vector of endpoints
+    /// * `server_root_ca_cert` - CA certificate
+    /// * `client_cert` - Client certificate
+    /// * `client_key` - Client key
+    /// * `rt` - Tokio runtime used for blocking calls of client and its transactions
+    ///
+    /// # Errors
+    ///
+    /// * endpoints vector is empty
+    /// * item in vector cannot by converted into Uri
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use std::sync::Arc;
+    /// use dgraph_tonic::sync::TlsClient;
+    /// use tokio::runtime::Builder;
+    ///
+    /// fn main() {
+    ///     let server_root_ca_cert = std::fs::read("path/to/ca.crt").expect("CA cert");
+    ///     let client_cert = std::fs::read("path/to/client.crt").expect("Client cert");
+    ///     let client_key = std::fs::read("path/to/ca.key").expect("Client key");
+    ///     let rt = Builder::new_current_thread().enable_all().build().expect("Tokio runtime");
+    ///     let client = TlsClient::new_with_runtime(
+    ///             "http://127.0.0.1:19080",
+    ///             server_root_ca_cert,
+    ///             client_cert,
+    ///             client_key,
+    ///             Arc::new(rt))
+    ///         .expect("Dgraph TLS client");
+    /// }
+    /// ```
+    ///
+    pub fn new_with_runtime<S: TryInto<Uri> + Debug, E: Into<Endpoints<S>>, V: Into<Vec<u8>>>(
+        endpoints: E,
+        server_root_ca_cert: V,
+        client_cert: V,
+        client_key: V,
+        rt: Arc<Runtime>,
+    ) -> Result<Self> {
+        let extra = Tls {
+            async_client: AsyncTlsClient::new(
+                endpoints,
+                server_root_ca_cert,
+                client_cert,
+                client_key,
+            )?,
+        };
+        let state = Box::new(ClientState::new_with_runtime(rt));
+        Ok(Self { state, extra })
+    }
+
     ///
     /// Create new Sync Dgraph client authorized with custom endpoint configuration and SSL cert for interacting v DB.
     ///
     /// The client can be backed by multiple endpoints (to the same server, or multiple servers in a cluster).
+    /// Duplicated endpoints are used only once.
     ///
     /// # Arguments
     ///
@@ -205,7 +277,7 @@ impl TlsClient {
     /// ```
     ///
     pub fn new_with_endpoint_config<
-        S: TryInto<Uri>,
+        S: TryInto<Uri> + Debug,
         E: Into<Endpoints<S>>,
         V: Into<Vec<u8>>,
         C: EndpointConfig + 'static,
@@ -228,4 +300,42 @@ impl TlsClient {
         let state = Box::new(ClientState::new());
         Ok(Self { state, extra })
     }
+
+    ///
+    /// Verify certificates of all endpoints against given domain name instead of host of
+    /// endpoint uri.
+    ///
+    /// # Arguments
+    ///
+    /// * `domain_name` - name in server certificate, e.g. `dgraph.example.com`
+    ///
+    pub fn with_domain_name<D: AsRef<str>>(mut self, domain_name: D) -> Self {
+        self.extra.async_client = self.extra.async_client.with_domain_name(domain_name);
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use crate::sync::TlsClient;
+
+    #[test]
+    fn new_with_runtime() {
+        let rt = tokio::runtime::Builder::new_current_thread()
+            .enable_all()
+            .build()
+            .unwrap();
+        let rt = Arc::new(rt);
+        let client = TlsClient::new_with_runtime(
+            "https://127.0.0.1:19080",
+            Vec::new(),
+            Vec::new(),
+            Vec::new(),
+            Arc::clone(&rt),
+        )
+        .unwrap();
+        assert!(Arc::ptr_eq(&client.rt, &rt));
+    }
 }
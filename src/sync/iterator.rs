@@ -1,5 +1,6 @@
 use std::collections::HashMap;
 use std::hash::Hash;
+use std::marker::PhantomData;
 
 use anyhow::Result;
 use serde::de::DeserializeOwned;
@@ -100,6 +101,88 @@ where
     }
 }
 
+struct ChunkIteratorState<C, T>
+where
+    C: ILazyClient,
+    T: DeserializeOwned,
+{
+    txn: TxnReadOnlyType<C>,
+    query: String,
+    vars: HashMap<String, String>,
+    first: usize,
+    offset: usize,
+    done: bool,
+    chunk: PhantomData<T>,
+}
+
+impl<C, T> ChunkIteratorState<C, T>
+where
+    C: ILazyClient,
+    T: DeserializeOwned,
+{
+    fn new<Q, K, V>(txn: TxnReadOnlyType<C>, query: Q, vars: HashMap<K, V>, first: usize) -> Self
+    where
+        Q: Into<String>,
+        K: Into<String> + Eq + Hash,
+        V: Into<String>,
+    {
+        let mut vars = vars.into_iter().fold(HashMap::new(), |mut tmp, (k, v)| {
+            tmp.insert(k.into(), v.into());
+            tmp
+        });
+        vars.insert(String::from("$first"), format!("{}", first));
+        Self {
+            txn,
+            query: query.into(),
+            vars,
+            first,
+            offset: 0,
+            done: false,
+            chunk: PhantomData,
+        }
+    }
+
+    fn fetch_items(&mut self) -> Result<Vec<T>> {
+        let mut vars = self.vars.to_owned();
+        vars.insert(String::from("$offset"), format!("{}", self.offset));
+        let chunk: Chunk<T> = self
+            .txn
+            .query_with_vars(self.query.to_owned(), vars)?
+            .try_into_owned()?;
+        Ok(chunk.items)
+    }
+}
+
+impl<C, T> Iterator for ChunkIteratorState<C, T>
+where
+    C: ILazyClient,
+    T: DeserializeOwned,
+{
+    type Item = Result<Vec<T>>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.done {
+            return None;
+        }
+        match self.fetch_items() {
+            Ok(items) => {
+                if items.len() < self.first {
+                    self.done = true;
+                }
+                if items.is_empty() {
+                    return None;
+                }
+                self.offset += items.len();
+                Some(Ok(items))
+            }
+            Err(err) => {
+                self.done = true;
+                Some(Err(err))
+            }
+        }
+    }
+}
+
 impl<C: ILazyClient> TxnReadOnlyType<C> {
     ///
     /// Readonly transaction is transformed into iterator.
@@ -257,6 +340,122 @@ impl<C: ILazyClient> TxnReadOnlyType<C> {
         );
         IteratorState::new(self, query, vars, first)
     }
+
+    ///
+    /// Readonly transaction is transformed into iterator over whole chunks of items.
+    ///
+    /// Input `query` must accept **$first: string, $offset: string** arguments which are used for paginating.
+    /// Iterator items must be returned in query block named **items**.
+    ///
+    /// # Return
+    ///
+    /// Iterator contains one page of deserialized items for every `next()` call.
+    /// Iteration stops after page with less than `first` items.
+    ///
+    /// # Arguments
+    ///
+    /// - `query`: GraphQL+- query segment.
+    /// - `first`:  number of items returned in one chunk
+    ///
+    /// # Errors
+    ///
+    /// * gRPC error
+    /// * If transaction is not initialized properly, return `EmptyTxn` error.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use dgraph_tonic::sync::Client;
+    /// #[cfg(feature = "acl")]
+    /// use dgraph_tonic::sync::AclClientType;
+    /// #[cfg(feature = "acl")]
+    /// use dgraph_tonic::LazyChannel;
+    /// use anyhow::Result;
+    /// use serde::Deserialize;
+    ///
+    /// #[cfg(not(feature = "acl"))]
+    /// fn client() -> Client {
+    ///     Client::new("http://127.0.0.1:19080").expect("Dgraph client")
+    /// }
+    ///
+    /// #[cfg(feature = "acl")]
+    /// fn client() -> AclClientType<LazyChannel> {
+    ///     let default = Client::new("http://127.0.0.1:19080").unwrap();
+    ///     default.login("groot", "password").expect("Acl client")
+    /// }
+    ///
+    /// #[derive(Deserialize, Debug)]
+    /// struct Person {
+    ///   uid: String,
+    ///   name: String,
+    /// }
+    ///
+    /// fn main() {
+    ///     let query = r#"query stream($first: string, $offset: string) {
+    ///         items(func: eq(name, "Alice"), first: $first, offset: $offset) {
+    ///             uid
+    ///             name
+    ///         }
+    ///     }"#;
+    ///
+    ///   let client = client();
+    ///   let iterator = client.new_read_only_txn().into_chunk_iter(query, 100);
+    ///   let chunks: Vec<Result<Vec<Person>>> = iterator.collect();
+    /// }
+    /// ```
+    ///
+    pub fn into_chunk_iter<Q, T>(
+        self,
+        query: Q,
+        first: usize,
+    ) -> impl Iterator<Item = Result<Vec<T>>>
+    where
+        Q: Into<String>,
+        T: DeserializeOwned,
+    {
+        self.into_chunk_iter_with_vars(query, HashMap::<String, String>::new(), first)
+    }
+
+    ///
+    /// Readonly transaction is transformed into iterator over whole chunks of items.
+    ///
+    /// Input `query` must accept **$first: string, $offset: string** arguments which are used for paginating.
+    /// Iterator items must be returned in query block named **items**.
+    ///
+    /// # Return
+    ///
+    /// Iterator contains one page of deserialized items for every `next()` call.
+    /// Iteration stops after page with less than `first` items.
+    ///
+    /// # Arguments
+    ///
+    /// - `query`: GraphQL+- query segment.
+    /// - `vars`: map of variables for query
+    /// - `first`:  number of items returned in one chunk
+    ///
+    /// # Errors
+    ///
+    /// * gRPC error
+    /// * If transaction is not initialized properly, return `EmptyTxn` error.
+    ///
+    pub fn into_chunk_iter_with_vars<Q, T, K, V>(
+        self,
+        query: Q,
+        vars: HashMap<K, V>,
+        first: usize,
+    ) -> impl Iterator<Item = Result<Vec<T>>>
+    where
+        Q: Into<String>,
+        T: DeserializeOwned,
+        K: Into<String> + Eq + Hash,
+        V: Into<String>,
+    {
+        assert_ne!(
+            first, 0,
+            "First attribute for stream must not be eq to zero"
+        );
+        ChunkIteratorState::new(self, query, vars, first)
+    }
 }
 
 #[cfg(test)]
@@ -425,4 +624,47 @@ mod tests {
         assert_eq!(cars.len(), 1);
         assert!(cars.iter().all(|car| car.is_err()))
     }
+
+    #[test]
+    fn chunk_iterator() {
+        let client = client();
+        client.drop_all().expect("Data not dropped");
+        client
+            .set_schema("color: string @index(exact) .")
+            .expect("Schema is not updated");
+        let txn = client.new_mutated_txn();
+        let data = vec![
+            Car {
+                uid: "_:a".to_string(),
+                color: "A".to_string(),
+            },
+            Car {
+                uid: "_:b".to_string(),
+                color: "B".to_string(),
+            },
+            Car {
+                uid: "_:c".to_string(),
+                color: "C".to_string(),
+            },
+        ];
+        let mut mu = Mutation::new();
+        mu.set_set_json(&data).expect("Invalid JSON");
+        let response = txn.mutate_and_commit_now(mu);
+        assert!(response.is_ok());
+        let iterator = client.new_read_only_txn().into_chunk_iter(
+            r#"
+            query stream($first: string, $offset: string) {
+                items(func: has(color), first: $first, offset: $offset) {{
+                    uid
+                    color
+                }}
+            }
+        "#,
+            2,
+        );
+        let chunks: Vec<Vec<Car>> = iterator.map(|chunk| chunk.expect("Chunk")).collect();
+        assert_eq!(chunks.len(), 2);
+        assert_eq!(chunks[0].len(), 2);
+        assert_eq!(chunks[1].len(), 1);
+    }
 }
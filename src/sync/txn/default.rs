@@ -41,7 +41,11 @@ impl<C: ILazyClient> IState for Base<C> {
         })
     }
 
-    #[cfg(any(feature = "dgraph-1-1", feature = "dgraph-21-03"))]
+    #[cfg(any(
+        feature = "dgraph-1-1",
+        feature = "dgraph-21-03",
+        feature = "dgraph-24-02"
+    ))]
     fn query_rdf_with_vars<Q, K, V>(&mut self, query: Q, vars: HashMap<K, V>) -> Result<Response>
     where
         Q: Into<String> + Send + Sync,
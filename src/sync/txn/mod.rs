@@ -4,12 +4,13 @@ use std::ops::{Deref, DerefMut};
 
 use anyhow::Result;
 use async_trait::async_trait;
+use serde_json::Value;
 
 pub use crate::sync::txn::best_effort::TxnBestEffortType;
 pub use crate::sync::txn::default::TxnType;
 pub use crate::sync::txn::mutated::{Mutate, MutationResponse, TxnMutatedType};
 pub use crate::sync::txn::read_only::TxnReadOnlyType;
-use crate::Response;
+use crate::{Response, ToDgraphVar};
 
 pub(crate) mod best_effort;
 pub(crate) mod default;
@@ -34,7 +35,11 @@ pub trait IState: Send + Sync + Clone {
         K: Into<String> + Send + Sync + Eq + Hash,
         V: Into<String> + Send + Sync;
 
-    #[cfg(any(feature = "dgraph-1-1", feature = "dgraph-21-03"))]
+    #[cfg(any(
+        feature = "dgraph-1-1",
+        feature = "dgraph-21-03",
+        feature = "dgraph-24-02"
+    ))]
     fn query_rdf_with_vars<Q, K, V>(&mut self, query: Q, vars: HashMap<K, V>) -> Result<Response>
     where
         Q: Into<String> + Send + Sync,
@@ -186,7 +191,11 @@ pub trait Query: Send + Sync {
     /// }
     /// ```
     ///
-    #[cfg(any(feature = "dgraph-1-1", feature = "dgraph-21-03"))]
+    #[cfg(any(
+        feature = "dgraph-1-1",
+        feature = "dgraph-21-03",
+        feature = "dgraph-24-02"
+    ))]
     fn query_rdf<Q>(&mut self, query: Q) -> Result<Response>
     where
         Q: Into<String> + Send + Sync;
@@ -317,12 +326,183 @@ pub trait Query: Send + Sync {
     /// }
     /// ```
     ///
-    #[cfg(any(feature = "dgraph-1-1", feature = "dgraph-21-03"))]
+    #[cfg(any(
+        feature = "dgraph-1-1",
+        feature = "dgraph-21-03",
+        feature = "dgraph-24-02"
+    ))]
     fn query_rdf_with_vars<Q, K, V>(&mut self, query: Q, vars: HashMap<K, V>) -> Result<Response>
     where
         Q: Into<String> + Send + Sync,
         K: Into<String> + Send + Sync + Eq + Hash,
         V: Into<String> + Send + Sync;
+
+    ///
+    /// Run query and return JSON data of response without deserialization.
+    ///
+    /// Data can be forwarded to another service as they are. Transaction context is updated in
+    /// same way like by `query`.
+    ///
+    /// # Arguments
+    ///
+    /// * `query`: GraphQL+- query
+    ///
+    /// # Errors
+    ///
+    /// If transaction is not initialized properly, return `EmptyTxn` error.
+    ///
+    /// gRPC errors can be returned also.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use dgraph_tonic::sync::{Query, Client};
+    /// #[cfg(feature = "acl")]
+    /// use dgraph_tonic::sync::AclClientType;
+    /// #[cfg(feature = "acl")]
+    /// use dgraph_tonic::LazyChannel;
+    ///
+    /// #[cfg(not(feature = "acl"))]
+    /// fn client() -> Client {
+    ///     Client::new("http://127.0.0.1:19080").expect("Dgraph client")
+    /// }
+    ///
+    /// #[cfg(feature = "acl")]
+    /// fn client() -> AclClientType<LazyChannel> {
+    ///     let default = Client::new("http://127.0.0.1:19080").unwrap();
+    ///     default.login("groot", "password").expect("Acl client")
+    /// }
+    ///
+    /// fn main() {
+    ///     let client = client();
+    ///     let mut txn = client.new_read_only_txn();
+    ///     let json: Vec<u8> = txn
+    ///         .query_json("{ all(func: has(name)) { uid name } }")
+    ///         .expect("JSON");
+    ///     println!("{}", String::from_utf8(json).unwrap());
+    /// }
+    /// ```
+    ///
+    fn query_json<Q>(&mut self, query: Q) -> Result<Vec<u8>>
+    where
+        Q: Into<String> + Send + Sync,
+    {
+        self.query_json_with_vars(query, HashMap::<String, String, _>::with_capacity(0))
+    }
+
+    ///
+    /// Run query with variables and return JSON data of response without deserialization.
+    ///
+    /// # Arguments
+    ///
+    /// * `query`: GraphQL+- query
+    /// * `vars`: map of variables
+    ///
+    /// # Errors
+    ///
+    /// If transaction is not initialized properly, return `EmptyTxn` error.
+    ///
+    /// gRPC errors can be returned also.
+    ///
+    fn query_json_with_vars<Q, K, V>(&mut self, query: Q, vars: HashMap<K, V>) -> Result<Vec<u8>>
+    where
+        Q: Into<String> + Send + Sync,
+        K: Into<String> + Send + Sync + Eq + Hash,
+        V: Into<String> + Send + Sync,
+    {
+        Ok(self.query_with_vars(query, vars)?.json)
+    }
+
+    ///
+    /// Run query with variables which are not strings, e.g. numbers or bools.
+    ///
+    /// Values are formatted by `ToDgraphVar`, so Dgraph parses them according to types of
+    /// variables declared in query.
+    ///
+    /// # Arguments
+    ///
+    /// * `query`: GraphQL+- query
+    /// * `vars`: map of variables
+    ///
+    /// # Errors
+    ///
+    /// If transaction is not initialized properly, return `EmptyTxn` error.
+    ///
+    /// gRPC errors can be returned also.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use std::collections::HashMap;
+    /// use dgraph_tonic::Response;
+    /// use dgraph_tonic::sync::{Query, Client};
+    /// #[cfg(feature = "acl")]
+    /// use dgraph_tonic::sync::AclClientType;
+    /// #[cfg(feature = "acl")]
+    /// use dgraph_tonic::LazyChannel;
+    ///
+    /// #[cfg(not(feature = "acl"))]
+    /// fn client() -> Client {
+    ///     Client::new("http://127.0.0.1:19080").expect("Dgraph client")
+    /// }
+    ///
+    /// #[cfg(feature = "acl")]
+    /// fn client() -> AclClientType<LazyChannel> {
+    ///     let default = Client::new("http://127.0.0.1:19080").unwrap();
+    ///     default.login("groot", "password").expect("Acl client")
+    /// }
+    ///
+    /// fn main() {
+    ///     let q = r#"query all($first: int) {
+    ///         all(func: has(name), first: $first) {
+    ///         uid
+    ///         name
+    ///         }
+    ///     }"#;
+    ///
+    ///     let mut vars = HashMap::new();
+    ///     vars.insert("$first", 10);
+    ///
+    ///     let client = client();
+    ///     let mut txn = client.new_read_only_txn();
+    ///     let resp: Response = txn.query_with_typed_vars(q, vars).expect("query response");
+    /// }
+    /// ```
+    ///
+    fn query_with_typed_vars<Q, K, V>(&mut self, query: Q, vars: HashMap<K, V>) -> Result<Response>
+    where
+        Q: Into<String> + Send + Sync,
+        K: Into<String> + Send + Sync + Eq + Hash,
+        V: ToDgraphVar + Send + Sync,
+    {
+        let vars = vars.into_iter().fold(HashMap::new(), |mut tmp, (k, v)| {
+            tmp.insert(k.into(), v.to_dgraph_var());
+            tmp
+        });
+        self.query_with_vars(query, vars)
+    }
+
+    ///
+    /// Run query with several named blocks and return JSON data of every block by its name.
+    ///
+    /// # Arguments
+    ///
+    /// * `query`: GraphQL+- query
+    ///
+    /// # Errors
+    ///
+    /// If transaction is not initialized properly, return `EmptyTxn` error.
+    ///
+    /// If response is not JSON object, return `JsonDeserialize` error.
+    ///
+    /// gRPC errors can be returned also.
+    ///
+    fn query_blocks<Q>(&mut self, query: Q) -> Result<HashMap<String, Value>>
+    where
+        Q: Into<String> + Send + Sync,
+    {
+        Ok(self.query(query)?.try_into_owned()?)
+    }
 }
 
 impl<S: IState> Query for TxnVariant<S> {
@@ -333,7 +513,11 @@ impl<S: IState> Query for TxnVariant<S> {
         self.query_with_vars(query, HashMap::<String, String, _>::with_capacity(0))
     }
 
-    #[cfg(any(feature = "dgraph-1-1", feature = "dgraph-21-03"))]
+    #[cfg(any(
+        feature = "dgraph-1-1",
+        feature = "dgraph-21-03",
+        feature = "dgraph-24-02"
+    ))]
     fn query_rdf<Q>(&mut self, query: Q) -> Result<Response>
     where
         Q: Into<String> + Send + Sync,
@@ -350,7 +534,11 @@ impl<S: IState> Query for TxnVariant<S> {
         self.extra.query_with_vars(query, vars)
     }
 
-    #[cfg(any(feature = "dgraph-1-1", feature = "dgraph-21-03"))]
+    #[cfg(any(
+        feature = "dgraph-1-1",
+        feature = "dgraph-21-03",
+        feature = "dgraph-24-02"
+    ))]
     fn query_rdf_with_vars<Q, K, V>(&mut self, query: Q, vars: HashMap<K, V>) -> Result<Response>
     where
         Q: Into<String> + Send + Sync,
@@ -458,7 +646,11 @@ mod tests {
     }
 
     #[test]
-    #[cfg(any(feature = "dgraph-1-1", feature = "dgraph-21-03"))]
+    #[cfg(any(
+        feature = "dgraph-1-1",
+        feature = "dgraph-21-03",
+        feature = "dgraph-24-02"
+    ))]
     fn upsert() {
         let client = client();
         let mut txn = client.new_mutated_txn();
@@ -496,7 +688,11 @@ mod tests {
     }
 
     #[test]
-    #[cfg(any(feature = "dgraph-1-1", feature = "dgraph-21-03"))]
+    #[cfg(any(
+        feature = "dgraph-1-1",
+        feature = "dgraph-21-03",
+        feature = "dgraph-24-02"
+    ))]
     fn upsert_and_commit_now() {
         let client = client();
         let mut txn = client.new_mutated_txn();
@@ -534,7 +730,11 @@ mod tests {
     }
 
     #[test]
-    #[cfg(any(feature = "dgraph-1-1", feature = "dgraph-21-03"))]
+    #[cfg(any(
+        feature = "dgraph-1-1",
+        feature = "dgraph-21-03",
+        feature = "dgraph-24-02"
+    ))]
     fn upsert_with_vars() {
         let client = client();
         let mut txn = client.new_mutated_txn();
@@ -574,7 +774,11 @@ mod tests {
     }
 
     #[test]
-    #[cfg(any(feature = "dgraph-1-1", feature = "dgraph-21-03"))]
+    #[cfg(any(
+        feature = "dgraph-1-1",
+        feature = "dgraph-21-03",
+        feature = "dgraph-24-02"
+    ))]
     fn upsert_with_vars_and_commit_now() {
         let client = client();
         let mut txn = client.new_mutated_txn();
@@ -11,14 +11,18 @@ use tokio::runtime::Runtime;
 use crate::client::ILazyClient;
 use crate::sync::txn::{IState, Query, TxnType, TxnVariant};
 use crate::txn::mutated::Mutate as AsyncMutate;
-#[cfg(any(feature = "dgraph-1-1", feature = "dgraph-21-03"))]
-use crate::txn::mutated::UpsertMutation;
+#[cfg(any(
+    feature = "dgraph-1-1",
+    feature = "dgraph-21-03",
+    feature = "dgraph-24-02"
+))]
+use crate::txn::mutated::{split_uids, UpsertMutation};
 use crate::txn::TxnMutatedType as AsyncMutatedTxn;
 #[cfg(feature = "dgraph-1-0")]
 use crate::Assigned;
-use crate::Mutation;
 use crate::Query as AsyncQuery;
 use crate::Response;
+use crate::{Committed, Mutation};
 
 ///
 /// In Dgraph v1.0.x is mutation response represented as Assigned object
@@ -28,7 +32,11 @@ pub type MutationResponse = Assigned;
 ///
 /// In Dgraph v1.1.x is mutation response represented as Response object
 ///
-#[cfg(any(feature = "dgraph-1-1", feature = "dgraph-21-03"))]
+#[cfg(any(
+    feature = "dgraph-1-1",
+    feature = "dgraph-21-03",
+    feature = "dgraph-24-02"
+))]
 pub type MutationResponse = Response;
 
 ///
@@ -59,7 +67,11 @@ impl<C: ILazyClient> IState for Mutated<C> {
         })
     }
 
-    #[cfg(any(feature = "dgraph-1-1", feature = "dgraph-21-03"))]
+    #[cfg(any(
+        feature = "dgraph-1-1",
+        feature = "dgraph-21-03",
+        feature = "dgraph-24-02"
+    ))]
     fn query_rdf_with_vars<Q, K, V>(&mut self, query: Q, vars: HashMap<K, V>) -> Result<Response>
     where
         Q: Into<String> + Send + Sync,
@@ -118,11 +130,14 @@ pub trait Mutate: Query {
     ///
     /// Commit transaction
     ///
+    /// Returned `Committed` contains commit timestamp and uids of blank nodes created by all
+    /// mutations of transaction.
+    ///
     /// # Errors
     ///
     /// Return gRPC error.
     ///
-    fn commit(self) -> Result<()>;
+    fn commit(self) -> Result<Committed>;
 
     ///
     /// Adding or removing data in Dgraph is called a mutation.
@@ -182,6 +197,21 @@ pub trait Mutate: Query {
     ///
     fn mutate(&mut self, mu: Mutation) -> Result<MutationResponse>;
 
+    ///
+    /// Same as `mutate`, but mutation is borrowed, so it can be resubmitted, e.g. in new
+    /// transaction after previous one was aborted.
+    ///
+    /// # Arguments
+    ///
+    /// * `mu`: required mutations
+    ///
+    /// # Errors
+    ///
+    /// * `GrpcError`: there is error in communication or server does not accept mutation
+    /// * `MissingTxnContext`: there is error in txn setup
+    ///
+    fn mutate_ref(&mut self, mu: &Mutation) -> Result<MutationResponse>;
+
     ///
     /// Adding or removing data in Dgraph is called a mutation.
     ///
@@ -349,7 +379,11 @@ pub trait Mutate: Query {
     /// }
     /// ```
     ///
-    #[cfg(any(feature = "dgraph-1-1", feature = "dgraph-21-03"))]
+    #[cfg(any(
+        feature = "dgraph-1-1",
+        feature = "dgraph-21-03",
+        feature = "dgraph-24-02"
+    ))]
     fn upsert<Q, M>(&mut self, query: Q, mu: M) -> Result<MutationResponse>
     where
         Q: Into<String> + Send + Sync,
@@ -371,7 +405,11 @@ pub trait Mutate: Query {
     /// * `GrpcError`: there is error in communication or server does not accept mutation
     /// * `MissingTxnContext`: there is error in txn setup
     ///
-    #[cfg(any(feature = "dgraph-1-1", feature = "dgraph-21-03"))]
+    #[cfg(any(
+        feature = "dgraph-1-1",
+        feature = "dgraph-21-03",
+        feature = "dgraph-24-02"
+    ))]
     fn upsert_and_commit_now<Q, M>(self, query: Q, mu: M) -> Result<MutationResponse>
     where
         Q: Into<String> + Send + Sync,
@@ -490,7 +528,11 @@ pub trait Mutate: Query {
     /// }
     /// ```
     ///
-    #[cfg(any(feature = "dgraph-1-1", feature = "dgraph-21-03"))]
+    #[cfg(any(
+        feature = "dgraph-1-1",
+        feature = "dgraph-21-03",
+        feature = "dgraph-24-02"
+    ))]
     fn upsert_with_vars<Q, K, V, M>(
         &mut self,
         query: Q,
@@ -521,7 +563,11 @@ pub trait Mutate: Query {
     /// * `GrpcError`: there is error in communication or server does not accept mutation
     /// * `MissingTxnContext`: there is error in txn setup
     ///
-    #[cfg(any(feature = "dgraph-1-1", feature = "dgraph-21-03"))]
+    #[cfg(any(
+        feature = "dgraph-1-1",
+        feature = "dgraph-21-03",
+        feature = "dgraph-24-02"
+    ))]
     fn upsert_with_vars_and_commit_now<Q, K, V, M>(
         self,
         query: Q,
@@ -533,6 +579,134 @@ pub trait Mutate: Query {
         K: Into<String> + Send + Sync + Eq + Hash,
         V: Into<String> + Send + Sync,
         M: Into<UpsertMutation> + Send + Sync;
+
+    ///
+    /// Same as `upsert`, but uids of newly created blank nodes are returned separately from response.
+    ///
+    /// Returned map is empty when upsert query found existing nodes and no new node was created.
+    ///
+    /// # Arguments
+    ///
+    /// * `q`: Dgraph query
+    /// * `mu`: required mutations
+    ///
+    /// # Errors
+    ///
+    /// * `GrpcError`: there is error in communication or server does not accept mutation
+    /// * `MissingTxnContext`: there is error in txn setup
+    ///
+    #[cfg(any(
+        feature = "dgraph-1-1",
+        feature = "dgraph-21-03",
+        feature = "dgraph-24-02"
+    ))]
+    fn upsert_returning<Q, M>(
+        &mut self,
+        query: Q,
+        mu: M,
+    ) -> Result<(MutationResponse, HashMap<String, String>)>
+    where
+        Q: Into<String> + Send + Sync,
+        M: Into<UpsertMutation> + Send + Sync;
+
+    ///
+    /// Same as `upsert_and_commit_now`, but uids of newly created blank nodes are returned separately from response.
+    ///
+    /// Returned map is empty when upsert query found existing nodes and no new node was created.
+    ///
+    /// Transaction is committed.
+    ///
+    /// # Arguments
+    ///
+    /// * `q`: Dgraph query
+    /// * `mu`: required mutations
+    ///
+    /// # Errors
+    ///
+    /// * `GrpcError`: there is error in communication or server does not accept mutation
+    /// * `MissingTxnContext`: there is error in txn setup
+    ///
+    #[cfg(any(
+        feature = "dgraph-1-1",
+        feature = "dgraph-21-03",
+        feature = "dgraph-24-02"
+    ))]
+    fn upsert_returning_and_commit_now<Q, M>(
+        self,
+        query: Q,
+        mu: M,
+    ) -> Result<(MutationResponse, HashMap<String, String>)>
+    where
+        Q: Into<String> + Send + Sync,
+        M: Into<UpsertMutation> + Send + Sync;
+
+    ///
+    /// Same as `upsert_with_vars`, but uids of newly created blank nodes are returned separately from response.
+    ///
+    /// Returned map is empty when upsert query found existing nodes and no new node was created.
+    ///
+    /// # Arguments
+    ///
+    /// * `q`: Dgraph query
+    /// * `mu`: required mutations
+    /// * `vars`: query variables
+    ///
+    /// # Errors
+    ///
+    /// * `GrpcError`: there is error in communication or server does not accept mutation
+    /// * `MissingTxnContext`: there is error in txn setup
+    ///
+    #[cfg(any(
+        feature = "dgraph-1-1",
+        feature = "dgraph-21-03",
+        feature = "dgraph-24-02"
+    ))]
+    fn upsert_returning_with_vars<Q, K, V, M>(
+        &mut self,
+        query: Q,
+        vars: HashMap<K, V>,
+        mu: M,
+    ) -> Result<(MutationResponse, HashMap<String, String>)>
+    where
+        Q: Into<String> + Send + Sync,
+        K: Into<String> + Send + Sync + Eq + Hash,
+        V: Into<String> + Send + Sync,
+        M: Into<UpsertMutation> + Send + Sync;
+
+    ///
+    /// Same as `upsert_with_vars_and_commit_now`, but uids of newly created blank nodes are returned separately from response.
+    ///
+    /// Returned map is empty when upsert query found existing nodes and no new node was created.
+    ///
+    /// Transaction is committed.
+    ///
+    /// # Arguments
+    ///
+    /// * `q`: Dgraph query
+    /// * `mu`: required mutations
+    /// * `vars`: query variables
+    ///
+    /// # Errors
+    ///
+    /// * `GrpcError`: there is error in communication or server does not accept mutation
+    /// * `MissingTxnContext`: there is error in txn setup
+    ///
+    #[cfg(any(
+        feature = "dgraph-1-1",
+        feature = "dgraph-21-03",
+        feature = "dgraph-24-02"
+    ))]
+    fn upsert_returning_with_vars_and_commit_now<Q, K, V, M>(
+        self,
+        query: Q,
+        vars: HashMap<K, V>,
+        mu: M,
+    ) -> Result<(MutationResponse, HashMap<String, String>)>
+    where
+        Q: Into<String> + Send + Sync,
+        K: Into<String> + Send + Sync + Eq + Hash,
+        V: Into<String> + Send + Sync,
+        M: Into<UpsertMutation> + Send + Sync;
 }
 
 impl<C: ILazyClient> Mutate for TxnMutatedType<C> {
@@ -544,7 +718,7 @@ impl<C: ILazyClient> Mutate for TxnMutatedType<C> {
         })
     }
 
-    fn commit(self) -> Result<()> {
+    fn commit(self) -> Result<Committed> {
         let async_txn = self.extra.async_txn;
         self.extra.rt.block_on(async move {
             let async_txn = async_txn.lock().expect("MutatedTxn").to_owned();
@@ -560,6 +734,10 @@ impl<C: ILazyClient> Mutate for TxnMutatedType<C> {
         })
     }
 
+    fn mutate_ref(&mut self, mu: &Mutation) -> Result<MutationResponse> {
+        self.mutate(mu.clone())
+    }
+
     fn mutate_and_commit_now(self, mu: Mutation) -> Result<MutationResponse> {
         let async_txn = self.extra.async_txn;
         self.extra.rt.block_on(async move {
@@ -568,7 +746,11 @@ impl<C: ILazyClient> Mutate for TxnMutatedType<C> {
         })
     }
 
-    #[cfg(any(feature = "dgraph-1-1", feature = "dgraph-21-03"))]
+    #[cfg(any(
+        feature = "dgraph-1-1",
+        feature = "dgraph-21-03",
+        feature = "dgraph-24-02"
+    ))]
     fn upsert<Q, M>(&mut self, query: Q, mu: M) -> Result<MutationResponse>
     where
         Q: Into<String> + Send + Sync,
@@ -581,7 +763,11 @@ impl<C: ILazyClient> Mutate for TxnMutatedType<C> {
         })
     }
 
-    #[cfg(any(feature = "dgraph-1-1", feature = "dgraph-21-03"))]
+    #[cfg(any(
+        feature = "dgraph-1-1",
+        feature = "dgraph-21-03",
+        feature = "dgraph-24-02"
+    ))]
     fn upsert_and_commit_now<Q, M>(self, query: Q, mu: M) -> Result<MutationResponse>
     where
         Q: Into<String> + Send + Sync,
@@ -594,7 +780,11 @@ impl<C: ILazyClient> Mutate for TxnMutatedType<C> {
         })
     }
 
-    #[cfg(any(feature = "dgraph-1-1", feature = "dgraph-21-03"))]
+    #[cfg(any(
+        feature = "dgraph-1-1",
+        feature = "dgraph-21-03",
+        feature = "dgraph-24-02"
+    ))]
     fn upsert_with_vars<Q, K, V, M>(
         &mut self,
         query: Q,
@@ -614,7 +804,11 @@ impl<C: ILazyClient> Mutate for TxnMutatedType<C> {
         })
     }
 
-    #[cfg(any(feature = "dgraph-1-1", feature = "dgraph-21-03"))]
+    #[cfg(any(
+        feature = "dgraph-1-1",
+        feature = "dgraph-21-03",
+        feature = "dgraph-24-02"
+    ))]
     fn upsert_with_vars_and_commit_now<Q, K, V, M>(
         self,
         query: Q,
@@ -635,4 +829,178 @@ impl<C: ILazyClient> Mutate for TxnMutatedType<C> {
                 .await
         })
     }
+
+    #[cfg(any(
+        feature = "dgraph-1-1",
+        feature = "dgraph-21-03",
+        feature = "dgraph-24-02"
+    ))]
+    fn upsert_returning<Q, M>(
+        &mut self,
+        query: Q,
+        mu: M,
+    ) -> Result<(MutationResponse, HashMap<String, String>)>
+    where
+        Q: Into<String> + Send + Sync,
+        M: Into<UpsertMutation> + Send + Sync,
+    {
+        let response = self.upsert(query, mu)?;
+        Ok(split_uids(response))
+    }
+
+    #[cfg(any(
+        feature = "dgraph-1-1",
+        feature = "dgraph-21-03",
+        feature = "dgraph-24-02"
+    ))]
+    fn upsert_returning_and_commit_now<Q, M>(
+        self,
+        query: Q,
+        mu: M,
+    ) -> Result<(MutationResponse, HashMap<String, String>)>
+    where
+        Q: Into<String> + Send + Sync,
+        M: Into<UpsertMutation> + Send + Sync,
+    {
+        let response = self.upsert_and_commit_now(query, mu)?;
+        Ok(split_uids(response))
+    }
+
+    #[cfg(any(
+        feature = "dgraph-1-1",
+        feature = "dgraph-21-03",
+        feature = "dgraph-24-02"
+    ))]
+    fn upsert_returning_with_vars<Q, K, V, M>(
+        &mut self,
+        query: Q,
+        vars: HashMap<K, V>,
+        mu: M,
+    ) -> Result<(MutationResponse, HashMap<String, String>)>
+    where
+        Q: Into<String> + Send + Sync,
+        K: Into<String> + Send + Sync + Eq + Hash,
+        V: Into<String> + Send + Sync,
+        M: Into<UpsertMutation> + Send + Sync,
+    {
+        let response = self.upsert_with_vars(query, vars, mu)?;
+        Ok(split_uids(response))
+    }
+
+    #[cfg(any(
+        feature = "dgraph-1-1",
+        feature = "dgraph-21-03",
+        feature = "dgraph-24-02"
+    ))]
+    fn upsert_returning_with_vars_and_commit_now<Q, K, V, M>(
+        self,
+        query: Q,
+        vars: HashMap<K, V>,
+        mu: M,
+    ) -> Result<(MutationResponse, HashMap<String, String>)>
+    where
+        Q: Into<String> + Send + Sync,
+        K: Into<String> + Send + Sync + Eq + Hash,
+        V: Into<String> + Send + Sync,
+        M: Into<UpsertMutation> + Send + Sync,
+    {
+        let response = self.upsert_with_vars_and_commit_now(query, vars, mu)?;
+        Ok(split_uids(response))
+    }
+}
+
+impl<C: ILazyClient> TxnMutatedType<C> {
+    ///
+    /// Return conflict keys tracked by Dgraph for mutations of transaction.
+    ///
+    /// Keys are merged from all responses of transaction, sorted and deduplicated. Two
+    /// transactions with common conflict key cannot be both committed.
+    ///
+    pub fn conflict_keys(&self) -> Vec<String> {
+        self.extra
+            .async_txn
+            .lock()
+            .expect("MutatedTxn")
+            .conflict_keys()
+            .to_vec()
+    }
+
+    ///
+    /// Return predicates touched by mutations of transaction.
+    ///
+    /// Predicates are merged from all responses of transaction, sorted and deduplicated.
+    ///
+    pub fn predicates(&self) -> Vec<String> {
+        self.extra
+            .async_txn
+            .lock()
+            .expect("MutatedTxn")
+            .predicates()
+            .to_vec()
+    }
+
+    ///
+    /// Return true if transaction sent some mutation.
+    ///
+    /// Mutation is counted also when it failed, because Dgraph may have applied it before error
+    /// was returned. Commit of transaction without mutation returns immediately without request.
+    ///
+    pub fn has_mutated(&self) -> bool {
+        self.extra
+            .async_txn
+            .lock()
+            .expect("MutatedTxn")
+            .has_mutated()
+    }
+
+    ///
+    /// Submit every mutation in given order and return merged uids of all blank nodes.
+    ///
+    /// Transaction is left open, so it must be committed or discarded afterwards. Submitting
+    /// stops on first failed mutation.
+    ///
+    /// # Arguments
+    ///
+    /// * `mus`: mutations submitted one by one
+    ///
+    /// # Errors
+    ///
+    /// * `GrpcError`: there is error in communication or server does not accept mutation
+    /// * `MissingTxnContext`: there is error in txn setup
+    ///
+    pub fn mutate_all(&mut self, mus: Vec<Mutation>) -> Result<HashMap<String, String>> {
+        let async_txn = Arc::clone(&self.extra.async_txn);
+        self.extra.rt.block_on(async move {
+            let mut async_txn = async_txn.lock().expect("MutatedTxn");
+            async_txn.mutate_all(mus).await
+        })
+    }
+
+    ///
+    /// Send all mutations in one request and return merged response.
+    ///
+    /// Transaction is left open, so it must be committed or discarded afterwards. Empty batch is
+    /// not sent.
+    ///
+    /// # Arguments
+    ///
+    /// * `mus`: mutations sent together
+    ///
+    /// # Errors
+    ///
+    /// * `GrpcError`: there is error in communication or server does not accept mutation
+    /// * `MissingTxnContext`: there is error in txn setup
+    ///
+    #[cfg(any(
+        feature = "dgraph-1-1",
+        feature = "dgraph-21-03",
+        feature = "dgraph-24-02"
+    ))]
+    pub fn mutate_batch(&mut self, mus: Vec<Mutation>) -> Result<MutationResponse> {
+        let async_txn = Arc::clone(&self.extra.async_txn);
+        self.extra.rt.block_on(async move {
+            let mut async_txn = async_txn.lock().expect("MutatedTxn");
+            async_txn.mutate_batch(mus).await
+        })
+    }
 }
@@ -6,6 +6,8 @@ use std::sync::{Arc, Mutex};
 
 use anyhow::Result;
 use async_trait::async_trait;
+#[cfg(feature = "dgraph-24-02")]
+use serde::de::DeserializeOwned;
 use tokio::runtime::Runtime;
 
 use crate::client::ILazyClient;
@@ -41,7 +43,11 @@ impl<C: ILazyClient> IState for ReadOnly<C> {
         })
     }
 
-    #[cfg(any(feature = "dgraph-1-1", feature = "dgraph-21-03"))]
+    #[cfg(any(
+        feature = "dgraph-1-1",
+        feature = "dgraph-21-03",
+        feature = "dgraph-24-02"
+    ))]
     fn query_rdf_with_vars<Q, K, V>(&mut self, query: Q, vars: HashMap<K, V>) -> Result<Response>
     where
         Q: Into<String> + Send + Sync,
@@ -83,3 +89,89 @@ impl<C: ILazyClient> TxnType<C> {
         }
     }
 }
+
+impl<C: ILazyClient> TxnReadOnlyType<C> {
+    ///
+    /// Return start timestamp which was assigned by Dgraph to the first query of transaction.
+    ///
+    /// Timestamp is `0` before any query was executed.
+    ///
+    pub fn last_start_ts(&self) -> u64 {
+        self.extra.async_txn.lock().expect("Txn").last_start_ts()
+    }
+
+    ///
+    /// Enable or disable best effort mode for next queries of transaction.
+    ///
+    /// Unlike `best_effort()`, mode can be decided per query without creating new transaction.
+    ///
+    /// # Arguments
+    ///
+    /// - `best_effort`: if `true`, Dgraph Alpha tries to get timestamps from memory
+    ///
+    pub fn set_best_effort(&mut self, best_effort: bool) {
+        self.extra
+            .async_txn
+            .lock()
+            .expect("Txn")
+            .set_best_effort(best_effort)
+    }
+
+    ///
+    /// Return number of nodes matched by root function.
+    ///
+    /// Query `{ c(func: <func_query>) { count(uid) } }` is built and executed.
+    ///
+    /// # Arguments
+    ///
+    /// - `func_query`: root function, e.g. `has(name)` or `eq(name, "Alice")`
+    ///
+    /// # Errors
+    ///
+    /// * gRPC error
+    /// * If transaction is not initialized properly, return `EmptyTxn` error.
+    ///
+    pub fn count(&mut self, func_query: &str) -> Result<u64> {
+        let async_txn = Arc::clone(&self.extra.async_txn);
+        self.extra.rt.block_on(async move {
+            let mut async_txn = async_txn.lock().expect("Txn");
+            async_txn.count(func_query).await
+        })
+    }
+
+    ///
+    /// Return `top_k` nodes whose vector `predicate` is most similar to given `vector`.
+    ///
+    /// Query `{ q(func: similar_to(<predicate>, <top_k>, "[<vector>]")) { <block> } }` is built
+    /// and executed. Predicate must be of `float32vector` type with `hnsw` index.
+    ///
+    /// # Arguments
+    ///
+    /// - `predicate`: name of vector predicate
+    /// - `vector`: searched embedding
+    /// - `top_k`: maximal number of returned nodes
+    /// - `block`: selected predicates of returned nodes, e.g. `uid name`
+    ///
+    /// # Errors
+    ///
+    /// * gRPC error
+    /// * If transaction is not initialized properly, return `EmptyTxn` error.
+    /// * `InvalidPredicate` if predicate is empty or contains whitespace or reserved characters
+    /// * `InvalidVector` if component of vector is NaN or infinite
+    /// * `JsonDeserialize` if nodes cannot be deserialized into `T`
+    ///
+    #[cfg(feature = "dgraph-24-02")]
+    pub fn similar_to<T: DeserializeOwned>(
+        &mut self,
+        predicate: &str,
+        vector: &[f32],
+        top_k: usize,
+        block: &str,
+    ) -> Result<Vec<T>> {
+        let async_txn = Arc::clone(&self.extra.async_txn);
+        self.extra.rt.block_on(async move {
+            let mut async_txn = async_txn.lock().expect("Txn");
+            async_txn.similar_to(predicate, vector, top_k, block).await
+        })
+    }
+}
@@ -29,6 +29,10 @@ impl<C: ILazyClient> IState for BestEffort<C> {
         request.best_effort = true;
         request
     }
+
+    fn stale_retry(&self) -> bool {
+        self.read_only.stale_retry()
+    }
 }
 
 ///
@@ -48,6 +48,8 @@ impl<C: ILazyClient> TxnType<C> {
             state: Box::new(TxnState {
                 context: Default::default(),
                 stub,
+                #[cfg(feature = "debug-requests")]
+                last_request: None,
             }),
             extra: Base {
                 mark: PhantomData {},
@@ -3,23 +3,29 @@ use std::collections::HashMap;
 use std::hash::Hash;
 use std::marker::{Send, Sync};
 use std::ops::{Deref, DerefMut};
+use std::time::Instant;
 
 use anyhow::Result;
 use async_trait::async_trait;
+use serde_json::Value;
 
 use crate::client::ILazyClient;
 use crate::stub::Stub;
 pub use crate::txn::best_effort::TxnBestEffortType;
 pub use crate::txn::default::TxnType;
-pub use crate::txn::mutated::{Mutate, MutationResponse, TxnMutatedType};
+pub use crate::txn::mutated::{Committed, Mutate, MutationResponse, TxnMutatedType};
 pub use crate::txn::read_only::TxnReadOnlyType;
-use crate::{DgraphError, IDgraphClient};
+pub use crate::txn::read_pool::ReadPool;
+#[cfg(feature = "capture-metadata")]
+use crate::MetadataMap;
+use crate::{ClientError, DgraphError, IDgraphClient, RequestContext, ToDgraphVar};
 use crate::{Request, Response, TxnContext};
 
 pub(crate) mod best_effort;
 pub(crate) mod default;
 pub(crate) mod mutated;
 pub(crate) mod read_only;
+pub(crate) mod read_pool;
 
 ///
 /// Transaction state.
@@ -29,6 +35,8 @@ pub(crate) mod read_only;
 pub struct TxnState<C: ILazyClient> {
     stub: Stub<C>,
     context: TxnContext,
+    #[cfg(feature = "debug-requests")]
+    last_request: Option<Request>,
 }
 
 ///
@@ -41,6 +49,41 @@ pub trait IState: Send + Sync + Clone {
         query: String,
         vars: HashMap<String, String>,
     ) -> Request;
+
+    ///
+    /// Return `true` if query failed on too old read timestamp should be repeated with new one.
+    ///
+    fn stale_retry(&self) -> bool {
+        false
+    }
+}
+
+///
+/// Message of error returned by Dgraph when read timestamp of query is older than snapshot.
+///
+const STALE_READ_PATTERN: &str = "less than minTs";
+
+fn is_stale_read(err: &anyhow::Error) -> bool {
+    match err.downcast_ref::<ClientError>() {
+        Some(ClientError::CannotQuery(status)) => status.message().contains(STALE_READ_PATTERN),
+        _ => false,
+    }
+}
+
+///
+/// Return `InvalidPredicate` error when predicate name cannot be safely put into query.
+///
+#[cfg(any(
+    feature = "dgraph-1-1",
+    feature = "dgraph-21-03",
+    feature = "dgraph-24-02"
+))]
+pub(crate) fn check_predicate(predicate: &str) -> Result<(), DgraphError> {
+    let invalid = |c: char| c.is_whitespace() || c.is_control() || "^}|{\\`,<>\"".contains(c);
+    if predicate.is_empty() || predicate.contains(invalid) {
+        return Err(DgraphError::InvalidPredicate(predicate.to_string()));
+    }
+    Ok(())
 }
 
 ///
@@ -82,6 +125,77 @@ impl<S: IState, C: ILazyClient> TxnVariant<S, C> {
         result.context = Default::default();
         result
     }
+
+    ///
+    /// Set deadline shared by all following queries, mutations and commit of transaction.
+    ///
+    /// Every gRPC call gets timeout equal to the remaining time of budget, so one deadline
+    /// covers whole request handler instead of fixed timeout of every call. When budget is
+    /// exhausted, call fails with `Timeout` error. Earlier deadline is used when client has also
+    /// deadline source.
+    ///
+    /// # Arguments
+    ///
+    /// * `deadline`: instant after which no more calls are made
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use std::time::{Duration, Instant};
+    /// use dgraph_tonic::{Client, Mutate, Mutation, Query};
+    /// #[cfg(feature = "acl")]
+    /// use dgraph_tonic::{AclClientType, LazyChannel};
+    ///
+    /// #[cfg(not(feature = "acl"))]
+    /// async fn client() -> Client {
+    ///     Client::new("http://127.0.0.1:19080").expect("Dgraph client")
+    /// }
+    ///
+    /// #[cfg(feature = "acl")]
+    /// async fn client() -> AclClientType<LazyChannel> {
+    ///     let default = Client::new("http://127.0.0.1:19080").unwrap();
+    ///     default.login("groot", "password").await.expect("Acl client")
+    /// }
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     let deadline = Instant::now() + Duration::from_secs(5);
+    ///     let client = client().await;
+    ///     let mut txn = client.new_mutated_txn().with_deadline(deadline);
+    ///     txn.query("{ q(func: has(name)) { uid } }").await.expect("Query");
+    ///     let mut mu = Mutation::new();
+    ///     mu.set_set_nquads(r#"_:alice <name> "Alice" ."#);
+    ///     txn.mutate(mu).await.expect("Mutated");
+    ///     txn.commit().await.expect("Committed");
+    /// }
+    /// ```
+    ///
+    pub fn with_deadline(mut self, deadline: Instant) -> Self {
+        self.state.stub = self.state.stub.clone().with_deadline(Some(deadline));
+        self
+    }
+
+    ///
+    /// Return last query or mutation request sent by transaction.
+    ///
+    /// Request can be inspected to check flags like `read_only` or `best_effort` or final form
+    /// of upsert query. Mutations of Dgraph v1.0.x are not recorded.
+    ///
+    #[cfg(feature = "debug-requests")]
+    pub fn last_request(&self) -> Option<&Request> {
+        self.state.last_request.as_ref()
+    }
+
+    ///
+    /// Return headers and trailers of last query, mutation or commit response of transaction.
+    ///
+    /// Metadata of failed call are taken from returned status. Proxies in front of Dgraph can
+    /// report real cause of failure in them. Returns `None` before first call of transaction.
+    ///
+    #[cfg(feature = "capture-metadata")]
+    pub fn last_response_metadata(&self) -> Option<MetadataMap> {
+        self.stub.last_metadata().cloned()
+    }
 }
 
 ///
@@ -203,7 +317,11 @@ pub trait Query: Send + Sync {
     /// }
     /// ```
     ///
-    #[cfg(any(feature = "dgraph-1-1", feature = "dgraph-21-03"))]
+    #[cfg(any(
+        feature = "dgraph-1-1",
+        feature = "dgraph-21-03",
+        feature = "dgraph-24-02"
+    ))]
     async fn query_rdf<Q>(&mut self, query: Q) -> Result<Response>
     where
         Q: Into<String> + Send + Sync;
@@ -330,7 +448,11 @@ pub trait Query: Send + Sync {
     ///     println!("{}",String::from_utf8(resp.rdf).unwrap());
     /// }
     /// ```
-    #[cfg(any(feature = "dgraph-1-1", feature = "dgraph-21-03"))]
+    #[cfg(any(
+        feature = "dgraph-1-1",
+        feature = "dgraph-21-03",
+        feature = "dgraph-24-02"
+    ))]
     async fn query_rdf_with_vars<Q, K, V>(
         &mut self,
         query: Q,
@@ -340,6 +462,266 @@ pub trait Query: Send + Sync {
         Q: Into<String> + Send + Sync,
         K: Into<String> + Send + Sync + Eq + Hash,
         V: Into<String> + Send + Sync;
+
+    ///
+    /// Run query and return JSON data of response without deserialization.
+    ///
+    /// Data can be forwarded to another service as they are. Transaction context is updated in
+    /// same way like by `query`.
+    ///
+    /// # Arguments
+    ///
+    /// * `query`: GraphQL+- query
+    ///
+    /// # Errors
+    ///
+    /// If transaction is not initialized properly, return `EmptyTxn` error.
+    ///
+    /// gRPC errors can be returned also.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use dgraph_tonic::{Client, Query};
+    /// #[cfg(feature = "acl")]
+    /// use dgraph_tonic::{AclClientType, LazyChannel};
+    ///
+    /// #[cfg(not(feature = "acl"))]
+    /// async fn client() -> Client {
+    ///     Client::new("http://127.0.0.1:19080").expect("Dgraph client")
+    /// }
+    ///
+    /// #[cfg(feature = "acl")]
+    /// async fn client() -> AclClientType<LazyChannel> {
+    ///     let default = Client::new("http://127.0.0.1:19080").unwrap();
+    ///     default.login("groot", "password").await.expect("Acl client")
+    /// }
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     let client = client().await;
+    ///     let mut txn = client.new_read_only_txn();
+    ///     let json: Vec<u8> = txn
+    ///         .query_json("{ all(func: has(name)) { uid name } }")
+    ///         .await
+    ///         .expect("JSON");
+    ///     println!("{}", String::from_utf8(json).unwrap());
+    /// }
+    /// ```
+    ///
+    async fn query_json<Q>(&mut self, query: Q) -> Result<Vec<u8>>
+    where
+        Q: Into<String> + Send + Sync,
+    {
+        self.query_json_with_vars(query, HashMap::<String, String, _>::with_capacity(0))
+            .await
+    }
+
+    ///
+    /// Run query with variables and return JSON data of response without deserialization.
+    ///
+    /// # Arguments
+    ///
+    /// * `query`: GraphQL+- query
+    /// * `vars`: map of variables
+    ///
+    /// # Errors
+    ///
+    /// If transaction is not initialized properly, return `EmptyTxn` error.
+    ///
+    /// gRPC errors can be returned also.
+    ///
+    async fn query_json_with_vars<Q, K, V>(
+        &mut self,
+        query: Q,
+        vars: HashMap<K, V>,
+    ) -> Result<Vec<u8>>
+    where
+        Q: Into<String> + Send + Sync,
+        K: Into<String> + Send + Sync + Eq + Hash,
+        V: Into<String> + Send + Sync,
+    {
+        Ok(self.query_with_vars(query, vars).await?.json)
+    }
+
+    ///
+    /// Run query with variables which are not strings, e.g. numbers or bools.
+    ///
+    /// Values are formatted by `ToDgraphVar`, so Dgraph parses them according to types of
+    /// variables declared in query.
+    ///
+    /// # Arguments
+    ///
+    /// * `query`: GraphQL+- query
+    /// * `vars`: map of variables
+    ///
+    /// # Errors
+    ///
+    /// If transaction is not initialized properly, return `EmptyTxn` error.
+    ///
+    /// gRPC errors can be returned also.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use std::collections::HashMap;
+    /// use dgraph_tonic::{Client, Response, Query};
+    /// #[cfg(feature = "acl")]
+    /// use dgraph_tonic::{AclClientType, LazyChannel};
+    ///
+    /// #[cfg(not(feature = "acl"))]
+    /// async fn client() -> Client {
+    ///     Client::new("http://127.0.0.1:19080").expect("Dgraph client")
+    /// }
+    ///
+    /// #[cfg(feature = "acl")]
+    /// async fn client() -> AclClientType<LazyChannel> {
+    ///     let default = Client::new("http://127.0.0.1:19080").unwrap();
+    ///     default.login("groot", "password").await.expect("Acl client")
+    /// }
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     let q = r#"query all($first: int) {
+    ///         all(func: has(name), first: $first) {
+    ///         uid
+    ///         name
+    ///         }
+    ///     }"#;
+    ///
+    ///     let mut vars = HashMap::new();
+    ///     vars.insert("$first", 10);
+    ///
+    ///     let client = client().await;
+    ///     let mut txn = client.new_read_only_txn();
+    ///     let resp: Response = txn.query_with_typed_vars(q, vars).await.expect("query response");
+    /// }
+    /// ```
+    ///
+    async fn query_with_typed_vars<Q, K, V>(
+        &mut self,
+        query: Q,
+        vars: HashMap<K, V>,
+    ) -> Result<Response>
+    where
+        Q: Into<String> + Send + Sync,
+        K: Into<String> + Send + Sync + Eq + Hash,
+        V: ToDgraphVar + Send + Sync,
+    {
+        let vars = vars.into_iter().fold(HashMap::new(), |mut tmp, (k, v)| {
+            tmp.insert(k.into(), v.to_dgraph_var());
+            tmp
+        });
+        self.query_with_vars(query, vars).await
+    }
+
+    ///
+    /// Run query with several named blocks and return JSON data of every block by its name.
+    ///
+    /// Blocks can be picked at runtime, so there is no need for struct with all blocks.
+    ///
+    /// # Arguments
+    ///
+    /// * `query`: GraphQL+- query
+    ///
+    /// # Errors
+    ///
+    /// If transaction is not initialized properly, return `EmptyTxn` error.
+    ///
+    /// If response is not JSON object, return `JsonDeserialize` error.
+    ///
+    /// gRPC errors can be returned also.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use dgraph_tonic::{Client, Query};
+    /// #[cfg(feature = "acl")]
+    /// use dgraph_tonic::{AclClientType, LazyChannel};
+    ///
+    /// #[cfg(not(feature = "acl"))]
+    /// async fn client() -> Client {
+    ///     Client::new("http://127.0.0.1:19080").expect("Dgraph client")
+    /// }
+    ///
+    /// #[cfg(feature = "acl")]
+    /// async fn client() -> AclClientType<LazyChannel> {
+    ///     let default = Client::new("http://127.0.0.1:19080").unwrap();
+    ///     default.login("groot", "password").await.expect("Acl client")
+    /// }
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     let q = r#"{
+    ///         people(func: has(name)) { uid name }
+    ///         first(func: has(name), first: 1) { uid }
+    ///     }"#;
+    ///     let client = client().await;
+    ///     let mut txn = client.new_read_only_txn();
+    ///     let blocks = txn.query_blocks(q).await.expect("Blocks");
+    ///     println!("{}", blocks["people"]);
+    /// }
+    /// ```
+    ///
+    async fn query_blocks<Q>(&mut self, query: Q) -> Result<HashMap<String, Value>>
+    where
+        Q: Into<String> + Send + Sync,
+    {
+        Ok(self.query(query).await?.try_into_owned()?)
+    }
+}
+
+///
+/// Send query request without touching transaction context.
+///
+/// When stale query is repeated, start timestamp of request is reset to `0`.
+///
+async fn run_query<C: ILazyClient>(
+    stub: &mut Stub<C>,
+    request: &mut Request,
+    stale_retry: bool,
+) -> Result<Response> {
+    stub.check_deadline()?;
+    let mut response = stub.retry(|stub| stub.query(request.clone())).await;
+    if let Err(err) = &response {
+        if stale_retry && is_stale_read(err) {
+            request.start_ts = 0;
+            response = stub.retry(|stub| stub.query(request.clone())).await;
+        }
+    }
+    match response {
+        Ok(response) => Ok(response),
+        Err(err) => {
+            stub.check_deadline()?;
+            let context = RequestContext {
+                #[cfg(feature = "debug-requests")]
+                request: Some(request.clone()),
+                query: request.query.clone(),
+                start_ts: request.start_ts,
+            };
+            anyhow::bail!(DgraphError::GrpcError(err.context(context)))
+        }
+    }
+}
+
+impl<S: IState, C: ILazyClient> TxnVariant<S, C> {
+    async fn send_query(&mut self, mut request: Request) -> Result<Response> {
+        #[cfg(feature = "debug-requests")]
+        {
+            self.state.last_request = Some(request.clone());
+        }
+        let stale_retry = self.extra.stale_retry();
+        let response = run_query(&mut self.state.stub, &mut request, stale_retry).await;
+        if request.start_ts == 0 {
+            self.context.start_ts = 0;
+        }
+        let response = response?;
+        match response.txn.as_ref() {
+            Some(src) => self.context.merge_context(src)?,
+            None => anyhow::bail!(DgraphError::EmptyTxn),
+        };
+        Ok(response)
+    }
 }
 
 #[async_trait]
@@ -352,7 +734,11 @@ impl<S: IState, C: ILazyClient> Query for TxnVariant<S, C> {
             .await
     }
 
-    #[cfg(any(feature = "dgraph-1-1", feature = "dgraph-21-03"))]
+    #[cfg(any(
+        feature = "dgraph-1-1",
+        feature = "dgraph-21-03",
+        feature = "dgraph-24-02"
+    ))]
     async fn query_rdf<Q>(&mut self, query: Q) -> Result<Response>
     where
         Q: Into<String> + Send + Sync,
@@ -372,18 +758,14 @@ impl<S: IState, C: ILazyClient> Query for TxnVariant<S, C> {
             tmp
         });
         let request = self.extra.query_request(&self.state, query.into(), vars);
-        let response = match self.stub.query(request).await {
-            Ok(response) => response,
-            Err(err) => anyhow::bail!(DgraphError::GrpcError(err)),
-        };
-        match response.txn.as_ref() {
-            Some(src) => self.context.merge_context(src)?,
-            None => anyhow::bail!(DgraphError::EmptyTxn),
-        };
-        Ok(response)
+        self.send_query(request).await
     }
 
-    #[cfg(any(feature = "dgraph-1-1", feature = "dgraph-21-03"))]
+    #[cfg(any(
+        feature = "dgraph-1-1",
+        feature = "dgraph-21-03",
+        feature = "dgraph-24-02"
+    ))]
     async fn query_rdf_with_vars<Q, K, V>(
         &mut self,
         query: Q,
@@ -400,15 +782,7 @@ impl<S: IState, C: ILazyClient> Query for TxnVariant<S, C> {
         });
         let mut request = self.extra.query_request(&self.state, query.into(), vars);
         request.resp_format = crate::api::request::RespFormat::Rdf as i32;
-        let response = match self.stub.query(request).await {
-            Ok(response) => response,
-            Err(err) => anyhow::bail!(DgraphError::GrpcError(err)),
-        };
-        match response.txn.as_ref() {
-            Some(src) => self.context.merge_context(src)?,
-            None => anyhow::bail!(DgraphError::EmptyTxn),
-        };
-        Ok(response)
+        self.send_query(request).await
     }
 }
 
@@ -422,7 +796,7 @@ mod tests {
     use crate::client::Client;
     #[cfg(feature = "acl")]
     use crate::client::{AclClientType, LazyChannel};
-    use crate::{Mutate, Mutation};
+    use crate::{ClientError, Mutate, Mutation};
 
     use super::*;
 
@@ -453,8 +827,119 @@ mod tests {
         pub uid: String,
     }
 
-    async fn insert_data() {
-        let client = client().await;
+    ///
+    /// Query which implements only required methods and returns canned JSON.
+    ///
+    #[derive(Default)]
+    struct CannedQuery {
+        json: Vec<u8>,
+        vars: Vec<HashMap<String, String>>,
+    }
+
+    #[async_trait]
+    impl Query for CannedQuery {
+        async fn query<Q>(&mut self, query: Q) -> Result<Response>
+        where
+            Q: Into<String> + Send + Sync,
+        {
+            self.query_with_vars(query, HashMap::<String, String>::new())
+                .await
+        }
+
+        #[cfg(any(
+            feature = "dgraph-1-1",
+            feature = "dgraph-21-03",
+            feature = "dgraph-24-02"
+        ))]
+        async fn query_rdf<Q>(&mut self, query: Q) -> Result<Response>
+        where
+            Q: Into<String> + Send + Sync,
+        {
+            self.query(query).await
+        }
+
+        async fn query_with_vars<Q, K, V>(
+            &mut self,
+            _query: Q,
+            vars: HashMap<K, V>,
+        ) -> Result<Response>
+        where
+            Q: Into<String> + Send + Sync,
+            K: Into<String> + Send + Sync + Eq + Hash,
+            V: Into<String> + Send + Sync,
+        {
+            let vars = vars.into_iter().map(|(k, v)| (k.into(), v.into()));
+            self.vars.push(vars.collect());
+            Ok(Response {
+                json: self.json.clone(),
+                ..Default::default()
+            })
+        }
+
+        #[cfg(any(
+            feature = "dgraph-1-1",
+            feature = "dgraph-21-03",
+            feature = "dgraph-24-02"
+        ))]
+        async fn query_rdf_with_vars<Q, K, V>(
+            &mut self,
+            query: Q,
+            vars: HashMap<K, V>,
+        ) -> Result<Response>
+        where
+            Q: Into<String> + Send + Sync,
+            K: Into<String> + Send + Sync + Eq + Hash,
+            V: Into<String> + Send + Sync,
+        {
+            self.query_with_vars(query, vars).await
+        }
+    }
+
+    #[tokio::test]
+    async fn default_query_json() {
+        let mut query = CannedQuery {
+            json: br#"{"q":[]}"#.to_vec(),
+            ..Default::default()
+        };
+        let json = query.query_json("{ q(func: has(name)) { uid } }").await;
+        assert_eq!(json.expect("JSON"), br#"{"q":[]}"#.to_vec());
+        let mut vars = HashMap::new();
+        vars.insert("$a", "Alice");
+        let json = query
+            .query_json_with_vars("query q($a: string) {}", vars)
+            .await;
+        assert_eq!(json.expect("JSON"), br#"{"q":[]}"#.to_vec());
+        assert_eq!(query.vars[1]["$a"], "Alice");
+    }
+
+    #[tokio::test]
+    async fn default_query_with_typed_vars() {
+        let mut query = CannedQuery::default();
+        let mut vars = HashMap::new();
+        vars.insert("$first", 10);
+        query
+            .query_with_typed_vars("query q($first: int) {}", vars)
+            .await
+            .expect("Response");
+        assert_eq!(query.vars[0]["$first"], "10");
+    }
+
+    #[tokio::test]
+    async fn default_query_blocks() {
+        let mut query = CannedQuery {
+            json: br#"{"q":[{"uid":"0x1"}],"count":[]}"#.to_vec(),
+            ..Default::default()
+        };
+        let blocks = query
+            .query_blocks("{ q(func: uid(0x1)) { uid } count(func: has(name)) { uid } }")
+            .await
+            .expect("Blocks");
+        assert_eq!(blocks.len(), 2);
+        assert_eq!(blocks["q"][0]["uid"], "0x1");
+    }
+
+    async fn insert_data() {
+        let client = client().await;
         let txn = client.new_mutated_txn();
         let p = Person {
             uid: "_:alice".to_string(),
@@ -480,6 +965,23 @@ mod tests {
         assert!(response.is_ok());
     }
 
+    #[cfg(feature = "gzip")]
+    #[tokio::test]
+    async fn mutate_with_compression() {
+        let client = client().await;
+        let txn = client
+            .new_mutated_txn()
+            .with_compression(crate::Compression::Gzip);
+        let p = Person {
+            uid: "_:alice".to_string(),
+            name: "Alice".to_string(),
+        };
+        let mut mu = Mutation::new();
+        mu.set_set_json(&p).expect("Invalid JSON");
+        let response = txn.mutate_and_commit_now(mu).await;
+        assert!(response.is_ok());
+    }
+
     #[tokio::test]
     async fn commit() {
         let client = client().await;
@@ -507,7 +1009,604 @@ mod tests {
         assert!(commit.is_ok())
     }
 
-    #[cfg(any(feature = "dgraph-1-1", feature = "dgraph-21-03"))]
+    #[tokio::test]
+    async fn mutate_all() {
+        let client = client().await;
+        let mut txn = client.new_mutated_txn();
+        let mut mus = Vec::new();
+        for name in &["Alice", "Mike"] {
+            let p = Person {
+                uid: format!("_:{}", name),
+                name: name.to_string(),
+            };
+            let mut mu = Mutation::new();
+            mu.set_set_json(&p).expect("Invalid JSON");
+            mus.push(mu);
+        }
+        let uids = txn.mutate_all(mus).await.expect("Mutated");
+        assert!(uids.contains_key("Alice"));
+        assert!(uids.contains_key("Mike"));
+        let commit = txn.commit().await;
+        assert!(commit.is_ok())
+    }
+
+    #[cfg(any(
+        feature = "dgraph-1-1",
+        feature = "dgraph-21-03",
+        feature = "dgraph-24-02"
+    ))]
+    #[tokio::test]
+    async fn mutated_txn_audit_log() {
+        let client = client().await;
+        let mut txn = client.new_txn().with_audit();
+        let p = Person {
+            uid: "_:alice".to_string(),
+            name: "Alice".to_string(),
+        };
+        let mut mu = Mutation::new();
+        mu.set_set_json(&p).expect("Invalid JSON");
+        txn.mutate(mu).await.expect("Mutated");
+        assert_eq!(txn.audit_log().len(), 1);
+        assert_eq!(txn.audit_log()[0].mutations.len(), 1);
+        assert!(!txn.audit_log()[0].commit_now);
+        let default = client.new_mutated_txn();
+        assert!(default.audit_log().is_empty());
+        txn.commit().await.expect("Committed");
+    }
+
+    #[tokio::test]
+    async fn read_only_txn_stale_retry() {
+        let client = Client::new("http://127.0.0.1:19080").unwrap();
+        let txn = client.new_read_only_txn();
+        assert!(!txn.extra.stale_retry());
+        let txn = txn.with_stale_retry();
+        assert!(txn.extra.stale_retry());
+        assert!(txn.best_effort().extra.stale_retry());
+        let stale = ClientError::CannotQuery(crate::Status::unknown(
+            "readTs: 10 less than minTs: 20 for key: \"name\"",
+        ))
+        .into();
+        assert!(is_stale_read(&stale));
+        let other =
+            ClientError::CannotQuery(crate::Status::unknown("Transaction has been aborted")).into();
+        assert!(!is_stale_read(&other));
+    }
+
+    #[tokio::test]
+    async fn read_only_txn_snapshot() {
+        let client = client().await;
+        insert_data().await;
+        let mut txn = client.new_read_only_txn();
+        let query = "{ uids(func: has(name)) { uid } }";
+        let first = txn.query(query).await.expect("First query");
+        let start_ts = txn.last_start_ts();
+        assert_ne!(start_ts, 0);
+        let request = txn
+            .extra
+            .query_request(&txn.state, query.to_string(), HashMap::new());
+        assert_eq!(request.start_ts, start_ts);
+        let second = txn.query(query).await.expect("Second query");
+        assert_eq!(first.txn.unwrap().start_ts, start_ts);
+        assert_eq!(second.txn.unwrap().start_ts, start_ts);
+        assert_eq!(txn.last_start_ts(), start_ts);
+    }
+
+    ///
+    /// Start fake gRPC server which records decoded requests.
+    ///
+    /// Response contains only txn context with start timestamp of request and `x-recorded`
+    /// trailer. New timestamp equal to number of received requests is assigned when request has
+    /// none.
+    ///
+    pub(super) async fn recording_server(
+    ) -> (String, std::sync::Arc<std::sync::Mutex<Vec<Request>>>) {
+        use std::sync::{Arc, Mutex};
+
+        use crate::fake_server::{serve, FakeReply};
+
+        let received = Arc::new(Mutex::new(Vec::new()));
+        let recorder = Arc::clone(&received);
+        let (addr, _) = serve(move |call| {
+            let request: Request = call.message();
+            let mut received = recorder.lock().unwrap();
+            let start_ts = match request.start_ts {
+                0 => received.len() as u64 + 1,
+                start_ts => start_ts,
+            };
+            received.push(request);
+            let response = Response {
+                txn: Some(TxnContext {
+                    start_ts,
+                    ..Default::default()
+                }),
+                ..Default::default()
+            };
+            FakeReply::new(&response).with_trailer("x-recorded", "true")
+        })
+        .await;
+        (addr, received)
+    }
+
+    #[tokio::test]
+    async fn query_with_directives_is_verbatim() {
+        let (addr, received) = recording_server().await;
+        let client = Client::new(addr).unwrap();
+        let queries = [
+            "{\n  q(func: has(name)) @cascade {\n    name\n    friend @filter(has(age)) { age }\n  }\n}\n\n",
+            "  query q($a: string) {\n\tq(func: eq(name, $a)) @filter(NOT has(age)) { uid }\r\n}",
+        ];
+        for query in queries.iter() {
+            let mut txn = client.new_read_only_txn();
+            let _ = txn.query(*query).await;
+        }
+        let received = received.lock().unwrap();
+        let sent: Vec<&str> = received
+            .iter()
+            .map(|request| request.query.as_str())
+            .collect();
+        assert_eq!(sent, queries);
+    }
+
+    #[cfg(any(
+        feature = "dgraph-1-1",
+        feature = "dgraph-21-03",
+        feature = "dgraph-24-02"
+    ))]
+    #[tokio::test]
+    async fn upsert_query_ends_with_newline() {
+        let (addr, received) = recording_server().await;
+        let client = Client::new(addr).unwrap();
+        let query = "query {\n  user as var(func: eq(email, \"a\")) @cascade\n}";
+        let mu = Mutation::from_set_nquads(r#"uid(user) <email> "a" ."#);
+        let mut txn = client.new_mutated_txn();
+        let _ = txn.upsert(query, mu.clone()).await;
+        let mut txn = client.new_mutated_txn();
+        let _ = txn.upsert(format!("{}\n", query), mu).await;
+        let received = received.lock().unwrap();
+        assert_eq!(received[0].query, format!("{}\n", query));
+        assert_eq!(received[1].query, format!("{}\n", query));
+    }
+
+    #[cfg(any(
+        feature = "dgraph-1-1",
+        feature = "dgraph-21-03",
+        feature = "dgraph-24-02"
+    ))]
+    #[tokio::test]
+    async fn mutate_batch_sends_one_request() {
+        let (addr, received) = recording_server().await;
+        let client = Client::new(addr).unwrap();
+        let mus = vec![
+            Mutation::from_set_nquads(r#"_:alice <name> "Alice" ."#),
+            Mutation::from_set_nquads(r#"_:bob <name> "Bob" ."#),
+        ];
+        let mut txn = client.new_mutated_txn();
+        let _ = txn.mutate_batch(mus.clone()).await;
+        txn.mutate_batch(Vec::new()).await.expect("Empty batch");
+        let received = received.lock().unwrap();
+        assert_eq!(received.len(), 1);
+        assert_eq!(received[0].mutations, mus);
+        assert!(!received[0].commit_now);
+    }
+
+    #[cfg(feature = "capture-metadata")]
+    #[tokio::test]
+    async fn last_response_metadata() {
+        let (addr, _) = recording_server().await;
+        let client = Client::new(addr).unwrap();
+        let mut txn = client.new_read_only_txn();
+        assert!(txn.last_response_metadata().is_none());
+        txn.query("{ q(func: uid(0x1)) { uid } }")
+            .await
+            .expect("Query");
+        let metadata = txn.last_response_metadata().expect("Metadata");
+        assert_eq!(metadata.get("x-recorded").unwrap(), "true");
+    }
+
+    #[cfg(feature = "debug-requests")]
+    #[tokio::test]
+    async fn last_request() {
+        let client = Client::new("http://127.0.0.1:1").unwrap();
+        let mut txn = client.new_read_only_txn().best_effort();
+        assert!(txn.last_request().is_none());
+        let _ = txn.query("{ q(func: uid(0x1)) { uid } }").await;
+        let request = txn.last_request().expect("Request");
+        assert!(request.read_only);
+        assert!(request.best_effort);
+        assert_eq!(request.query, "{ q(func: uid(0x1)) { uid } }");
+    }
+
+    #[cfg(any(
+        feature = "dgraph-1-1",
+        feature = "dgraph-21-03",
+        feature = "dgraph-24-02"
+    ))]
+    #[tokio::test]
+    async fn mutate_batch() {
+        let client = client().await;
+        let mus = vec![
+            Mutation::from_set_nquads(r#"_:alice <name> "Alice" ."#),
+            Mutation::from_set_nquads(r#"_:bob <name> "Bob" ."#),
+        ];
+        let mut txn = client.new_mutated_txn();
+        let response = txn.mutate_batch(mus).await.expect("Mutated");
+        assert!(response.uids.contains_key("alice"));
+        assert!(response.uids.contains_key("bob"));
+        txn.commit().await.expect("Committed");
+    }
+
+    #[tokio::test]
+    async fn mutate_ref() {
+        let client = client().await;
+        let mut mu = Mutation::new();
+        mu.set_set_nquads(r#"_:alice <name> "Alice" ."#);
+        let mut txn = client.new_mutated_txn();
+        txn.mutate_ref(&mu).await.expect("First mutation");
+        txn.discard().await.expect("Discarded");
+        let mut txn = client.new_mutated_txn();
+        let response = txn.mutate_ref(&mu).await.expect("Resubmitted mutation");
+        assert!(response.uids.contains_key("alice"));
+        txn.commit().await.expect("Committed");
+    }
+
+    #[cfg(any(
+        feature = "dgraph-1-1",
+        feature = "dgraph-21-03",
+        feature = "dgraph-24-02"
+    ))]
+    #[tokio::test]
+    async fn affected_count() {
+        let client = client().await;
+        let mut mu = Mutation::new();
+        mu.set_set_nquads(
+            r#"_:alice <name> "Alice" .
+            _:bob <name> "Bob" ."#,
+        );
+        let txn = client.new_mutated_txn();
+        let response = txn.mutate_and_commit_now(mu).await.expect("Inserted");
+        let mut mu = Mutation::new();
+        mu.set_delete_nquads(format!(
+            r#"<{}> <name> "Alice" .
+            <{}> <name> "Bob" ."#,
+            response.uids["alice"], response.uids["bob"]
+        ));
+        let txn = client.new_mutated_txn();
+        let response = txn.mutate_and_commit_now(mu).await.expect("Deleted");
+        assert_eq!(response.affected_count(), 2);
+    }
+
+    #[tokio::test]
+    async fn commit_returns_committed() {
+        let client = client().await;
+        let mut txn = client.new_mutated_txn();
+        let mut mu = Mutation::new();
+        mu.set_set_nquads(r#"_:alice <name> "Alice" ."#);
+        txn.mutate(mu).await.expect("First mutation");
+        let mut mu = Mutation::new();
+        mu.set_set_nquads(r#"_:bob <name> "Bob" ."#);
+        txn.mutate(mu).await.expect("Second mutation");
+        let committed = txn.commit().await.expect("Committed");
+        assert!(committed.commit_ts > 0);
+        assert!(committed.uids.contains_key("alice"));
+        assert!(committed.uids.contains_key("bob"));
+    }
+
+    #[tokio::test]
+    async fn commit_without_mutation() {
+        let client = Client::new("http://127.0.0.1:19080").unwrap();
+        let txn = client.new_mutated_txn();
+        let committed = txn.commit().await.expect("Committed");
+        assert_eq!(committed, Committed::default());
+    }
+
+    #[tokio::test]
+    async fn has_mutated() {
+        let client = Client::new("http://127.0.0.1:1").unwrap();
+        let mut txn = client.new_mutated_txn();
+        assert!(!txn.has_mutated());
+        let mut mu = Mutation::new();
+        mu.set_set_nquads(r#"_:alice <name> "Alice" ."#);
+        assert!(txn.mutate(mu).await.is_err());
+        assert!(txn.has_mutated());
+    }
+
+    #[tokio::test]
+    async fn txn_with_expired_deadline() {
+        let client = Client::new("http://127.0.0.1:19080").unwrap();
+        let deadline = Instant::now() - Duration::from_secs(1);
+        let mut txn = client.new_mutated_txn().with_deadline(deadline);
+        let err = txn.query("{ q(func: uid(0x1)) { uid } }").await;
+        assert!(matches!(
+            err.expect_err("Timeout").downcast_ref::<DgraphError>(),
+            Some(DgraphError::Timeout)
+        ));
+        let mut mu = Mutation::new();
+        mu.set_set_nquads(r#"_:alice <name> "Alice" ."#);
+        let err = txn.mutate(mu).await;
+        assert!(matches!(
+            err.expect_err("Timeout").downcast_ref::<DgraphError>(),
+            Some(DgraphError::Timeout)
+        ));
+    }
+
+    #[tokio::test]
+    async fn txn_with_deadline() {
+        let client = client().await;
+        let deadline = Instant::now() + Duration::from_secs(5);
+        let mut txn = client.new_mutated_txn().with_deadline(deadline);
+        txn.query("{ q(func: uid(0x1)) { uid } }")
+            .await
+            .expect("Query");
+        let mut mu = Mutation::new();
+        mu.set_set_nquads(r#"_:alice <name> "Alice" ."#);
+        txn.mutate(mu).await.expect("Mutated");
+        txn.commit().await.expect("Committed");
+    }
+
+    #[cfg(any(
+        feature = "dgraph-1-1",
+        feature = "dgraph-21-03",
+        feature = "dgraph-24-02"
+    ))]
+    #[tokio::test]
+    async fn get_or_create() {
+        let client = client().await;
+        client
+            .set_schema("email: string @index(exact) .")
+            .await
+            .expect("Schema is not updated");
+        let email = format!("{}\"@example.com", rand::random::<u64>());
+        let created = client
+            .new_mutated_txn()
+            .get_or_create("email", &email, r#"_:node <name> "Alice" ."#)
+            .await
+            .expect("Created");
+        let found = client
+            .new_mutated_txn()
+            .get_or_create("email", &email, r#"_:node <name> "Alice" ."#)
+            .await
+            .expect("Found");
+        assert_eq!(created, found);
+    }
+
+    #[cfg(any(
+        feature = "dgraph-1-1",
+        feature = "dgraph-21-03",
+        feature = "dgraph-24-02"
+    ))]
+    #[tokio::test]
+    async fn upsert_by_key() {
+        let client = client().await;
+        client
+            .set_schema("email: string @index(exact) @upsert .")
+            .await
+            .expect("Schema is not updated");
+        let email = format!("{}@example.com", rand::random::<u64>());
+        let mut uids = Vec::new();
+        for name in ["Alice", "Alicia"].iter() {
+            let mut txn = client.new_mutated_txn();
+            let person = Person {
+                uid: String::new(),
+                name: name.to_string(),
+            };
+            uids.push(
+                txn.upsert_by_key("email", &email, &person)
+                    .await
+                    .expect("Uid"),
+            );
+            txn.commit().await.expect("Committed");
+        }
+        assert_eq!(uids[0], uids[1]);
+        let query = format!(
+            r#"{{ people(func: eq(email, "{}")) {{ uid name }} }}"#,
+            email
+        );
+        let mut txn = client.new_read_only_txn();
+        let people: HashMap<String, Vec<Person>> = txn
+            .query(query)
+            .await
+            .expect("Query")
+            .try_into_owned()
+            .expect("JSON");
+        assert_eq!(people["people"].len(), 1);
+        assert_eq!(people["people"][0].name, "Alicia");
+    }
+
+    #[cfg(any(
+        feature = "dgraph-1-1",
+        feature = "dgraph-21-03",
+        feature = "dgraph-24-02"
+    ))]
+    #[tokio::test]
+    async fn upsert_if_absent_or_present() {
+        let client = client().await;
+        client
+            .set_schema("email: string @index(exact) .\nname: string @index(exact) .")
+            .await
+            .expect("Schema is not updated");
+        let email = format!("{}@example.com", rand::random::<u64>());
+        let query = format!(r#"{{ user as var(func: eq(email, "{}")) }}"#, email);
+        let create = Mutation::from_set_nquads(format!(r#"_:user <email> "{}" ."#, email));
+        let update = Mutation::from_set_nquads(r#"uid(user) <name> "Updated" ."#);
+        // user does not exist, so it is created and nothing is updated
+        let mut txn = client.new_mutated_txn();
+        txn.upsert_if_present(query.as_str(), "user", update.clone())
+            .await
+            .expect("Skipped update");
+        let created = txn
+            .upsert_if_absent(query.as_str(), "user", create.clone())
+            .await
+            .expect("Created");
+        assert!(created.uids.contains_key("user"));
+        txn.commit().await.expect("Committed");
+        // user exists, so it is updated and nothing is created
+        let mut txn = client.new_mutated_txn();
+        let skipped = txn
+            .upsert_if_absent(query.as_str(), "user", create)
+            .await
+            .expect("Skipped create");
+        assert!(skipped.uids.is_empty());
+        txn.upsert_if_present(query.as_str(), "user", update)
+            .await
+            .expect("Updated");
+        txn.commit().await.expect("Committed");
+        let query = format!(r#"{{ q(func: eq(email, "{}")) {{ name }} }}"#, email);
+        let response = client
+            .new_read_only_txn()
+            .query(query)
+            .await
+            .expect("Response");
+        let users: Vec<HashMap<String, String>> = response.try_into_block("q").expect("Users");
+        assert_eq!(users.len(), 1);
+        assert_eq!(users[0]["name"], "Updated");
+    }
+
+    #[tokio::test]
+    async fn mutated_query_request_start_ts() {
+        let client = Client::new("http://127.0.0.1:19080").unwrap();
+        let mut txn = client.new_mutated_txn();
+        txn.context.start_ts = 42;
+        let request = txn
+            .extra
+            .query_request(&txn.state, String::new(), HashMap::new());
+        assert_eq!(request.start_ts, 42);
+    }
+
+    #[tokio::test]
+    async fn read_your_writes() {
+        let client = client().await;
+        client
+            .set_schema("name: string @index(exact) .")
+            .await
+            .expect("Schema is not updated");
+        let name = format!("Alice {}", rand::random::<u64>());
+        let mut txn = client.new_mutated_txn();
+        let mut mu = Mutation::new();
+        mu.set_set_nquads(format!(r#"_:alice <name> "{}" ."#, name));
+        txn.mutate(mu).await.expect("Mutated");
+        let query = r#"query all($a: string) {
+            uids(func: eq(name, $a)) {
+              uid
+            }
+          }"#;
+        let mut vars = HashMap::new();
+        vars.insert("$a", name.as_str());
+        let response = txn.query_with_vars(query, vars.clone()).await;
+        let json: UidJson = response.expect("Response").try_into().unwrap();
+        assert_eq!(json.uids.len(), 1);
+        let mut other = client.new_read_only_txn();
+        let response = other.query_with_vars(query, vars).await;
+        let json: UidJson = response.expect("Response").try_into().unwrap();
+        assert!(json.uids.is_empty());
+        txn.discard().await.expect("Discarded");
+    }
+
+    #[tokio::test]
+    async fn commit_after_cancelled_mutation() {
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        let server = tokio::spawn(async move {
+            // connection is accepted, but no response is ever sent
+            let (_socket, _) = listener.accept().await.unwrap();
+            std::future::pending::<()>().await;
+        });
+        let client = Client::new(format!("http://{}", addr)).unwrap();
+        let mut txn = client.new_mutated_txn();
+        let mut mu = Mutation::new();
+        mu.set_set_nquads(r#"_:alice <name> "Alice" ."#);
+        let cancelled = tokio::time::timeout(Duration::from_millis(100), txn.mutate(mu)).await;
+        assert!(cancelled.is_err());
+        let err = txn.commit().await.expect_err("Inconsistent txn");
+        assert!(matches!(
+            err.downcast_ref::<DgraphError>(),
+            Some(DgraphError::InconsistentTxn)
+        ));
+        server.abort();
+    }
+
+    #[tokio::test]
+    async fn mutate_message_too_large() {
+        let client = Client::new("http://127.0.0.1:19080")
+            .unwrap()
+            .with_max_message_size(64);
+        let mut txn = client.new_mutated_txn();
+        let p = Person {
+            uid: "_:alice".to_string(),
+            name: "Alice".repeat(20),
+        };
+        let mut mu = Mutation::new();
+        mu.set_set_json(&p).expect("Invalid JSON");
+        let err = txn.mutate(mu).await.expect_err("Too large");
+        assert!(matches!(
+            err.downcast_ref::<ClientError>(),
+            Some(ClientError::MessageTooLarge { limit: 64, .. })
+        ));
+    }
+
+    #[tokio::test]
+    async fn mutated_txn_conflict_keys_and_predicates() {
+        let client = Client::new("http://127.0.0.1:19080").unwrap();
+        let mut txn = client.new_mutated_txn();
+        assert!(txn.conflict_keys().is_empty());
+        let first = TxnContext {
+            start_ts: 1,
+            keys: vec!["b".to_string(), "a".to_string()],
+            preds: vec!["1-name".to_string()],
+            ..Default::default()
+        };
+        let second = TxnContext {
+            start_ts: 1,
+            keys: vec!["a".to_string()],
+            preds: vec!["1-age".to_string()],
+            ..Default::default()
+        };
+        txn.context.merge_context(&first).unwrap();
+        txn.context.merge_context(&second).unwrap();
+        assert_eq!(txn.conflict_keys(), ["a", "b"]);
+        assert_eq!(txn.predicates(), ["1-age", "1-name"]);
+    }
+
+    #[tokio::test]
+    async fn commit_with_timeout() {
+        let client = client().await;
+        let mut txn = client.new_mutated_txn();
+        let p = Person {
+            uid: "_:alice".to_string(),
+            name: "Alice".to_string(),
+        };
+        let mut mu = Mutation::new();
+        mu.set_set_json(&p).expect("Invalid JSON");
+        let response = txn.mutate(mu).await;
+        assert!(response.is_ok());
+        let commit = txn.commit_with_timeout(Duration::from_secs(5)).await;
+        assert!(commit.is_ok())
+    }
+
+    #[tokio::test]
+    async fn discard_with_expired_timeout() {
+        let client = client().await;
+        let mut txn = client.new_mutated_txn();
+        let p = Person {
+            uid: "_:alice".to_string(),
+            name: "Alice".to_string(),
+        };
+        let mut mu = Mutation::new();
+        mu.set_set_json(&p).expect("Invalid JSON");
+        let response = txn.mutate(mu).await;
+        assert!(response.is_ok());
+        let discard = txn.discard_with_timeout(Duration::from_nanos(0)).await;
+        let err = discard.expect_err("Timeout");
+        assert!(matches!(
+            err.downcast_ref::<DgraphError>(),
+            Some(DgraphError::Timeout)
+        ));
+    }
+
+    #[cfg(any(
+        feature = "dgraph-1-1",
+        feature = "dgraph-21-03",
+        feature = "dgraph-24-02"
+    ))]
     #[tokio::test]
     async fn upsert() {
         let client = client().await;
@@ -549,7 +1648,47 @@ mod tests {
         assert!(txn.commit().await.is_ok());
     }
 
-    #[cfg(any(feature = "dgraph-1-1", feature = "dgraph-21-03"))]
+    #[cfg(any(
+        feature = "dgraph-1-1",
+        feature = "dgraph-21-03",
+        feature = "dgraph-24-02"
+    ))]
+    #[tokio::test]
+    async fn upsert_returning() {
+        let client = client().await;
+        client.drop_all().await.expect("Data not dropped");
+        client
+            .set_schema("name: string @index(exact) .")
+            .await
+            .expect("Schema is not updated");
+        let query = r#"
+          query {
+              user as var(func: eq(name, "Zoe"))
+          }"#;
+        let upsert = || {
+            let mut mu = Mutation::new();
+            mu.set_set_nquads(r#"_:zoe <name> "Zoe" ."#);
+            mu.set_cond("@if(eq(len(user), 0))");
+            mu
+        };
+        let txn = client.new_mutated_txn();
+        let (_, uids) = txn
+            .upsert_returning_and_commit_now(query, upsert())
+            .await
+            .expect("Created");
+        assert!(uids.contains_key("zoe"));
+        let mut txn = client.new_mutated_txn();
+        let (response, uids) = txn.upsert_returning(query, upsert()).await.expect("Found");
+        assert!(uids.is_empty());
+        assert!(response.uids.is_empty());
+        assert!(txn.commit().await.is_ok());
+    }
+
+    #[cfg(any(
+        feature = "dgraph-1-1",
+        feature = "dgraph-21-03",
+        feature = "dgraph-24-02"
+    ))]
     #[tokio::test]
     async fn upsert_and_commit_now() {
         let client = client().await;
@@ -591,7 +1730,11 @@ mod tests {
         assert!(response.is_ok())
     }
 
-    #[cfg(any(feature = "dgraph-1-1", feature = "dgraph-21-03"))]
+    #[cfg(any(
+        feature = "dgraph-1-1",
+        feature = "dgraph-21-03",
+        feature = "dgraph-24-02"
+    ))]
     #[tokio::test]
     async fn upsert_with_vars() {
         let client = client().await;
@@ -635,7 +1778,11 @@ mod tests {
         assert!(txn.commit().await.is_ok());
     }
 
-    #[cfg(any(feature = "dgraph-1-1", feature = "dgraph-21-03"))]
+    #[cfg(any(
+        feature = "dgraph-1-1",
+        feature = "dgraph-21-03",
+        feature = "dgraph-24-02"
+    ))]
     #[tokio::test]
     async fn upsert_with_vars_and_commit_now() {
         let client = client().await;
@@ -701,6 +1848,26 @@ mod tests {
         assert!(json.uids.pop().is_some());
     }
 
+    #[tokio::test]
+    async fn query_json() {
+        let client = client().await;
+        client
+            .set_schema("name: string @index(exact) .")
+            .await
+            .expect("Schema is not updated");
+        insert_data().await;
+        let mut txn = client.new_read_only_txn();
+        let query = r#"{
+            uids(func: eq(name, "Alice")) {
+                uid
+            }
+        }"#;
+        let json = txn.query_json(query).await.expect("JSON");
+        let mut json: UidJson = serde_json::from_slice(&json).unwrap();
+        assert!(json.uids.pop().is_some());
+        assert!(txn.context.start_ts > 0);
+    }
+
     #[tokio::test]
     async fn mutated_txn_query() {
         let client = client().await;
@@ -765,6 +1932,70 @@ mod tests {
         assert!(json.uids.pop().is_some());
     }
 
+    #[tokio::test]
+    async fn query_with_typed_vars() {
+        let client = client().await;
+        client
+            .set_schema("name: string @index(exact) .")
+            .await
+            .expect("Schema is not updated");
+        insert_data().await;
+        let mut txn = client.new_read_only_txn();
+        let query = r#"query all($first: int) {
+            uids(func: has(name), first: $first) {
+              uid
+            }
+          }"#;
+        let mut vars = HashMap::new();
+        vars.insert("$first", 1);
+        let response = txn.query_with_typed_vars(query, vars).await;
+        let json: UidJson = response.expect("Response").try_into().unwrap();
+        assert_eq!(json.uids.len(), 1);
+    }
+
+    #[tokio::test]
+    async fn query_blocks() {
+        let client = client().await;
+        client
+            .set_schema("name: string @index(exact) .")
+            .await
+            .expect("Schema is not updated");
+        insert_data().await;
+        let mut txn = client.new_read_only_txn();
+        let query = r#"{
+            first(func: has(name), first: 1) { uid }
+            second(func: has(name), first: 2) { uid }
+          }"#;
+        let blocks = txn.query_blocks(query).await.expect("Blocks");
+        assert_eq!(blocks.len(), 2);
+        assert_eq!(blocks["first"].as_array().map(Vec::len), Some(1));
+        assert_eq!(blocks["second"].as_array().map(Vec::len), Some(2));
+    }
+
+    #[cfg(any(
+        feature = "dgraph-1-1",
+        feature = "dgraph-21-03",
+        feature = "dgraph-24-02"
+    ))]
+    #[tokio::test]
+    async fn query_rdf() {
+        let client = client().await;
+        client
+            .set_schema("name: string @index(exact) .")
+            .await
+            .expect("Schema is not updated");
+        insert_data().await;
+        let mut txn = client.new_read_only_txn();
+        let query = r#"{
+            q(func: eq(name, "Alice")) { name }
+          }"#;
+        let response = txn.query_rdf(query).await.expect("Response");
+        let triples = response.rdf_triples().expect("RDF");
+        assert!(triples
+            .iter()
+            .any(|(_, predicate, object)| predicate == "name" && object == "Alice"));
+    }
+
     #[tokio::test]
     async fn mutated_txn_query_with_vars() {
         let client = client().await;
@@ -809,4 +2040,93 @@ mod tests {
         let mut json: UidJson = response.unwrap().try_into().unwrap();
         assert!(json.uids.pop().is_some());
     }
+
+    #[tokio::test]
+    async fn read_only_txn_set_best_effort() {
+        let client = Client::new("http://127.0.0.1:19080").unwrap();
+        let mut txn = client.new_read_only_txn();
+        let request = txn
+            .extra
+            .query_request(&txn.state, String::new(), HashMap::new());
+        assert!(request.read_only);
+        assert!(!request.best_effort);
+        txn.set_best_effort(true);
+        let request = txn
+            .extra
+            .query_request(&txn.state, String::new(), HashMap::new());
+        assert!(request.best_effort);
+    }
+
+    #[tokio::test]
+    async fn read_only_txn_query_json_reader() {
+        use tokio::io::AsyncReadExt;
+
+        let client = client().await;
+        client
+            .set_schema("name: string @index(exact) .")
+            .await
+            .expect("Schema is not updated");
+        insert_data().await;
+        let mut txn = client.new_read_only_txn();
+        let mut reader = txn
+            .query_json_reader(
+                r#"{
+                    uids(func: eq(name, "Alice")) {
+                        uid
+                    }
+                }"#,
+            )
+            .await
+            .expect("Reader");
+        let mut json = Vec::new();
+        reader.read_to_end(&mut json).await.expect("JSON");
+        let json: UidJson = serde_json::from_slice(&json).expect("UidJson");
+        assert!(!json.uids.is_empty());
+    }
+
+    #[tokio::test]
+    async fn read_only_txn_count() {
+        let client = client().await;
+        client.drop_all().await.expect("Data not dropped");
+        client
+            .set_schema("name: string @index(exact) .")
+            .await
+            .expect("Schema is not updated");
+        let mut txn = client.new_mutated_txn();
+        let mut mus = Vec::new();
+        for name in &["Alice", "Mike"] {
+            let p = Person {
+                uid: format!("_:{}", name),
+                name: name.to_string(),
+            };
+            let mut mu = Mutation::new();
+            mu.set_set_json(&p).expect("Invalid JSON");
+            mus.push(mu);
+        }
+        txn.mutate_all(mus).await.expect("Mutated");
+        txn.commit().await.expect("Committed");
+        let mut txn = client.new_read_only_txn();
+        assert_eq!(txn.count("has(name)").await.expect("Count"), 2);
+        assert_eq!(txn.count(r#"eq(name, "Alice")"#).await.expect("Count"), 1);
+    }
+
+    #[tokio::test]
+    async fn read_only_txn_last_start_ts() {
+        let client = client().await;
+        client
+            .set_schema("name: string @index(exact) .")
+            .await
+            .expect("Schema is not updated");
+        insert_data().await;
+        let mut txn = client.new_read_only_txn();
+        assert_eq!(txn.last_start_ts(), 0);
+        let query = r#"{
+            uids(func: eq(name, "Alice")) {
+                uid
+            }
+        }"#;
+        let response = txn.query(query).await;
+        assert!(response.is_ok());
+        assert!(txn.last_start_ts() > 0);
+    }
 }
@@ -1,21 +1,55 @@
 use std::collections::hash_map::RandomState;
 use std::collections::HashMap;
-use std::fmt::Debug;
+use std::fmt::{Debug, Formatter};
 use std::hash::Hash;
+use std::sync::Arc;
+use std::time::Duration;
 
 use anyhow::Result;
 use async_trait::async_trait;
+#[cfg(any(
+    feature = "dgraph-1-1",
+    feature = "dgraph-21-03",
+    feature = "dgraph-24-02"
+))]
+use serde::{ser, Serialize};
+#[cfg(any(
+    feature = "dgraph-1-1",
+    feature = "dgraph-21-03",
+    feature = "dgraph-24-02"
+))]
+use serde_json::Value;
+use tokio::runtime::Handle;
 
+#[cfg(feature = "gzip")]
+use crate::client::Compression;
 use crate::client::ILazyClient;
-use crate::errors::DgraphError;
+#[cfg(any(
+    feature = "dgraph-1-1",
+    feature = "dgraph-21-03",
+    feature = "dgraph-24-02"
+))]
+use crate::errors::ClientError;
+use crate::errors::{DgraphError, RequestContext};
+use crate::lifecycle::OpenTxn;
+#[cfg(any(
+    feature = "dgraph-1-1",
+    feature = "dgraph-21-03",
+    feature = "dgraph-24-02"
+))]
+use crate::txn::check_predicate;
 use crate::txn::default::Base;
 use crate::txn::{IState, Query, TxnState, TxnType, TxnVariant};
 #[cfg(feature = "dgraph-1-0")]
 use crate::Assigned;
 use crate::IDgraphClient;
-#[cfg(any(feature = "dgraph-1-1", feature = "dgraph-21-03"))]
+#[cfg(any(
+    feature = "dgraph-1-1",
+    feature = "dgraph-21-03",
+    feature = "dgraph-24-02"
+))]
 use crate::Response;
-use crate::{Mutation, Request};
+use crate::{Mutation, Request, TxnContext};
 
 ///
 /// In Dgraph v1.0.x is mutation response represented as Assigned object
@@ -25,9 +59,30 @@ pub type MutationResponse = Assigned;
 ///
 /// In Dgraph v1.1.x is mutation response represented as Response object
 ///
-#[cfg(any(feature = "dgraph-1-1", feature = "dgraph-21-03"))]
+#[cfg(any(
+    feature = "dgraph-1-1",
+    feature = "dgraph-21-03",
+    feature = "dgraph-24-02"
+))]
 pub type MutationResponse = Response;
 
+///
+/// Result of committed transaction.
+///
+/// Committed transaction cannot be used anymore, so this is the only data which outlive it.
+///
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Committed {
+    ///
+    /// Timestamp of commit. It is `0` when nothing was mutated, because no commit was sent to DB.
+    ///
+    pub commit_ts: u64,
+    ///
+    /// Uids of blank nodes created by all mutations of transaction
+    ///
+    pub uids: HashMap<String, String>,
+}
+
 ///
 /// Inner state for transaction which can modify data in DB.
 ///
@@ -35,30 +90,270 @@ pub type MutationResponse = Response;
 pub struct Mutated<C: ILazyClient> {
     base: Base<C>,
     mutated: bool,
+    inconsistent: bool,
+    uids: HashMap<String, String>,
+    #[cfg(any(
+        feature = "dgraph-1-1",
+        feature = "dgraph-21-03",
+        feature = "dgraph-24-02"
+    ))]
+    audit: Option<Vec<Request>>,
+    drop_abort: DropAbort,
+}
+
+type AbortFn = dyn Fn(TxnContext) + Send + Sync;
+
+///
+/// Abort transaction with pending mutations when it is dropped without commit or discard.
+///
+/// Transaction with pending mutations is also tracked by client, so it can be aborted when client
+/// is shut down.
+///
+#[derive(Default)]
+struct DropAbort {
+    abort: Option<Arc<AbortFn>>,
+    context: Option<TxnContext>,
+    open: Option<OpenTxn>,
+}
+
+impl DropAbort {
+    ///
+    /// Remember actual context of transaction which will be aborted on drop.
+    ///
+    fn arm(&mut self, context: &TxnContext) {
+        if let Some(open) = &self.open {
+            open.set(context);
+        }
+        if self.abort.is_some() {
+            self.context = Some(context.to_owned());
+        }
+    }
+
+    ///
+    /// Track context after mutation. Mutation with `commit_now` finished transaction, so there is
+    /// nothing to abort anymore.
+    ///
+    fn mutated(&mut self, context: &TxnContext, commit_now: bool) {
+        if commit_now {
+            self.disarm();
+        } else {
+            self.arm(context);
+        }
+    }
+
+    ///
+    /// Transaction is finished, so nothing is aborted on drop.
+    ///
+    fn disarm(&mut self) {
+        if let Some(open) = &self.open {
+            open.clear();
+        }
+        self.context = None;
+    }
+}
+
+impl Clone for DropAbort {
+    fn clone(&self) -> Self {
+        Self {
+            abort: self.abort.clone(),
+            context: None,
+            open: self.open.clone(),
+        }
+    }
+}
+
+impl Debug for DropAbort {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("DropAbort")
+            .field("enabled", &self.abort.is_some())
+            .field("context", &self.context)
+            .field("tracked", &self.open.is_some())
+            .finish()
+    }
+}
+
+impl Drop for DropAbort {
+    fn drop(&mut self) {
+        if let (Some(abort), Some(mut context)) = (self.abort.as_ref(), self.context.take()) {
+            context.aborted = true;
+            abort(context);
+        }
+    }
+}
+
+///
+/// Build condition which compares length of query variable with zero.
+///
+#[cfg(any(
+    feature = "dgraph-1-1",
+    feature = "dgraph-21-03",
+    feature = "dgraph-24-02"
+))]
+fn len_cond(query_var: &str, op: &str) -> String {
+    format!("@if({}(len({}), 0))", op, query_var)
+}
+
+///
+/// Build query which finds nodes with value of key predicate given in `$key` variable as `v`.
+///
+/// Predicate is written as `<predicate>`, so it must not contain whitespace nor any of
+/// ``^}|{\`,<>"`` characters, which are not allowed in predicate names.
+///
+#[cfg(any(
+    feature = "dgraph-1-1",
+    feature = "dgraph-21-03",
+    feature = "dgraph-24-02"
+))]
+fn key_query(name: &str, key_predicate: &str) -> Result<String, DgraphError> {
+    check_predicate(key_predicate)?;
+    Ok(format!(
+        r#"query {0}($key: string) {{
+            v as var(func: eq(<{1}>, $key))
+            node(func: uid(v)) {{ uid }}
+        }}"#,
+        name, key_predicate
+    ))
 }
 
 ///
 /// Upsert mutation can be defined with one or more mutations
 ///
-#[cfg(any(feature = "dgraph-1-1", feature = "dgraph-21-03"))]
+#[cfg(any(
+    feature = "dgraph-1-1",
+    feature = "dgraph-21-03",
+    feature = "dgraph-24-02"
+))]
 pub struct UpsertMutation {
     mu: Vec<Mutation>,
 }
 
-#[cfg(any(feature = "dgraph-1-1", feature = "dgraph-21-03"))]
+#[cfg(any(
+    feature = "dgraph-1-1",
+    feature = "dgraph-21-03",
+    feature = "dgraph-24-02"
+))]
 impl From<Vec<Mutation>> for UpsertMutation {
     fn from(mu: Vec<Mutation>) -> Self {
         Self { mu }
     }
 }
 
-#[cfg(any(feature = "dgraph-1-1", feature = "dgraph-21-03"))]
+#[cfg(any(
+    feature = "dgraph-1-1",
+    feature = "dgraph-21-03",
+    feature = "dgraph-24-02"
+))]
 impl From<Mutation> for UpsertMutation {
     fn from(mu: Mutation) -> Self {
         Self { mu: vec![mu] }
     }
 }
 
+///
+/// Patterns of server messages caused by unique checks of Dgraph v24.02 and newer.
+///
+#[cfg(any(
+    feature = "dgraph-1-1",
+    feature = "dgraph-21-03",
+    feature = "dgraph-24-02"
+))]
+const UPSERT_VALIDATION_PATTERNS: [&str; 3] = [
+    "__dgraph_uniquecheck_",
+    "Unclosed action",
+    "could not insert duplicate value",
+];
+
+///
+/// Return server message if request failed on unique check of upsert.
+///
+#[cfg(any(
+    feature = "dgraph-1-1",
+    feature = "dgraph-21-03",
+    feature = "dgraph-24-02"
+))]
+fn upsert_validation_message(err: &anyhow::Error) -> Option<String> {
+    let status = match err.downcast_ref::<ClientError>() {
+        Some(ClientError::CannotDoRequest(status)) => status,
+        _ => return None,
+    };
+    let message = status.message();
+    if UPSERT_VALIDATION_PATTERNS
+        .iter()
+        .any(|pattern| message.contains(pattern))
+    {
+        Some(message.to_string())
+    } else {
+        None
+    }
+}
+
+///
+/// Build conditional mutations which create node with given key or update node found as `v`.
+///
+#[cfg(any(
+    feature = "dgraph-1-1",
+    feature = "dgraph-21-03",
+    feature = "dgraph-24-02"
+))]
+fn key_mutations<T: Serialize + ?Sized>(
+    key_predicate: &str,
+    key_value: &str,
+    data: &T,
+) -> Result<Vec<Mutation>, serde_json::Error> {
+    let mut node = match serde_json::to_value(data)? {
+        Value::Object(node) => node,
+        _ => {
+            return Err(ser::Error::custom(
+                "data must be serialized into JSON object",
+            ))
+        }
+    };
+    node.insert(key_predicate.into(), key_value.into());
+    node.insert("uid".into(), "_:node".into());
+    let mut create = Mutation::new();
+    create.set_set_json(&node)?;
+    create.set_cond(len_cond("v", "eq"));
+    node.insert("uid".into(), "uid(v)".into());
+    let mut update = Mutation::new();
+    update.set_set_json(&node)?;
+    update.set_cond(len_cond("v", "gt"));
+    Ok(vec![create, update])
+}
+
+///
+/// Return uid of node created as `_:node` or uid of first node returned in `node` block.
+///
+#[cfg(any(
+    feature = "dgraph-1-1",
+    feature = "dgraph-21-03",
+    feature = "dgraph-24-02"
+))]
+fn key_node_uid(response: Response) -> Result<String> {
+    if let Some(uid) = response.uids.get("node") {
+        return Ok(uid.to_owned());
+    }
+    let found: HashMap<String, Vec<HashMap<String, String>>> = response.try_into_owned()?;
+    found
+        .get("node")
+        .and_then(|nodes| nodes.first())
+        .and_then(|node| node.get("uid"))
+        .cloned()
+        .ok_or_else(|| DgraphError::MissingUid.into())
+}
+
+///
+/// Move uids of created blank nodes out of mutation response.
+///
+#[cfg(any(
+    feature = "dgraph-1-1",
+    feature = "dgraph-21-03",
+    feature = "dgraph-24-02"
+))]
+pub(crate) fn split_uids(mut response: Response) -> (Response, HashMap<String, String>) {
+    let uids = std::mem::take(&mut response.uids);
+    (response, uids)
+}
+
 #[async_trait]
 impl<C: ILazyClient> IState for Mutated<C> {
     ///
@@ -84,14 +379,88 @@ impl<C: ILazyClient> TxnType<C> {
     /// Create new transaction for mutation operations.
     ///
     pub fn mutated(self) -> TxnMutatedType<C> {
+        let drop_abort = DropAbort {
+            abort: None,
+            context: None,
+            open: self.stub.track_txn(),
+        };
         TxnVariant {
             state: self.state,
             extra: Mutated {
                 base: self.extra,
                 mutated: false,
+                inconsistent: false,
+                uids: HashMap::new(),
+                #[cfg(any(
+                    feature = "dgraph-1-1",
+                    feature = "dgraph-21-03",
+                    feature = "dgraph-24-02"
+                ))]
+                audit: None,
+                drop_abort,
             },
         }
     }
+
+    ///
+    /// Create new transaction for mutation operations, which records every sent mutation request.
+    ///
+    /// Recorded requests are available with `audit_log()` and they can be used for debugging or
+    /// replay of transaction. Transactions created with `mutated()` do not record anything.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use dgraph_tonic::Client;
+    ///
+    /// let client = Client::new("http://127.0.0.1:19080").expect("Dgraph client");
+    /// let txn = client.new_txn().with_audit();
+    /// assert!(txn.audit_log().is_empty());
+    /// ```
+    ///
+    #[cfg(any(
+        feature = "dgraph-1-1",
+        feature = "dgraph-21-03",
+        feature = "dgraph-24-02"
+    ))]
+    pub fn with_audit(self) -> TxnMutatedType<C> {
+        let mut txn = self.mutated();
+        txn.extra.audit = Some(Vec::new());
+        txn
+    }
+}
+
+impl<C: ILazyClient + 'static> TxnType<C> {
+    ///
+    /// Create new transaction for mutation operations, which is aborted when it is dropped.
+    ///
+    /// If transaction with mutations is dropped without `commit` or `discard`, e.g. because of
+    /// panic or early return, abort is sent to Dgraph in detached task, so transaction does not
+    /// stay open until server timeout. Abort is best effort, it is scheduled only when
+    /// transaction is dropped inside Tokio runtime and its result is ignored.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use dgraph_tonic::Client;
+    ///
+    /// let client = Client::new("http://127.0.0.1:19080").expect("Dgraph client");
+    /// let txn = client.new_txn().with_drop_abort();
+    /// ```
+    ///
+    pub fn with_drop_abort(self) -> TxnMutatedType<C> {
+        let stub = self.stub.clone();
+        let mut txn = self.mutated();
+        txn.extra.drop_abort.abort = Some(Arc::new(move |context| {
+            let mut stub = stub.clone();
+            if let Ok(handle) = Handle::try_current() {
+                handle.spawn(async move {
+                    let _ = stub.commit_or_abort(context).await;
+                });
+            }
+        }));
+        txn
+    }
 }
 
 ///
@@ -111,11 +480,18 @@ pub trait Mutate: Query {
     ///
     /// Commit transaction
     ///
+    /// Returned `Committed` contains commit timestamp and uids of blank nodes created by all
+    /// mutations of transaction.
+    ///
     /// # Errors
     ///
     /// Return gRPC error.
     ///
-    async fn commit(self) -> Result<()>;
+    /// Return `InconsistentTxn` error if future of some mutation was dropped before it was
+    /// finished. Such mutation could be applied by Dgraph without being tracked by transaction,
+    /// so transaction can be only discarded.
+    ///
+    async fn commit(self) -> Result<Committed>;
 
     ///
     /// Adding or removing data in Dgraph is called a mutation.
@@ -172,6 +548,54 @@ pub trait Mutate: Query {
     ///
     async fn mutate(&mut self, mu: Mutation) -> Result<MutationResponse>;
 
+    ///
+    /// Same as `mutate`, but mutation is borrowed, so it can be resubmitted, e.g. in new
+    /// transaction after previous one was aborted.
+    ///
+    /// # Arguments
+    ///
+    /// * `mu`: required mutations
+    ///
+    /// # Errors
+    ///
+    /// * `GrpcError`: there is error in communication or server does not accept mutation
+    /// * `MissingTxnContext`: there is error in txn setup
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use dgraph_tonic::{Client, Mutation, Mutate};
+    /// #[cfg(feature = "acl")]
+    /// use dgraph_tonic::{AclClientType, LazyChannel};
+    ///
+    /// #[cfg(not(feature = "acl"))]
+    /// async fn client() -> Client {
+    ///     Client::new("http://127.0.0.1:19080").expect("Dgraph client")
+    /// }
+    ///
+    /// #[cfg(feature = "acl")]
+    /// async fn client() -> AclClientType<LazyChannel> {
+    ///     let default = Client::new("http://127.0.0.1:19080").unwrap();
+    ///     default.login("groot", "password").await.expect("Acl client")
+    /// }
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///    let mut mu = Mutation::new();
+    ///    mu.set_set_nquads(r#"_:alice <name> "Alice" ."#);
+    ///
+    ///    let client = client().await;
+    ///    for _ in 0..3 {
+    ///        let mut txn = client.new_mutated_txn();
+    ///        if txn.mutate_ref(&mu).await.is_ok() && txn.commit().await.is_ok() {
+    ///            break;
+    ///        }
+    ///    }
+    /// }
+    /// ```
+    ///
+    async fn mutate_ref(&mut self, mu: &Mutation) -> Result<MutationResponse>;
+
     ///
     /// Adding or removing data in Dgraph is called a mutation.
     ///
@@ -234,6 +658,10 @@ pub trait Mutate: Query {
     ///
     /// This function allows you to run upserts consisting of one query and one or more mutations.
     ///
+    /// Dgraph v24.02 and newer injects `__dgraph_uniquecheck_` blocks into upsert query for
+    /// predicates with `@unique` directive. Injected text can break query lexer ("Unclosed
+    /// action") if query block does not end with newline, so missing trailing newline is added
+    /// to query.
     ///
     /// # Arguments
     ///
@@ -244,6 +672,8 @@ pub trait Mutate: Query {
     ///
     /// * `GrpcError`: there is error in communication or server does not accept mutation
     /// * `MissingTxnContext`: there is error in txn setup
+    /// * `UpsertValidation`: upsert query is rejected by unique check or it violates unique
+    /// constraint
     ///
     /// # Example
     ///
@@ -333,7 +763,11 @@ pub trait Mutate: Query {
     /// }
     /// ```
     ///
-    #[cfg(any(feature = "dgraph-1-1", feature = "dgraph-21-03"))]
+    #[cfg(any(
+        feature = "dgraph-1-1",
+        feature = "dgraph-21-03",
+        feature = "dgraph-24-02"
+    ))]
     async fn upsert<Q, M>(&mut self, query: Q, mu: M) -> Result<MutationResponse>
     where
         Q: Into<String> + Send + Sync,
@@ -355,7 +789,11 @@ pub trait Mutate: Query {
     /// * `GrpcError`: there is error in communication or server does not accept mutation
     /// * `MissingTxnContext`: there is error in txn setup
     ///
-    #[cfg(any(feature = "dgraph-1-1", feature = "dgraph-21-03"))]
+    #[cfg(any(
+        feature = "dgraph-1-1",
+        feature = "dgraph-21-03",
+        feature = "dgraph-24-02"
+    ))]
     async fn upsert_and_commit_now<Q, M>(mut self, query: Q, mu: M) -> Result<MutationResponse>
     where
         Q: Into<String> + Send + Sync,
@@ -470,7 +908,11 @@ pub trait Mutate: Query {
     /// }
     /// ```
     ///
-    #[cfg(any(feature = "dgraph-1-1", feature = "dgraph-21-03"))]
+    #[cfg(any(
+        feature = "dgraph-1-1",
+        feature = "dgraph-21-03",
+        feature = "dgraph-24-02"
+    ))]
     async fn upsert_with_vars<Q, K, V, M>(
         &mut self,
         query: Q,
@@ -501,7 +943,11 @@ pub trait Mutate: Query {
     /// * `GrpcError`: there is error in communication or server does not accept mutation
     /// * `MissingTxnContext`: there is error in txn setup
     ///
-    #[cfg(any(feature = "dgraph-1-1", feature = "dgraph-21-03"))]
+    #[cfg(any(
+        feature = "dgraph-1-1",
+        feature = "dgraph-21-03",
+        feature = "dgraph-24-02"
+    ))]
     async fn upsert_with_vars_and_commit_now<Q, K, V, M>(
         mut self,
         query: Q,
@@ -513,16 +959,145 @@ pub trait Mutate: Query {
         K: Into<String> + Send + Sync + Eq + Hash,
         V: Into<String> + Send + Sync,
         M: Into<UpsertMutation> + Send + Sync;
+
+    ///
+    /// Same as `upsert`, but uids of newly created blank nodes are returned separately from response.
+    ///
+    /// Returned map is empty when upsert query found existing nodes and no new node was created.
+    ///
+    /// # Arguments
+    ///
+    /// * `q`: Dgraph query
+    /// * `mu`: required mutations
+    ///
+    /// # Errors
+    ///
+    /// * `GrpcError`: there is error in communication or server does not accept mutation
+    /// * `MissingTxnContext`: there is error in txn setup
+    ///
+    #[cfg(any(
+        feature = "dgraph-1-1",
+        feature = "dgraph-21-03",
+        feature = "dgraph-24-02"
+    ))]
+    async fn upsert_returning<Q, M>(
+        &mut self,
+        query: Q,
+        mu: M,
+    ) -> Result<(MutationResponse, HashMap<String, String>)>
+    where
+        Q: Into<String> + Send + Sync,
+        M: Into<UpsertMutation> + Send + Sync;
+
+    ///
+    /// Same as `upsert_and_commit_now`, but uids of newly created blank nodes are returned separately from response.
+    ///
+    /// Returned map is empty when upsert query found existing nodes and no new node was created.
+    ///
+    /// Transaction is committed.
+    ///
+    /// # Arguments
+    ///
+    /// * `q`: Dgraph query
+    /// * `mu`: required mutations
+    ///
+    /// # Errors
+    ///
+    /// * `GrpcError`: there is error in communication or server does not accept mutation
+    /// * `MissingTxnContext`: there is error in txn setup
+    ///
+    #[cfg(any(
+        feature = "dgraph-1-1",
+        feature = "dgraph-21-03",
+        feature = "dgraph-24-02"
+    ))]
+    async fn upsert_returning_and_commit_now<Q, M>(
+        mut self,
+        query: Q,
+        mu: M,
+    ) -> Result<(MutationResponse, HashMap<String, String>)>
+    where
+        Q: Into<String> + Send + Sync,
+        M: Into<UpsertMutation> + Send + Sync;
+
+    ///
+    /// Same as `upsert_with_vars`, but uids of newly created blank nodes are returned separately from response.
+    ///
+    /// Returned map is empty when upsert query found existing nodes and no new node was created.
+    ///
+    /// # Arguments
+    ///
+    /// * `q`: Dgraph query
+    /// * `mu`: required mutations
+    /// * `vars`: query variables
+    ///
+    /// # Errors
+    ///
+    /// * `GrpcError`: there is error in communication or server does not accept mutation
+    /// * `MissingTxnContext`: there is error in txn setup
+    ///
+    #[cfg(any(
+        feature = "dgraph-1-1",
+        feature = "dgraph-21-03",
+        feature = "dgraph-24-02"
+    ))]
+    async fn upsert_returning_with_vars<Q, K, V, M>(
+        &mut self,
+        query: Q,
+        vars: HashMap<K, V>,
+        mu: M,
+    ) -> Result<(MutationResponse, HashMap<String, String>)>
+    where
+        Q: Into<String> + Send + Sync,
+        K: Into<String> + Send + Sync + Eq + Hash,
+        V: Into<String> + Send + Sync,
+        M: Into<UpsertMutation> + Send + Sync;
+
+    ///
+    /// Same as `upsert_with_vars_and_commit_now`, but uids of newly created blank nodes are returned separately from response.
+    ///
+    /// Returned map is empty when upsert query found existing nodes and no new node was created.
+    ///
+    /// Transaction is committed.
+    ///
+    /// # Arguments
+    ///
+    /// * `q`: Dgraph query
+    /// * `mu`: required mutations
+    /// * `vars`: query variables
+    ///
+    /// # Errors
+    ///
+    /// * `GrpcError`: there is error in communication or server does not accept mutation
+    /// * `MissingTxnContext`: there is error in txn setup
+    ///
+    #[cfg(any(
+        feature = "dgraph-1-1",
+        feature = "dgraph-21-03",
+        feature = "dgraph-24-02"
+    ))]
+    async fn upsert_returning_with_vars_and_commit_now<Q, K, V, M>(
+        mut self,
+        query: Q,
+        vars: HashMap<K, V>,
+        mu: M,
+    ) -> Result<(MutationResponse, HashMap<String, String>)>
+    where
+        Q: Into<String> + Send + Sync,
+        K: Into<String> + Send + Sync + Eq + Hash,
+        V: Into<String> + Send + Sync,
+        M: Into<UpsertMutation> + Send + Sync;
 }
 
 #[async_trait]
 impl<C: ILazyClient> Mutate for TxnMutatedType<C> {
     async fn discard(mut self) -> Result<()> {
         self.context.aborted = true;
-        self.commit_or_abort().await
+        self.commit_or_abort().await?;
+        Ok(())
     }
 
-    async fn commit(self) -> Result<()> {
+    async fn commit(self) -> Result<Committed> {
         self.commit_or_abort().await
     }
 
@@ -531,12 +1106,20 @@ impl<C: ILazyClient> Mutate for TxnMutatedType<C> {
             .await
     }
 
+    async fn mutate_ref(&mut self, mu: &Mutation) -> Result<MutationResponse> {
+        self.mutate(mu.clone()).await
+    }
+
     async fn mutate_and_commit_now(mut self, mu: Mutation) -> Result<MutationResponse> {
         self.do_mutation("", HashMap::<String, String>::with_capacity(0), mu, true)
             .await
     }
 
-    #[cfg(any(feature = "dgraph-1-1", feature = "dgraph-21-03"))]
+    #[cfg(any(
+        feature = "dgraph-1-1",
+        feature = "dgraph-21-03",
+        feature = "dgraph-24-02"
+    ))]
     async fn upsert<Q, M>(&mut self, query: Q, mu: M) -> Result<MutationResponse>
     where
         Q: Into<String> + Send + Sync,
@@ -551,7 +1134,11 @@ impl<C: ILazyClient> Mutate for TxnMutatedType<C> {
         .await
     }
 
-    #[cfg(any(feature = "dgraph-1-1", feature = "dgraph-21-03"))]
+    #[cfg(any(
+        feature = "dgraph-1-1",
+        feature = "dgraph-21-03",
+        feature = "dgraph-24-02"
+    ))]
     async fn upsert_and_commit_now<Q, M>(mut self, query: Q, mu: M) -> Result<MutationResponse>
     where
         Q: Into<String> + Send + Sync,
@@ -561,7 +1148,11 @@ impl<C: ILazyClient> Mutate for TxnMutatedType<C> {
             .await
     }
 
-    #[cfg(any(feature = "dgraph-1-1", feature = "dgraph-21-03"))]
+    #[cfg(any(
+        feature = "dgraph-1-1",
+        feature = "dgraph-21-03",
+        feature = "dgraph-24-02"
+    ))]
     async fn upsert_with_vars<Q, K, V, M>(
         &mut self,
         query: Q,
@@ -577,7 +1168,11 @@ impl<C: ILazyClient> Mutate for TxnMutatedType<C> {
         self.do_mutation(query, vars, mu, false).await
     }
 
-    #[cfg(any(feature = "dgraph-1-1", feature = "dgraph-21-03"))]
+    #[cfg(any(
+        feature = "dgraph-1-1",
+        feature = "dgraph-21-03",
+        feature = "dgraph-24-02"
+    ))]
     async fn upsert_with_vars_and_commit_now<Q, K, V, M>(
         mut self,
         query: Q,
@@ -592,39 +1187,738 @@ impl<C: ILazyClient> Mutate for TxnMutatedType<C> {
     {
         self.do_mutation(query, vars, mu, true).await
     }
-}
 
-impl<C: ILazyClient> TxnMutatedType<C> {
-    #[cfg(feature = "dgraph-1-0")]
-    async fn do_mutation<Q, K, V>(
+    #[cfg(any(
+        feature = "dgraph-1-1",
+        feature = "dgraph-21-03",
+        feature = "dgraph-24-02"
+    ))]
+    async fn upsert_returning<Q, M>(
         &mut self,
-        _query: Q,
-        _vars: HashMap<K, V>,
-        mut mu: Mutation,
-        commit_now: bool,
-    ) -> Result<MutationResponse>
+        query: Q,
+        mu: M,
+    ) -> Result<(MutationResponse, HashMap<String, String>)>
     where
         Q: Into<String> + Send + Sync,
-        K: Into<String> + Send + Sync + Eq + Hash,
-        V: Into<String> + Send + Sync,
+        M: Into<UpsertMutation> + Send + Sync,
     {
-        self.extra.mutated = true;
-        mu.commit_now = commit_now;
-        mu.start_ts = self.context.start_ts;
-        let assigned = match self.stub.mutate(mu).await {
-            Ok(assigned) => assigned,
-            Err(err) => {
-                anyhow::bail!(DgraphError::GrpcError(err));
-            }
-        };
+        let response = self.upsert(query, mu).await?;
+        Ok(split_uids(response))
+    }
+
+    #[cfg(any(
+        feature = "dgraph-1-1",
+        feature = "dgraph-21-03",
+        feature = "dgraph-24-02"
+    ))]
+    async fn upsert_returning_and_commit_now<Q, M>(
+        mut self,
+        query: Q,
+        mu: M,
+    ) -> Result<(MutationResponse, HashMap<String, String>)>
+    where
+        Q: Into<String> + Send + Sync,
+        M: Into<UpsertMutation> + Send + Sync,
+    {
+        let response = self.upsert_and_commit_now(query, mu).await?;
+        Ok(split_uids(response))
+    }
+
+    #[cfg(any(
+        feature = "dgraph-1-1",
+        feature = "dgraph-21-03",
+        feature = "dgraph-24-02"
+    ))]
+    async fn upsert_returning_with_vars<Q, K, V, M>(
+        &mut self,
+        query: Q,
+        vars: HashMap<K, V>,
+        mu: M,
+    ) -> Result<(MutationResponse, HashMap<String, String>)>
+    where
+        Q: Into<String> + Send + Sync,
+        K: Into<String> + Send + Sync + Eq + Hash,
+        V: Into<String> + Send + Sync,
+        M: Into<UpsertMutation> + Send + Sync,
+    {
+        let response = self.upsert_with_vars(query, vars, mu).await?;
+        Ok(split_uids(response))
+    }
+
+    #[cfg(any(
+        feature = "dgraph-1-1",
+        feature = "dgraph-21-03",
+        feature = "dgraph-24-02"
+    ))]
+    async fn upsert_returning_with_vars_and_commit_now<Q, K, V, M>(
+        mut self,
+        query: Q,
+        vars: HashMap<K, V>,
+        mu: M,
+    ) -> Result<(MutationResponse, HashMap<String, String>)>
+    where
+        Q: Into<String> + Send + Sync,
+        K: Into<String> + Send + Sync + Eq + Hash,
+        V: Into<String> + Send + Sync,
+        M: Into<UpsertMutation> + Send + Sync,
+    {
+        let response = self
+            .upsert_with_vars_and_commit_now(query, vars, mu)
+            .await?;
+        Ok(split_uids(response))
+    }
+}
+
+impl<C: ILazyClient + 'static> TxnMutatedType<C> {
+    ///
+    /// Commit transaction, but wait at most `timeout` for the result.
+    ///
+    /// If commit is not finished in time, transaction is aborted in background task, so it is
+    /// not left open on server side. Transaction can be still committed if server finished the
+    /// commit before the abort request arrives.
+    ///
+    /// # Arguments
+    ///
+    /// * `timeout`: maximal duration of commit
+    ///
+    /// # Errors
+    ///
+    /// * `GrpcError`: there is error in communication or server does not accept commit
+    /// * `Timeout`: commit was not finished in given time
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use std::time::Duration;
+    /// use dgraph_tonic::{Client, Mutation, Mutate};
+    /// use serde::Serialize;
+    /// #[cfg(feature = "acl")]
+    /// use dgraph_tonic::{AclClientType, LazyChannel};
+    ///
+    /// #[cfg(not(feature = "acl"))]
+    /// async fn client() -> Client {
+    ///     Client::new("http://127.0.0.1:19080").expect("Dgraph client")
+    /// }
+    ///
+    /// #[cfg(feature = "acl")]
+    /// async fn client() -> AclClientType<LazyChannel> {
+    ///     let default = Client::new("http://127.0.0.1:19080").unwrap();
+    ///     default.login("groot", "password").await.expect("Acl client")
+    /// }
+    ///
+    /// #[derive(Serialize)]
+    /// struct Person {
+    ///     uid: String,
+    ///     name: String,
+    /// }
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     let p = Person {
+    ///         uid:  "_:alice".into(),
+    ///         name: "Alice".into(),
+    ///     };
+    ///     let mut mu = Mutation::new();
+    ///     mu.set_set_json(&p).expect("JSON");
+    ///     let client = client().await;
+    ///     let mut txn = client.new_mutated_txn();
+    ///     txn.mutate(mu).await.expect("mutated");
+    ///     txn.commit_with_timeout(Duration::from_secs(5)).await.expect("committed");
+    /// }
+    /// ```
+    ///
+    pub async fn commit_with_timeout(self, timeout: Duration) -> Result<Committed> {
+        self.commit_or_abort_with_timeout(timeout).await
+    }
+
+    ///
+    /// Discard transaction, but wait at most `timeout` for the result.
+    ///
+    /// If discard is not finished in time, another abort is sent in background task.
+    ///
+    /// # Arguments
+    ///
+    /// * `timeout`: maximal duration of discard
+    ///
+    /// # Errors
+    ///
+    /// * `GrpcError`: there is error in communication
+    /// * `Timeout`: discard was not finished in given time
+    ///
+    pub async fn discard_with_timeout(mut self, timeout: Duration) -> Result<()> {
+        self.context.aborted = true;
+        self.commit_or_abort_with_timeout(timeout).await?;
+        Ok(())
+    }
+
+    async fn commit_or_abort_with_timeout(self, timeout: Duration) -> Result<Committed> {
+        let mut stub = self.stub.clone();
+        let mut txn = self.context.clone();
+        match tokio::time::timeout(timeout, self.commit_or_abort()).await {
+            Ok(result) => result,
+            Err(_) => {
+                txn.aborted = true;
+                tokio::spawn(async move {
+                    let _ = stub.commit_or_abort(txn).await;
+                });
+                anyhow::bail!(DgraphError::Timeout)
+            }
+        }
+    }
+}
+
+impl<C: ILazyClient> TxnMutatedType<C> {
+    ///
+    /// Compress all requests and responses of this transaction.
+    ///
+    /// Compression is useful for big mutations, small requests are usually faster without it.
+    /// Other transactions of client are not affected.
+    ///
+    /// # Arguments
+    ///
+    /// * `compression`: used compression
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use dgraph_tonic::{Client, Compression};
+    ///
+    /// let client = Client::new("http://127.0.0.1:19080").expect("Dgraph client");
+    /// let txn = client.new_mutated_txn().with_compression(Compression::Gzip);
+    /// ```
+    ///
+    #[cfg(feature = "gzip")]
+    pub fn with_compression(mut self, compression: Compression) -> Self {
+        self.state.stub = self.state.stub.clone().with_compression(compression);
+        self
+    }
+
+    ///
+    /// Return requests sent by mutations of transaction in order of their execution.
+    ///
+    /// Requests are recorded only by transaction created with `with_audit()`, otherwise returned
+    /// log is always empty.
+    ///
+    #[cfg(any(
+        feature = "dgraph-1-1",
+        feature = "dgraph-21-03",
+        feature = "dgraph-24-02"
+    ))]
+    pub fn audit_log(&self) -> &[Request] {
+        self.extra.audit.as_deref().unwrap_or_default()
+    }
+
+    ///
+    /// Return conflict keys tracked by Dgraph for mutations of transaction.
+    ///
+    /// Keys are merged from all responses of transaction, sorted and deduplicated. Two
+    /// transactions with common conflict key cannot be both committed.
+    ///
+    pub fn conflict_keys(&self) -> &[String] {
+        &self.context.keys
+    }
+
+    ///
+    /// Return predicates touched by mutations of transaction.
+    ///
+    /// Predicates are merged from all responses of transaction, sorted and deduplicated.
+    ///
+    pub fn predicates(&self) -> &[String] {
+        &self.context.preds
+    }
+
+    ///
+    /// Return true if transaction sent some mutation.
+    ///
+    /// Mutation is counted also when it failed, because Dgraph may have applied it before error
+    /// was returned. Commit of transaction without mutation returns immediately without request.
+    ///
+    pub fn has_mutated(&self) -> bool {
+        self.extra.mutated
+    }
+
+    ///
+    /// Submit every mutation in given order and return merged uids of all blank nodes.
+    ///
+    /// Transaction is left open, so it must be committed or discarded afterwards. Submitting
+    /// stops on first failed mutation.
+    ///
+    /// # Arguments
+    ///
+    /// * `mus`: mutations submitted one by one
+    ///
+    /// # Errors
+    ///
+    /// * `GrpcError`: there is error in communication or server does not accept mutation
+    /// * `MissingTxnContext`: there is error in txn setup
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use dgraph_tonic::{Client, Mutation, Mutate};
+    /// use serde::Serialize;
+    /// #[cfg(feature = "acl")]
+    /// use dgraph_tonic::{AclClientType, LazyChannel};
+    ///
+    /// #[cfg(not(feature = "acl"))]
+    /// async fn client() -> Client {
+    ///     Client::new("http://127.0.0.1:19080").expect("Dgraph client")
+    /// }
+    ///
+    /// #[cfg(feature = "acl")]
+    /// async fn client() -> AclClientType<LazyChannel> {
+    ///     let default = Client::new("http://127.0.0.1:19080").unwrap();
+    ///     default.login("groot", "password").await.expect("Acl client")
+    /// }
+    ///
+    /// #[derive(Serialize)]
+    /// struct Person {
+    ///     uid: String,
+    ///     name: String,
+    /// }
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     let mut mus = Vec::new();
+    ///     for name in &["Alice", "Bob"] {
+    ///         let p = Person {
+    ///             uid: format!("_:{}", name.to_lowercase()),
+    ///             name: name.to_string(),
+    ///         };
+    ///         let mut mu = Mutation::new();
+    ///         mu.set_set_json(&p).expect("JSON");
+    ///         mus.push(mu);
+    ///     }
+    ///     let client = client().await;
+    ///     let mut txn = client.new_mutated_txn();
+    ///     let uids = txn.mutate_all(mus).await.expect("mutated");
+    ///     txn.commit().await.expect("committed");
+    ///     println!("{:?}", uids);
+    /// }
+    /// ```
+    ///
+    pub async fn mutate_all(&mut self, mus: Vec<Mutation>) -> Result<HashMap<String, String>> {
+        let mut uids = HashMap::new();
+        for mu in mus {
+            let response = self.mutate(mu).await?;
+            uids.extend(response.uids);
+        }
+        Ok(uids)
+    }
+
+    ///
+    /// Send all mutations in one request and return merged response.
+    ///
+    /// Unlike `mutate_all`, there is only one round-trip to Dgraph and mutations are applied
+    /// all or none. Transaction is left open, so it must be committed or discarded afterwards.
+    /// Empty batch is not sent.
+    ///
+    /// # Arguments
+    ///
+    /// * `mus`: mutations sent together
+    ///
+    /// # Errors
+    ///
+    /// * `GrpcError`: there is error in communication or server does not accept mutation
+    /// * `MissingTxnContext`: there is error in txn setup
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use dgraph_tonic::{Client, Mutation, Mutate};
+    /// #[cfg(feature = "acl")]
+    /// use dgraph_tonic::{AclClientType, LazyChannel};
+    ///
+    /// #[cfg(not(feature = "acl"))]
+    /// async fn client() -> Client {
+    ///     Client::new("http://127.0.0.1:19080").expect("Dgraph client")
+    /// }
+    ///
+    /// #[cfg(feature = "acl")]
+    /// async fn client() -> AclClientType<LazyChannel> {
+    ///     let default = Client::new("http://127.0.0.1:19080").unwrap();
+    ///     default.login("groot", "password").await.expect("Acl client")
+    /// }
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     let mus = vec![
+    ///         Mutation::from_set_nquads(r#"_:alice <name> "Alice" ."#),
+    ///         Mutation::from_set_nquads(r#"_:bob <name> "Bob" ."#),
+    ///     ];
+    ///     let client = client().await;
+    ///     let mut txn = client.new_mutated_txn();
+    ///     let response = txn.mutate_batch(mus).await.expect("mutated");
+    ///     txn.commit().await.expect("committed");
+    ///     println!("{:?}", response.uids);
+    /// }
+    /// ```
+    ///
+    #[cfg(any(
+        feature = "dgraph-1-1",
+        feature = "dgraph-21-03",
+        feature = "dgraph-24-02"
+    ))]
+    pub async fn mutate_batch(&mut self, mus: Vec<Mutation>) -> Result<MutationResponse> {
+        if mus.is_empty() {
+            return Ok(MutationResponse::default());
+        }
+        self.do_mutation("", HashMap::<String, String>::with_capacity(0), mus, false)
+            .await
+    }
+
+    ///
+    /// Run upsert with mutation which is applied only when query variable is empty.
+    ///
+    /// Condition `@if(eq(len(query_var), 0))` is attached to mutation, so it replaces condition
+    /// which was already set. Typical use is create of node which does not exist yet.
+    ///
+    /// # Arguments
+    ///
+    /// * `query`: upsert query which defines `query_var`
+    /// * `query_var`: name of query variable
+    /// * `mu`: mutation which creates data
+    ///
+    /// # Errors
+    ///
+    /// * `GrpcError`: there is error in communication or server does not accept mutation
+    /// * `MissingTxnContext`: there is error in txn setup
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use dgraph_tonic::{Client, Mutate, Mutation};
+    /// #[cfg(feature = "acl")]
+    /// use dgraph_tonic::{AclClientType, LazyChannel};
+    ///
+    /// #[cfg(not(feature = "acl"))]
+    /// async fn client() -> Client {
+    ///     Client::new("http://127.0.0.1:19080").expect("Dgraph client")
+    /// }
+    ///
+    /// #[cfg(feature = "acl")]
+    /// async fn client() -> AclClientType<LazyChannel> {
+    ///     let default = Client::new("http://127.0.0.1:19080").unwrap();
+    ///     default.login("groot", "password").await.expect("Acl client")
+    /// }
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     let client = client().await;
+    ///     client.set_schema("email: string @index(exact) .").await.expect("Schema");
+    ///     let mut txn = client.new_mutated_txn();
+    ///     let query = r#"{ user as var(func: eq(email, "alice@example.com")) }"#;
+    ///     let mu = Mutation::from_set_nquads(r#"_:alice <email> "alice@example.com" ."#);
+    ///     txn.upsert_if_absent(query, "user", mu).await.expect("Upsert");
+    ///     txn.commit().await.expect("Committed");
+    /// }
+    /// ```
+    ///
+    #[cfg(any(
+        feature = "dgraph-1-1",
+        feature = "dgraph-21-03",
+        feature = "dgraph-24-02"
+    ))]
+    pub async fn upsert_if_absent<Q>(
+        &mut self,
+        query: Q,
+        query_var: &str,
+        mut mu: Mutation,
+    ) -> Result<MutationResponse>
+    where
+        Q: Into<String> + Send + Sync,
+    {
+        mu.set_cond(len_cond(query_var, "eq"));
+        self.upsert(query, mu).await
+    }
+
+    ///
+    /// Run upsert with mutation which is applied only when query variable is not empty.
+    ///
+    /// Condition `@if(gt(len(query_var), 0))` is attached to mutation, so it replaces condition
+    /// which was already set. Typical use is update of nodes which already exist.
+    ///
+    /// # Arguments
+    ///
+    /// * `query`: upsert query which defines `query_var`
+    /// * `query_var`: name of query variable
+    /// * `mu`: mutation which updates data, usually with `uid(query_var)` subject
+    ///
+    /// # Errors
+    ///
+    /// * `GrpcError`: there is error in communication or server does not accept mutation
+    /// * `MissingTxnContext`: there is error in txn setup
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use dgraph_tonic::{Client, Mutate, Mutation};
+    /// #[cfg(feature = "acl")]
+    /// use dgraph_tonic::{AclClientType, LazyChannel};
+    ///
+    /// #[cfg(not(feature = "acl"))]
+    /// async fn client() -> Client {
+    ///     Client::new("http://127.0.0.1:19080").expect("Dgraph client")
+    /// }
+    ///
+    /// #[cfg(feature = "acl")]
+    /// async fn client() -> AclClientType<LazyChannel> {
+    ///     let default = Client::new("http://127.0.0.1:19080").unwrap();
+    ///     default.login("groot", "password").await.expect("Acl client")
+    /// }
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     let client = client().await;
+    ///     client.set_schema("email: string @index(exact) .").await.expect("Schema");
+    ///     let mut txn = client.new_mutated_txn();
+    ///     let query = r#"{ user as var(func: eq(email, "alice@example.com")) }"#;
+    ///     let mu = Mutation::from_set_nquads(r#"uid(user) <name> "Alice" ."#);
+    ///     txn.upsert_if_present(query, "user", mu).await.expect("Upsert");
+    ///     txn.commit().await.expect("Committed");
+    /// }
+    /// ```
+    ///
+    #[cfg(any(
+        feature = "dgraph-1-1",
+        feature = "dgraph-21-03",
+        feature = "dgraph-24-02"
+    ))]
+    pub async fn upsert_if_present<Q>(
+        &mut self,
+        query: Q,
+        query_var: &str,
+        mut mu: Mutation,
+    ) -> Result<MutationResponse>
+    where
+        Q: Into<String> + Send + Sync,
+    {
+        mu.set_cond(len_cond(query_var, "gt"));
+        self.upsert(query, mu).await
+    }
+
+    ///
+    /// Find node with given value of key predicate or create it, if it does not exist.
+    ///
+    /// Lookup and create are done by one upsert which is committed immediately, so node is not
+    /// created twice. Key value is passed as query variable, so it does not have to be escaped.
+    /// New node is referenced as `_:node` in `extra_nquads`, e.g.
+    /// `_:node <dgraph.type> "Person" .`. With `@unique` index of key predicate (Dgraph v24.02),
+    /// concurrent create of same node fails with `UpsertValidation` error.
+    ///
+    /// # Arguments
+    ///
+    /// * `key_predicate`: predicate with `exact` or `hash` index which identifies node
+    /// * `key_value`: value of key predicate
+    /// * `extra_nquads`: nquads added to created node, they are ignored for existing node
+    ///
+    /// # Return
+    ///
+    /// Uid of found or created node.
+    ///
+    /// # Errors
+    ///
+    /// * `InvalidPredicate`: key predicate is empty or contains whitespace or reserved characters
+    /// * `GrpcError`: there is error in communication or server does not accept mutation
+    /// * `MissingUid`: uid of node is neither found nor created
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use dgraph_tonic::Client;
+    /// #[cfg(feature = "acl")]
+    /// use dgraph_tonic::{AclClientType, LazyChannel};
+    ///
+    /// #[cfg(not(feature = "acl"))]
+    /// async fn client() -> Client {
+    ///     Client::new("http://127.0.0.1:19080").expect("Dgraph client")
+    /// }
+    ///
+    /// #[cfg(feature = "acl")]
+    /// async fn client() -> AclClientType<LazyChannel> {
+    ///     let default = Client::new("http://127.0.0.1:19080").unwrap();
+    ///     default.login("groot", "password").await.expect("Acl client")
+    /// }
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     let client = client().await;
+    ///     client.set_schema("email: string @index(exact) .").await.expect("Schema");
+    ///     let uid = client
+    ///         .new_mutated_txn()
+    ///         .get_or_create("email", "alice@example.com", r#"_:node <name> "Alice" ."#)
+    ///         .await
+    ///         .expect("Uid");
+    ///     println!("{}", uid);
+    /// }
+    /// ```
+    ///
+    #[cfg(any(
+        feature = "dgraph-1-1",
+        feature = "dgraph-21-03",
+        feature = "dgraph-24-02"
+    ))]
+    pub async fn get_or_create(
+        mut self,
+        key_predicate: &str,
+        key_value: &str,
+        extra_nquads: &str,
+    ) -> Result<String> {
+        let query = key_query("get_or_create", key_predicate)?;
+        let mut vars = HashMap::new();
+        vars.insert("$key", key_value);
+        let mut node = serde_json::Map::new();
+        node.insert("uid".into(), "_:node".into());
+        node.insert(key_predicate.into(), key_value.into());
+        let mut mu = Mutation::new();
+        mu.set_set_json(&node)?;
+        mu.set_set_nquads(extra_nquads);
+        mu.set_cond("@if(eq(len(v), 0))");
+        let response = self.do_mutation(query, vars, mu, true).await?;
+        key_node_uid(response)
+    }
+
+    ///
+    /// Write `data` into node identified by value of key predicate instead of blank node.
+    ///
+    /// One upsert finds node by key and updates it with `data` or creates new node with `data`
+    /// and key, when it does not exist. Repeating the same logical write, e.g. after failed
+    /// commit, therefore does not create duplicate node. Key predicate should have `@upsert`
+    /// directive, so concurrent writes of same key conflict on commit. Transaction is not
+    /// committed.
+    ///
+    /// # Arguments
+    ///
+    /// * `key_predicate`: predicate with `exact` or `hash` index which identifies node
+    /// * `key_value`: value of key predicate
+    /// * `data`: predicates of node, it must be serialized into JSON object and its `uid` is
+    ///   ignored
+    ///
+    /// # Return
+    ///
+    /// Stable uid of updated or created node. When more nodes have same key, all of them are
+    /// updated and uid of the first one is returned.
+    ///
+    /// # Errors
+    ///
+    /// * `InvalidPredicate`: key predicate is empty or contains whitespace or reserved characters
+    /// * `GrpcError`: there is error in communication or server does not accept mutation
+    /// * `MissingUid`: uid of node is neither found nor created
+    /// * JSON error if `data` is not serialized into JSON object
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use dgraph_tonic::Client;
+    /// use serde::Serialize;
+    /// #[cfg(feature = "acl")]
+    /// use dgraph_tonic::{AclClientType, LazyChannel};
+    ///
+    /// #[cfg(not(feature = "acl"))]
+    /// async fn client() -> Client {
+    ///     Client::new("http://127.0.0.1:19080").expect("Dgraph client")
+    /// }
+    ///
+    /// #[cfg(feature = "acl")]
+    /// async fn client() -> AclClientType<LazyChannel> {
+    ///     let default = Client::new("http://127.0.0.1:19080").unwrap();
+    ///     default.login("groot", "password").await.expect("Acl client")
+    /// }
+    ///
+    /// #[derive(Serialize)]
+    /// struct Person {
+    ///     name: String,
+    /// }
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     let client = client().await;
+    ///     client.set_schema("email: string @index(exact) @upsert .").await.expect("Schema");
+    ///     let alice = Person { name: "Alice".into() };
+    ///     let mut txn = client.new_mutated_txn();
+    ///     let uid = txn
+    ///         .upsert_by_key("email", "alice@example.com", &alice)
+    ///         .await
+    ///         .expect("Uid");
+    ///     txn.commit().await.expect("Committed");
+    ///     println!("{}", uid);
+    /// }
+    /// ```
+    ///
+    #[cfg(any(
+        feature = "dgraph-1-1",
+        feature = "dgraph-21-03",
+        feature = "dgraph-24-02"
+    ))]
+    pub async fn upsert_by_key<T: Serialize + ?Sized>(
+        &mut self,
+        key_predicate: &str,
+        key_value: &str,
+        data: &T,
+    ) -> Result<String> {
+        let query = key_query("upsert_by_key", key_predicate)?;
+        let mut vars = HashMap::new();
+        vars.insert("$key", key_value);
+        let mus = key_mutations(key_predicate, key_value, data)?;
+        let response = self.do_mutation(query, vars, mus, false).await?;
+        key_node_uid(response)
+    }
+
+    #[cfg(feature = "dgraph-1-0")]
+    async fn do_mutation<Q, K, V>(
+        &mut self,
+        _query: Q,
+        _vars: HashMap<K, V>,
+        mut mu: Mutation,
+        commit_now: bool,
+    ) -> Result<MutationResponse>
+    where
+        Q: Into<String> + Send + Sync,
+        K: Into<String> + Send + Sync + Eq + Hash,
+        V: Into<String> + Send + Sync,
+    {
+        self.extra.mutated = true;
+        mu.commit_now = commit_now;
+        mu.start_ts = self.context.start_ts;
+        self.stub.check_message_size(&mu)?;
+        self.stub.check_deadline()?;
+        // stays set when future is dropped before response is received
+        let inconsistent = std::mem::replace(&mut self.extra.inconsistent, true);
+        let assigned = if commit_now {
+            self.stub.once(|stub| stub.mutate(mu.clone())).await
+        } else {
+            self.stub.retry(|stub| stub.mutate(mu.clone())).await
+        };
+        self.extra.inconsistent = inconsistent;
+        let assigned = match assigned {
+            Ok(assigned) => assigned,
+            Err(err) => {
+                self.stub.check_deadline()?;
+                let context = RequestContext {
+                    query: String::new(),
+                    start_ts: mu.start_ts,
+                    #[cfg(feature = "debug-requests")]
+                    request: None,
+                };
+                anyhow::bail!(DgraphError::GrpcError(err.context(context)));
+            }
+        };
         match assigned.context.as_ref() {
             Some(src) => self.context.merge_context(src)?,
             None => anyhow::bail!(DgraphError::MissingTxnContext),
         }
+        self.extra
+            .drop_abort
+            .mutated(&self.state.context, commit_now);
+        self.extra.uids.extend(assigned.uids.clone());
         Ok(assigned)
     }
 
-    #[cfg(any(feature = "dgraph-1-1", feature = "dgraph-21-03"))]
+    #[cfg(any(
+        feature = "dgraph-1-1",
+        feature = "dgraph-21-03",
+        feature = "dgraph-24-02"
+    ))]
     async fn do_mutation<Q, K, V, M>(
         &mut self,
         query: Q,
@@ -645,37 +1939,262 @@ impl<C: ILazyClient> TxnMutatedType<C> {
         });
         let mu: UpsertMutation = mu.into();
         let request = Request {
-            query: query.into(),
             vars,
             start_ts: self.context.start_ts,
             commit_now,
-            mutations: mu.mu,
-            ..Default::default()
+            ..Request::upsert(query, mu.mu)
         };
-        let response = match self.stub.do_request(request).await {
+        #[cfg(feature = "debug-requests")]
+        {
+            self.state.last_request = Some(request.clone());
+        }
+        self.stub.check_message_size(&request)?;
+        self.stub.check_deadline()?;
+        if let Some(audit) = self.extra.audit.as_mut() {
+            audit.push(request.clone());
+        }
+        // stays set when future is dropped before response is received
+        let inconsistent = std::mem::replace(&mut self.extra.inconsistent, true);
+        let response = if commit_now {
+            self.stub
+                .once(|stub| stub.do_request(request.clone()))
+                .await
+        } else {
+            self.stub
+                .retry(|stub| stub.do_request(request.clone()))
+                .await
+        };
+        self.extra.inconsistent = inconsistent;
+        let response = match response {
             Ok(response) => response,
             Err(err) => {
-                anyhow::bail!(DgraphError::GrpcError(err));
+                self.stub.check_deadline()?;
+                if let Some(message) = upsert_validation_message(&err) {
+                    anyhow::bail!(DgraphError::UpsertValidation { message });
+                }
+                let context = RequestContext {
+                    #[cfg(feature = "debug-requests")]
+                    request: Some(request.clone()),
+                    query: request.query,
+                    start_ts: request.start_ts,
+                };
+                anyhow::bail!(DgraphError::GrpcError(err.context(context)));
             }
         };
         match response.txn.as_ref() {
             Some(txn) => self.context.merge_context(txn)?,
             None => anyhow::bail!(DgraphError::MissingTxnContext),
         }
+        self.extra
+            .drop_abort
+            .mutated(&self.state.context, commit_now);
+        self.extra.uids.extend(response.uids.clone());
         Ok(response)
     }
 
-    async fn commit_or_abort(self) -> Result<()> {
-        let extra = self.extra;
+    async fn commit_or_abort(self) -> Result<Committed> {
+        let mut extra = self.extra;
         let state = *self.state;
+        if extra.inconsistent && !state.context.aborted {
+            // transaction is still aborted on drop, if it was enabled
+            anyhow::bail!(DgraphError::InconsistentTxn);
+        }
+        extra.drop_abort.disarm();
         if !extra.mutated {
-            return Ok(());
+            return Ok(Committed::default());
         };
         let mut client = state.stub;
         let txn = state.context;
-        match client.commit_or_abort(txn).await {
-            Ok(_txn_context) => Ok(()),
-            Err(err) => anyhow::bail!(DgraphError::GrpcError(err)),
+        let expired = client.check_deadline();
+        if expired.is_err() {
+            // commit cannot be sent after deadline, but abort is sent best effort, so
+            // transaction does not stay open
+            let mut client = client.clone().with_deadline(None);
+            let mut abort = txn.clone();
+            abort.aborted = true;
+            let _ = client
+                .once(|stub| stub.commit_or_abort(abort.clone()))
+                .await;
+        }
+        expired?;
+        match client.retry(|stub| stub.commit_or_abort(txn.clone())).await {
+            Ok(txn_context) => Ok(Committed {
+                commit_ts: txn_context.commit_ts,
+                uids: extra.uids,
+            }),
+            Err(err) => {
+                client.check_deadline()?;
+                anyhow::bail!(DgraphError::GrpcError(err))
+            }
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::sync::{Arc, Mutex};
+
+    use super::DropAbort;
+    use crate::TxnContext;
+
+    #[test]
+    fn drop_abort() {
+        let aborted = Arc::new(Mutex::new(Vec::new()));
+        let abort = {
+            let aborted = Arc::clone(&aborted);
+            move |context: TxnContext| aborted.lock().unwrap().push(context)
+        };
+        let context = TxnContext {
+            start_ts: 7,
+            ..Default::default()
+        };
+        let mut guard = DropAbort {
+            abort: Some(Arc::new(abort)),
+            context: None,
+            open: None,
+        };
+        drop(guard.clone());
+        guard.arm(&context);
+        let mut finished = guard.clone();
+        finished.arm(&context);
+        finished.disarm();
+        drop(finished);
+        assert!(aborted.lock().unwrap().is_empty());
+        let mut committed = guard.clone();
+        committed.mutated(&context, false);
+        committed.mutated(&context, true);
+        drop(committed);
+        assert!(aborted.lock().unwrap().is_empty());
+        drop(guard);
+        let aborted = aborted.lock().unwrap();
+        assert_eq!(aborted.len(), 1);
+        assert_eq!(aborted[0].start_ts, 7);
+        assert!(aborted[0].aborted);
+    }
+
+    #[cfg(any(
+        feature = "dgraph-1-1",
+        feature = "dgraph-21-03",
+        feature = "dgraph-24-02"
+    ))]
+    #[test]
+    fn len_cond() {
+        assert_eq!(super::len_cond("user", "eq"), "@if(eq(len(user), 0))");
+        assert_eq!(super::len_cond("user", "gt"), "@if(gt(len(user), 0))");
+    }
+
+    #[cfg(any(
+        feature = "dgraph-1-1",
+        feature = "dgraph-21-03",
+        feature = "dgraph-24-02"
+    ))]
+    #[test]
+    fn key_query() {
+        use crate::DgraphError;
+
+        let query = super::key_query("get_or_create", "dgraph.xid").expect("Query");
+        assert!(query.starts_with("query get_or_create($key: string) {"));
+        assert!(query.contains("v as var(func: eq(<dgraph.xid>, $key))"));
+        for predicate in ["", "email, $key)) @filter(has(a", "a b", "a>", "{a}", "a\n"].iter() {
+            match super::key_query("get_or_create", predicate) {
+                Err(DgraphError::InvalidPredicate(invalid)) => assert_eq!(&invalid, predicate),
+                other => panic!("unexpected {:?}", other),
+            }
+        }
+    }
+
+    #[cfg(any(
+        feature = "dgraph-1-1",
+        feature = "dgraph-21-03",
+        feature = "dgraph-24-02"
+    ))]
+    #[test]
+    fn key_mutations() {
+        use serde_json::{json, Value};
+
+        let data = json!({"uid": "0x1", "name": "Alice", "email": "bob@example.com"});
+        let mus = super::key_mutations("email", "alice@example.com", &data).expect("Mutations");
+        let json = |mu: &crate::Mutation| serde_json::from_slice::<Value>(&mu.set_json).unwrap();
+        assert_eq!(
+            json(&mus[0]),
+            json!({"uid": "_:node", "name": "Alice", "email": "alice@example.com"})
+        );
+        assert_eq!(mus[0].cond, "@if(eq(len(v), 0))");
+        assert_eq!(
+            json(&mus[1]),
+            json!({"uid": "uid(v)", "name": "Alice", "email": "alice@example.com"})
+        );
+        assert_eq!(mus[1].cond, "@if(gt(len(v), 0))");
+        assert!(super::key_mutations("email", "alice@example.com", &["Alice"]).is_err());
+    }
+
+    #[cfg(any(
+        feature = "dgraph-1-1",
+        feature = "dgraph-21-03",
+        feature = "dgraph-24-02"
+    ))]
+    #[test]
+    fn upsert_validation_message() {
+        use crate::{ClientError, Status};
+
+        let err = ClientError::CannotDoRequest(Status::unknown(
+            "while lexing __dgraph_uniquecheck_0__ as var(func: eq(email,\"a\")) - Unclosed action",
+        ))
+        .into();
+        assert!(super::upsert_validation_message(&err)
+            .expect("Upsert validation")
+            .contains("Unclosed action"));
+        let err =
+            ClientError::CannotDoRequest(Status::unknown("Transaction has been aborted")).into();
+        assert!(super::upsert_validation_message(&err).is_none());
+    }
+
+    #[cfg(any(
+        feature = "dgraph-1-1",
+        feature = "dgraph-21-03",
+        feature = "dgraph-24-02"
+    ))]
+    #[tokio::test]
+    async fn commit_after_deadline_aborts() {
+        use std::time::{Duration, Instant};
+
+        use crate::fake_server::{serve, FakeReply};
+        use crate::{Client, DgraphError, Mutate, Mutation, Response};
+
+        let sent = Arc::new(Mutex::new(Vec::new()));
+        let recorder = Arc::clone(&sent);
+        let (addr, server) = serve(move |call| {
+            let context = TxnContext {
+                start_ts: 7,
+                ..Default::default()
+            };
+            if call.path.ends_with("/CommitOrAbort") {
+                recorder.lock().unwrap().push(call.message::<TxnContext>());
+                return FakeReply::new(&context);
+            }
+            FakeReply::new(&Response {
+                txn: Some(context),
+                ..Default::default()
+            })
+        })
+        .await;
+        let client = Client::new(addr).unwrap();
+        let mut txn = client
+            .new_mutated_txn()
+            .with_deadline(Instant::now() + Duration::from_millis(500));
+        let mut mu = Mutation::new();
+        mu.set_set_nquads(r#"_:a <name> "Alice" ."#);
+        txn.mutate(mu).await.expect("Mutated");
+        tokio::time::sleep(Duration::from_millis(600)).await;
+        let err = txn.commit().await.expect_err("Deadline exceeded");
+        assert!(matches!(
+            err.downcast_ref::<DgraphError>(),
+            Some(DgraphError::Timeout)
+        ));
+        let sent = sent.lock().unwrap();
+        assert_eq!(sent.len(), 1);
+        assert_eq!(sent[0].start_ts, 7);
+        assert!(sent[0].aborted);
+        server.abort();
+    }
+}
@@ -1,17 +1,76 @@
 use std::collections::HashMap;
 use std::fmt::Debug;
+use std::hash::Hash;
+use std::io::Cursor;
+
+use anyhow::Result;
+#[cfg(feature = "dgraph-24-02")]
+use serde::de::DeserializeOwned;
+use serde::Deserialize;
+use tokio::io::AsyncRead;
 
 use crate::client::ILazyClient;
+#[cfg(feature = "dgraph-24-02")]
+use crate::txn::check_predicate;
 use crate::txn::default::Base;
-use crate::txn::{IState, TxnState, TxnType, TxnVariant};
+use crate::txn::{IState, Query, TxnState, TxnType, TxnVariant};
+#[cfg(feature = "dgraph-24-02")]
+use crate::DgraphError;
 use crate::Request;
 
+#[derive(Deserialize)]
+struct Count {
+    count: u64,
+}
+
+#[derive(Deserialize)]
+struct CountBlock {
+    c: Vec<Count>,
+}
+
+#[cfg(feature = "dgraph-24-02")]
+#[derive(Deserialize)]
+#[serde(bound = "T: DeserializeOwned")]
+struct SimilarBlock<T> {
+    q: Vec<T>,
+}
+
+///
+/// Build query which returns `top_k` nodes with `predicate` vector nearest to `vector`.
+///
+/// Predicate must be valid name and all components of vector must be finite numbers, because
+/// they are put into query verbatim.
+///
+#[cfg(feature = "dgraph-24-02")]
+fn similar_to_query(
+    predicate: &str,
+    vector: &[f32],
+    top_k: usize,
+    block: &str,
+) -> Result<String, DgraphError> {
+    check_predicate(predicate)?;
+    if let Some(invalid) = vector.iter().find(|value| !value.is_finite()) {
+        return Err(DgraphError::InvalidVector(*invalid));
+    }
+    let vector = vector
+        .iter()
+        .map(|value| value.to_string())
+        .collect::<Vec<String>>()
+        .join(", ");
+    Ok(format!(
+        "{{ q(func: similar_to({}, {}, \"[{}]\")) {{ {} }} }}",
+        predicate, top_k, vector, block
+    ))
+}
+
 ///
 /// Inner state for read only transaction
 ///
 #[derive(Clone, Debug)]
 pub struct ReadOnly<C: ILazyClient> {
     base: Base<C>,
+    best_effort: bool,
+    stale_retry: bool,
 }
 
 impl<C: ILazyClient> IState for ReadOnly<C> {
@@ -26,13 +85,22 @@ impl<C: ILazyClient> IState for ReadOnly<C> {
     ) -> Request {
         let mut request = self.base.query_request(state, query, vars);
         request.read_only = true;
+        request.best_effort = self.best_effort;
         request
     }
+
+    fn stale_retry(&self) -> bool {
+        self.stale_retry
+    }
 }
 
 ///
 /// ReadOnly variant of transaction
 ///
+/// All queries of transaction read same snapshot. Start timestamp assigned by Dgraph to the first
+/// query is stored in transaction context and it is sent with every next query. Snapshot is
+/// changed only by stale retry, see `with_stale_retry`.
+///
 pub type TxnReadOnlyType<C> = TxnVariant<ReadOnly<C>, C>;
 
 impl<C: ILazyClient> TxnType<C> {
@@ -42,7 +110,286 @@ impl<C: ILazyClient> TxnType<C> {
     pub fn read_only(self) -> TxnReadOnlyType<C> {
         TxnVariant {
             state: self.state,
-            extra: ReadOnly { base: self.extra },
+            extra: ReadOnly {
+                base: self.extra,
+                best_effort: false,
+                stale_retry: false,
+            },
+        }
+    }
+}
+
+impl<C: ILazyClient> TxnReadOnlyType<C> {
+    ///
+    /// Return start timestamp which was assigned by Dgraph to the first query of transaction.
+    ///
+    /// Timestamp is `0` before any query was executed.
+    ///
+    pub fn last_start_ts(&self) -> u64 {
+        self.context.start_ts
+    }
+
+    ///
+    /// Enable or disable best effort mode for next queries of transaction.
+    ///
+    /// Unlike `best_effort()`, mode can be decided per query without creating new transaction.
+    ///
+    /// # Arguments
+    ///
+    /// - `best_effort`: if `true`, Dgraph Alpha tries to get timestamps from memory
+    ///
+    pub fn set_best_effort(&mut self, best_effort: bool) {
+        self.extra.best_effort = best_effort;
+    }
+
+    ///
+    /// Repeat query once with new read timestamp when Dgraph rejects too old timestamp.
+    ///
+    /// Long-lived read-only transaction can fail with `readTs: X less than minTs: Y` after old
+    /// snapshots were removed. With stale retry, failed query is sent again with fresh timestamp
+    /// and transaction continues on new snapshot. Results of queries before and after retry can
+    /// be therefore inconsistent.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use dgraph_tonic::Client;
+    ///
+    /// let client = Client::new("http://127.0.0.1:19080").expect("Dgraph client");
+    /// let txn = client.new_read_only_txn().with_stale_retry();
+    /// ```
+    ///
+    pub fn with_stale_retry(mut self) -> Self {
+        self.extra.stale_retry = true;
+        self
+    }
+
+    ///
+    /// Return number of nodes matched by root function.
+    ///
+    /// Query `{ c(func: <func_query>) { count(uid) } }` is built and executed.
+    ///
+    /// # Arguments
+    ///
+    /// - `func_query`: root function, e.g. `has(name)` or `eq(name, "Alice")`
+    ///
+    /// # Errors
+    ///
+    /// * gRPC error
+    /// * If transaction is not initialized properly, return `EmptyTxn` error.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use dgraph_tonic::Client;
+    /// #[cfg(feature = "acl")]
+    /// use dgraph_tonic::{AclClientType, LazyChannel};
+    ///
+    /// #[cfg(not(feature = "acl"))]
+    /// async fn client() -> Client {
+    ///     Client::new("http://127.0.0.1:19080").expect("Dgraph client")
+    /// }
+    ///
+    /// #[cfg(feature = "acl")]
+    /// async fn client() -> AclClientType<LazyChannel> {
+    ///     let default = Client::new("http://127.0.0.1:19080").unwrap();
+    ///     default.login("groot", "password").await.expect("Acl client")
+    /// }
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     let client = client().await;
+    ///     let mut txn = client.new_read_only_txn();
+    ///     let count = txn.count("has(name)").await.expect("Count");
+    ///     println!("{}", count);
+    /// }
+    /// ```
+    ///
+    pub async fn count(&mut self, func_query: &str) -> Result<u64> {
+        let query = format!("{{ c(func: {}) {{ count(uid) }} }}", func_query);
+        let block: CountBlock = self.query(query).await?.try_into_owned()?;
+        Ok(block.c.first().map(|c| c.count).unwrap_or(0))
+    }
+
+    ///
+    /// Return `top_k` nodes whose vector `predicate` is most similar to given `vector`.
+    ///
+    /// Query `{ q(func: similar_to(<predicate>, <top_k>, "[<vector>]")) { <block> } }` is built
+    /// and executed. Predicate must be of `float32vector` type with `hnsw` index.
+    ///
+    /// # Arguments
+    ///
+    /// - `predicate`: name of vector predicate
+    /// - `vector`: searched embedding
+    /// - `top_k`: maximal number of returned nodes
+    /// - `block`: selected predicates of returned nodes, e.g. `uid name`
+    ///
+    /// # Errors
+    ///
+    /// * gRPC error
+    /// * If transaction is not initialized properly, return `EmptyTxn` error.
+    /// * `InvalidPredicate` if predicate is empty or contains whitespace or reserved characters
+    /// * `InvalidVector` if component of vector is NaN or infinite
+    /// * `JsonDeserialize` if nodes cannot be deserialized into `T`
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use dgraph_tonic::Client;
+    /// use serde::Deserialize;
+    ///
+    /// #[derive(Deserialize, Debug)]
+    /// struct Product {
+    ///     uid: String,
+    ///     name: String,
+    /// }
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     let client = Client::new("http://127.0.0.1:19080").expect("Dgraph client");
+    ///     let mut txn = client.new_read_only_txn();
+    ///     let products: Vec<Product> = txn
+    ///         .similar_to("embedding", &[0.1, 0.2, 0.3], 5, "uid name")
+    ///         .await
+    ///         .expect("Products");
+    ///     println!("{:?}", products);
+    /// }
+    /// ```
+    ///
+    #[cfg(feature = "dgraph-24-02")]
+    pub async fn similar_to<T: DeserializeOwned>(
+        &mut self,
+        predicate: &str,
+        vector: &[f32],
+        top_k: usize,
+        block: &str,
+    ) -> Result<Vec<T>> {
+        let query = similar_to_query(predicate, vector, top_k, block)?;
+        let block: SimilarBlock<T> = self.query(query).await?.try_into_owned()?;
+        Ok(block.q)
+    }
+
+    ///
+    /// Run query and return JSON data of response as async reader.
+    ///
+    /// Reader can be passed into streaming JSON parser. Dgraph sends result of one query in one
+    /// message, so data are buffered by reader now, but they are not copied or parsed.
+    ///
+    /// # Arguments
+    ///
+    /// - `query`: GraphQL+- query
+    ///
+    /// # Errors
+    ///
+    /// * gRPC error
+    /// * If transaction is not initialized properly, return `EmptyTxn` error.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use dgraph_tonic::Client;
+    /// use tokio::io::AsyncReadExt;
+    /// #[cfg(feature = "acl")]
+    /// use dgraph_tonic::{AclClientType, LazyChannel};
+    ///
+    /// #[cfg(not(feature = "acl"))]
+    /// async fn client() -> Client {
+    ///     Client::new("http://127.0.0.1:19080").expect("Dgraph client")
+    /// }
+    ///
+    /// #[cfg(feature = "acl")]
+    /// async fn client() -> AclClientType<LazyChannel> {
+    ///     let default = Client::new("http://127.0.0.1:19080").unwrap();
+    ///     default.login("groot", "password").await.expect("Acl client")
+    /// }
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     let client = client().await;
+    ///     let mut txn = client.new_read_only_txn();
+    ///     let mut reader = txn
+    ///         .query_json_reader("{ all(func: has(name)) { uid name } }")
+    ///         .await
+    ///         .expect("Reader");
+    ///     let mut json = String::new();
+    ///     reader.read_to_string(&mut json).await.expect("JSON");
+    /// }
+    /// ```
+    ///
+    pub async fn query_json_reader<Q>(&mut self, query: Q) -> Result<impl AsyncRead + Send + Unpin>
+    where
+        Q: Into<String> + Send + Sync,
+    {
+        self.query_json_reader_with_vars(query, HashMap::<String, String>::with_capacity(0))
+            .await
+    }
+
+    ///
+    /// Run query with variables and return JSON data of response as async reader.
+    ///
+    /// # Arguments
+    ///
+    /// - `query`: GraphQL+- query
+    /// - `vars`: map of variables
+    ///
+    /// # Errors
+    ///
+    /// * gRPC error
+    /// * If transaction is not initialized properly, return `EmptyTxn` error.
+    ///
+    pub async fn query_json_reader_with_vars<Q, K, V>(
+        &mut self,
+        query: Q,
+        vars: HashMap<K, V>,
+    ) -> Result<impl AsyncRead + Send + Unpin>
+    where
+        Q: Into<String> + Send + Sync,
+        K: Into<String> + Send + Sync + Eq + Hash,
+        V: Into<String> + Send + Sync,
+    {
+        let response = self.query_with_vars(query, vars).await?;
+        Ok(Cursor::new(response.json))
+    }
+}
+
+#[cfg(all(test, feature = "dgraph-24-02"))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn similar_to_query_format() {
+        assert_eq!(
+            similar_to_query("embedding", &[0.5, -1.0, 2.25], 3, "uid name").expect("Query"),
+            r#"{ q(func: similar_to(embedding, 3, "[0.5, -1, 2.25]")) { uid name } }"#
+        );
+    }
+
+    #[test]
+    fn similar_to_query_invalid_predicate() {
+        for predicate in [
+            "",
+            "embedding, 3, \"[1]\")) { uid } a(func: has(b",
+            "a b",
+            "<a>",
+        ]
+        .iter()
+        {
+            match similar_to_query(predicate, &[1.0], 3, "uid") {
+                Err(DgraphError::InvalidPredicate(invalid)) => assert_eq!(&invalid, predicate),
+                other => panic!("unexpected {:?}", other),
+            }
+        }
+    }
+
+    #[test]
+    fn similar_to_query_invalid_vector() {
+        for invalid in [f32::NAN, f32::INFINITY, f32::NEG_INFINITY].iter() {
+            match similar_to_query("embedding", &[0.5, *invalid], 3, "uid") {
+                Err(DgraphError::InvalidVector(value)) => {
+                    assert_eq!(value.to_bits(), invalid.to_bits())
+                }
+                other => panic!("unexpected {:?}", other),
+            }
         }
     }
 }
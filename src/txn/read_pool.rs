@@ -0,0 +1,267 @@
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use anyhow::Result;
+use serde::de::DeserializeOwned;
+
+use crate::client::ILazyClient;
+use crate::txn::{run_query, IState, TxnReadOnlyType};
+use crate::{DgraphError, Response};
+
+///
+/// Start timestamp shared by all queries of pool until it is refreshed.
+///
+#[derive(Debug)]
+struct Snapshot {
+    refresh: Duration,
+    pinned: Mutex<Option<(u64, Instant)>>,
+}
+
+impl Snapshot {
+    fn new(refresh: Duration) -> Self {
+        Self {
+            refresh,
+            pinned: Mutex::new(None),
+        }
+    }
+
+    ///
+    /// Return pinned start timestamp or `0` when Dgraph should assign new one.
+    ///
+    fn start_ts(&self) -> u64 {
+        match *self.pinned.lock().expect("Snapshot") {
+            Some((start_ts, pinned_at)) if pinned_at.elapsed() < self.refresh => start_ts,
+            _ => 0,
+        }
+    }
+
+    ///
+    /// Pin timestamp used by query, if it differs from the one which was sent.
+    ///
+    fn update(&self, sent: u64, used: u64) {
+        if sent != used && used != 0 {
+            self.pinned
+                .lock()
+                .expect("Snapshot")
+                .replace((used, Instant::now()));
+        }
+    }
+}
+
+///
+/// Pool of read-only queries executed by one client endpoint.
+///
+/// Pool keeps prepared read-only transaction shared by all its queries. Only request and handle
+/// of client stub are created per query, so no endpoint selection nor new transaction state and
+/// context is needed. Pool can be cloned and shared between tasks, clones use same endpoint and
+/// same shared snapshot.
+///
+/// By default, every query reads latest data like query of new read-only transaction. In shared
+/// snapshot mode, see `with_shared_snapshot`, queries read same best effort snapshot until it is
+/// refreshed.
+///
+#[derive(Clone, Debug)]
+pub struct ReadPool<C: ILazyClient> {
+    txn: Arc<TxnReadOnlyType<C>>,
+    snapshot: Option<Arc<Snapshot>>,
+}
+
+impl<C: ILazyClient> ReadPool<C> {
+    ///
+    /// Create pool from prepared read-only transaction.
+    ///
+    pub(crate) fn new(txn: TxnReadOnlyType<C>) -> Self {
+        Self {
+            txn: Arc::new(txn),
+            snapshot: None,
+        }
+    }
+
+    ///
+    /// Share one best effort snapshot by all queries and refresh it periodically.
+    ///
+    /// Start timestamp assigned to the first query is reused by all queries of pool until
+    /// `refresh` elapses, then next query gets fresh timestamp. This avoids timestamp requests
+    /// to Dgraph Zero, which is useful for dashboard-style reads with high QPS.
+    ///
+    /// **Staleness:** queries do not see any data committed after snapshot was taken, so results
+    /// can be up to `refresh` old. Best effort reads can also miss the latest commits even on
+    /// fresh snapshot. When Dgraph rejects too old snapshot, query is repeated once with new
+    /// timestamp. Use default mode when reads must observe own writes.
+    ///
+    /// # Arguments
+    ///
+    /// - `refresh`: maximal age of shared snapshot
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use std::time::Duration;
+    /// use dgraph_tonic::Client;
+    ///
+    /// let client = Client::new("http://127.0.0.1:19080").expect("Dgraph client");
+    /// let pool = client.read_pool().with_shared_snapshot(Duration::from_secs(5));
+    /// ```
+    ///
+    pub fn with_shared_snapshot(mut self, refresh: Duration) -> Self {
+        let mut txn = TxnReadOnlyType::clone(&self.txn);
+        txn.set_best_effort(true);
+        self.txn = Arc::new(txn.with_stale_retry());
+        self.snapshot = Some(Arc::new(Snapshot::new(refresh)));
+        self
+    }
+
+    ///
+    /// Run query and deserialize JSON response into `T`.
+    ///
+    /// # Arguments
+    ///
+    /// - `query`: GraphQL+- query
+    ///
+    /// # Errors
+    ///
+    /// * gRPC error
+    /// * `JsonDeserialize` if response cannot be deserialized into `T`
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use dgraph_tonic::Client;
+    /// use serde::Deserialize;
+    /// #[cfg(feature = "acl")]
+    /// use dgraph_tonic::{AclClientType, LazyChannel};
+    ///
+    /// #[derive(Deserialize, Debug)]
+    /// struct Person {
+    ///     uid: String,
+    ///     name: String,
+    /// }
+    ///
+    /// #[derive(Deserialize, Debug)]
+    /// struct Persons {
+    ///     all: Vec<Person>,
+    /// }
+    ///
+    /// #[cfg(not(feature = "acl"))]
+    /// async fn client() -> Client {
+    ///     Client::new("http://127.0.0.1:19080").expect("Dgraph client")
+    /// }
+    ///
+    /// #[cfg(feature = "acl")]
+    /// async fn client() -> AclClientType<LazyChannel> {
+    ///     let default = Client::new("http://127.0.0.1:19080").unwrap();
+    ///     default.login("groot", "password").await.expect("Acl client")
+    /// }
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     let client = client().await;
+    ///     let pool = client.read_pool();
+    ///     let persons: Persons = pool
+    ///         .query("{ all(func: has(name)) { uid name } }")
+    ///         .await
+    ///         .expect("Persons");
+    ///     println!("{:?}", persons.all);
+    /// }
+    /// ```
+    ///
+    pub async fn query<Q, T>(&self, query: Q) -> Result<T>
+    where
+        Q: Into<String> + Send + Sync,
+        T: DeserializeOwned,
+    {
+        let response = self.query_raw(query).await?;
+        Ok(response.try_into_owned()?)
+    }
+
+    ///
+    /// Run query and return raw response.
+    ///
+    /// # Arguments
+    ///
+    /// - `query`: GraphQL+- query
+    ///
+    /// # Errors
+    ///
+    /// * gRPC error
+    ///
+    pub async fn query_raw<Q>(&self, query: Q) -> Result<Response>
+    where
+        Q: Into<String> + Send + Sync,
+    {
+        let start_ts = match &self.snapshot {
+            Some(snapshot) => snapshot.start_ts(),
+            None => 0,
+        };
+        let mut request =
+            self.txn
+                .extra
+                .query_request(&self.txn.state, query.into(), HashMap::new());
+        request.start_ts = start_ts;
+        let mut stub = self.txn.stub.clone();
+        let stale_retry = self.txn.extra.stale_retry();
+        let response = run_query(&mut stub, &mut request, stale_retry).await?;
+        let used = match response.txn.as_ref() {
+            Some(src) => src.start_ts,
+            None => anyhow::bail!(DgraphError::EmptyTxn),
+        };
+        if let Some(snapshot) = &self.snapshot {
+            snapshot.update(start_ts, used);
+        }
+        Ok(response)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::Snapshot;
+    use crate::txn::tests::recording_server;
+    use crate::Client;
+
+    #[test]
+    fn snapshot_refresh() {
+        let snapshot = Snapshot::new(Duration::from_millis(50));
+        assert_eq!(snapshot.start_ts(), 0);
+        snapshot.update(0, 10);
+        assert_eq!(snapshot.start_ts(), 10);
+        snapshot.update(10, 10);
+        snapshot.update(10, 0);
+        assert_eq!(snapshot.start_ts(), 10);
+        snapshot.update(10, 12);
+        assert_eq!(snapshot.start_ts(), 12);
+        std::thread::sleep(Duration::from_millis(60));
+        assert_eq!(snapshot.start_ts(), 0);
+    }
+
+    #[tokio::test]
+    async fn shared_snapshot() {
+        let (addr, received) = recording_server().await;
+        let client = Client::new(addr).unwrap();
+        let pool = client.read_pool();
+        let shared = pool.clone().with_shared_snapshot(Duration::from_secs(60));
+        let query = "{ q(func: has(name)) { uid } }";
+        for _ in 0..2 {
+            pool.query_raw(query).await.expect("Query");
+        }
+        for _ in 0..2 {
+            shared.clone().query_raw(query).await.expect("Shared query");
+        }
+        let received = received.lock().unwrap();
+        let sent: Vec<(u64, bool, bool)> = received
+            .iter()
+            .map(|request| (request.start_ts, request.read_only, request.best_effort))
+            .collect();
+        assert_eq!(
+            sent,
+            vec![
+                (0, true, false),
+                (0, true, false),
+                (0, true, true),
+                (3, true, true)
+            ]
+        );
+    }
+}
@@ -0,0 +1,51 @@
+///
+/// Value which can be passed as query variable.
+///
+/// Dgraph receives all variables as strings, so value is formatted into form which is parsed
+/// by Dgraph according to type of variable declared in query.
+///
+pub trait ToDgraphVar {
+    ///
+    /// Format value as query variable.
+    ///
+    fn to_dgraph_var(&self) -> String;
+}
+
+macro_rules! display_var {
+    ($($ty:ty),*) => {
+        $(
+            impl ToDgraphVar for $ty {
+                fn to_dgraph_var(&self) -> String {
+                    self.to_string()
+                }
+            }
+        )*
+    };
+}
+
+// `Display` of numbers and bools does not depend on locale and floats are never formatted
+// with exponent, so they are parsed by Dgraph without loss.
+display_var!(i8, i16, i32, i64, u8, u16, u32, u64, f32, f64, bool, str, String);
+
+impl<T: ToDgraphVar + ?Sized> ToDgraphVar for &T {
+    fn to_dgraph_var(&self) -> String {
+        (**self).to_dgraph_var()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn format_vars() {
+        assert_eq!(42i64.to_dgraph_var(), "42");
+        assert_eq!((-7i32).to_dgraph_var(), "-7");
+        assert_eq!(true.to_dgraph_var(), "true");
+        assert_eq!(false.to_dgraph_var(), "false");
+        assert_eq!(1.5f64.to_dgraph_var(), "1.5");
+        assert_eq!(1e21f64.to_dgraph_var(), "1000000000000000000000");
+        assert_eq!("Alice".to_dgraph_var(), "Alice");
+        assert_eq!(String::from("Bob").to_dgraph_var(), "Bob");
+    }
+}
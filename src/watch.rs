@@ -0,0 +1,269 @@
+use std::collections::{HashMap, HashSet};
+use std::time::Duration;
+
+use anyhow::Result;
+use async_stream::try_stream;
+use futures::stream::Stream;
+use serde::de::DeserializeOwned;
+use serde::Deserialize;
+use serde_json::Value;
+
+use crate::client::{ClientVariant, IClient};
+use crate::errors::DgraphError;
+use crate::stream::from_item;
+use crate::Query;
+
+#[derive(Deserialize)]
+struct Chunk {
+    items: Vec<Value>,
+}
+
+///
+/// Change of one node between two polls of watched query.
+///
+#[derive(Debug, Clone, PartialEq)]
+pub enum WatchEvent<T> {
+    ///
+    /// Node with new uid was returned by query.
+    ///
+    Added(T),
+    ///
+    /// Node was returned by previous poll, but its data are different now.
+    ///
+    Changed(T),
+    ///
+    /// Node with given uid is not returned by query anymore.
+    ///
+    Removed(String),
+}
+
+fn uid_of(item: &Value) -> Result<String> {
+    match item.get("uid").and_then(Value::as_str) {
+        Some(uid) => Ok(uid.to_string()),
+        None => anyhow::bail!(DgraphError::MissingUid),
+    }
+}
+
+impl<C: IClient> ClientVariant<C> {
+    ///
+    /// Watch changes of query result.
+    ///
+    /// Dgraph has no change feed, so this is **polling** helper, not a push notification. Query is
+    /// executed in new read-only transaction every `interval` and result is compared with result
+    /// of previous poll. Nodes are matched by `uid`, so every item must contain **uid** predicate.
+    /// Items must be returned in query block named **items**.
+    ///
+    /// First poll yields `Added` event for every returned node.
+    ///
+    /// # Arguments
+    ///
+    /// - `query`: GraphQL+- query segment.
+    /// - `interval`: delay between two polls
+    ///
+    /// # Errors
+    ///
+    /// * gRPC error
+    /// * `MissingUid` when some item has no uid
+    /// * item cannot be deserialized into `T`
+    ///
+    /// Stream is finished after first error.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use std::time::Duration;
+    /// use futures::pin_mut;
+    /// use futures::stream::StreamExt;
+    /// use dgraph_tonic::{Client, WatchEvent};
+    /// use serde::Deserialize;
+    ///
+    /// #[derive(Deserialize, Debug)]
+    /// struct Person {
+    ///   uid: String,
+    ///   name: String,
+    /// }
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     let query = r#"{
+    ///         items(func: has(name)) {
+    ///             uid
+    ///             name
+    ///         }
+    ///     }"#;
+    ///   let client = Client::new("http://127.0.0.1:19080").expect("Dgraph client");
+    ///   let stream = client.watch_query::<_, Person>(query, Duration::from_secs(1));
+    ///   pin_mut!(stream);
+    ///   while let Some(Ok(event)) = stream.next().await {
+    ///       match event {
+    ///           WatchEvent::Added(person) => println!("added {:?}", person),
+    ///           WatchEvent::Changed(person) => println!("changed {:?}", person),
+    ///           WatchEvent::Removed(uid) => println!("removed {}", uid),
+    ///       }
+    ///   }
+    /// }
+    /// ```
+    ///
+    pub fn watch_query<Q, T>(
+        &self,
+        query: Q,
+        interval: Duration,
+    ) -> impl Stream<Item = Result<WatchEvent<T>>>
+    where
+        Q: Into<String>,
+        T: DeserializeOwned,
+    {
+        let query = query.into();
+        let mut template = self.new_read_only_txn();
+        try_stream! {
+            let mut previous: Vec<(String, Value)> = Vec::new();
+            let mut first_poll = true;
+            loop {
+                if !first_poll {
+                    tokio::time::sleep(interval).await;
+                }
+                first_poll = false;
+                let mut txn = template.clone_and_reset();
+                let chunk: Chunk = txn.query(query.to_owned()).await?.try_into_owned()?;
+                let mut actual = Vec::with_capacity(chunk.items.len());
+                for item in chunk.items {
+                    actual.push((uid_of(&item)?, item));
+                }
+                let known: HashMap<&String, &Value> =
+                    previous.iter().map(|(uid, item)| (uid, item)).collect();
+                let mut events = Vec::new();
+                for (uid, item) in actual.iter() {
+                    match known.get(uid) {
+                        None => events.push(WatchEvent::Added(from_item(item)?)),
+                        Some(old) if *old != item => {
+                            events.push(WatchEvent::Changed(from_item(item)?))
+                        }
+                        _ => {}
+                    }
+                }
+                let actual_uids: HashSet<&String> = actual.iter().map(|(uid, _)| uid).collect();
+                for (uid, _) in previous.iter() {
+                    if !actual_uids.contains(uid) {
+                        events.push(WatchEvent::Removed(uid.to_owned()));
+                    }
+                }
+                for event in events {
+                    yield event;
+                }
+                previous = actual;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use futures::pin_mut;
+    use futures::stream::StreamExt;
+    use serde_derive::{Deserialize, Serialize};
+
+    use crate::client::Client;
+    #[cfg(feature = "acl")]
+    use crate::client::{AclClientType, LazyChannel};
+    use crate::{Mutate, Mutation, WatchEvent};
+
+    #[cfg(not(feature = "acl"))]
+    async fn client() -> Client {
+        Client::new("http://127.0.0.1:19080").expect("Dgraph client")
+    }
+
+    #[cfg(feature = "acl")]
+    async fn client() -> AclClientType<LazyChannel> {
+        let default = Client::new("http://127.0.0.1:19080").unwrap();
+        default
+            .login("groot", "password")
+            .await
+            .expect("Acl client")
+    }
+
+    #[derive(Serialize, Deserialize, Default, Debug, PartialEq)]
+    struct Car {
+        uid: String,
+        color: String,
+    }
+
+    #[tokio::test]
+    async fn watch_query() {
+        let client = client().await;
+        client.drop_all().await.expect("Data not dropped");
+        client
+            .set_schema("color: string @index(exact) .")
+            .await
+            .expect("Schema is not updated");
+        let txn = client.new_mutated_txn();
+        let mut mu = Mutation::new();
+        mu.set_set_json(&Car {
+            uid: "_:a".to_string(),
+            color: "A".to_string(),
+        })
+        .expect("Invalid JSON");
+        let response = txn.mutate_and_commit_now(mu).await.expect("Mutated");
+        let uid = response.uids.get("a").expect("uid").to_owned();
+        let stream = client.watch_query::<_, Car>(
+            r#"{
+                items(func: has(color)) {
+                    uid
+                    color
+                }
+            }"#,
+            Duration::from_millis(100),
+        );
+        pin_mut!(stream);
+        let added = stream.next().await.expect("event").expect("added");
+        assert!(matches!(added, WatchEvent::Added(Car { color, .. }) if color == "A"));
+        let txn = client.new_mutated_txn();
+        let mut mu = Mutation::new();
+        mu.set_set_json(&Car {
+            uid: uid.to_owned(),
+            color: "B".to_string(),
+        })
+        .expect("Invalid JSON");
+        txn.mutate_and_commit_now(mu).await.expect("Mutated");
+        let changed = stream.next().await.expect("event").expect("changed");
+        assert_eq!(
+            changed,
+            WatchEvent::Changed(Car {
+                uid,
+                color: "B".to_string()
+            })
+        );
+    }
+
+    #[tokio::test]
+    async fn watch_query_invalid_item() {
+        use crate::errors::DgraphError;
+        use crate::fake_server::{serve, FakeReply};
+        use crate::{Response, TxnContext};
+
+        let response = Response {
+            json: br#"{"items": [{"uid": "0x1", "color": 1}]}"#.to_vec(),
+            txn: Some(TxnContext {
+                start_ts: 1,
+                ..Default::default()
+            }),
+            ..Default::default()
+        };
+        let (addr, server) = serve(move |_| FakeReply::new(&response)).await;
+        let client = Client::new(addr).expect("Dgraph client");
+        let stream =
+            client.watch_query::<_, Car>("{ items(func: uid(0x1)) { uid color } }", Duration::ZERO);
+        pin_mut!(stream);
+        let err = stream
+            .next()
+            .await
+            .expect("event")
+            .expect_err("invalid item");
+        assert!(matches!(
+            err.downcast_ref::<DgraphError>(),
+            Some(DgraphError::JsonDeserialize { raw_len: 23, .. })
+        ));
+        server.abort();
+    }
+}